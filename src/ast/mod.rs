@@ -1,6 +1,6 @@
 use crate::tokenizer::Token;
 
-use self::{nodes::AstNode};
+use self::nodes::AstNode;
 
 mod parser;
 mod parsers;
@@ -9,22 +9,23 @@ pub mod nodes;
 #[derive(Debug)]
 pub struct AstProgram {
     pub body: Vec<AstNode>,
+    // Modules that await at the top level are evaluated asynchronously
+    pub has_top_level_await: bool,
 }
 
 impl AstProgram {
     pub fn new() -> AstProgram {
         Self {
             body: Vec::new(),
-        }
-    }
-
-    pub fn from_body(body: &Vec<AstNode>) -> AstProgram {
-        Self {
-            body: body.clone(),
+            has_top_level_await: false,
         }
     }
 }
 
-pub fn parse(tokens: &Vec<Token>) -> AstProgram {
+pub fn parse(tokens: &[Token]) -> AstProgram {
     parser::parse(tokens)
-}
\ No newline at end of file
+}
+
+pub fn parse_module(tokens: &[Token]) -> AstProgram {
+    parser::parse_module(tokens)
+}
@@ -1,4 +1,4 @@
-use crate::tokenizer::TokenType;
+use num_bigint::BigInt;
 
 #[derive(Debug, Clone)]
 pub struct Identifier {
@@ -7,56 +7,132 @@ pub struct Identifier {
 }
 
 #[derive(Debug, Clone)]
-pub struct Literal {
+pub struct NumericLiteral {
+    pub value: f64,
+    pub raw: String,
+    pub range: (usize, usize)
+}
+
+#[derive(Debug, Clone)]
+pub struct StringLiteral {
     pub value: String,
-    pub raw_value: String,
+    pub raw: String,
     pub range: (usize, usize)
 }
 
-impl Literal {
-    pub fn new() -> Self {
-        Self {
-            value: "".to_string(),
-            raw_value: "".to_string(),
-            range: (0,0)
-        }
-    }
+#[derive(Debug, Clone)]
+pub struct BooleanLiteral {
+    pub value: bool,
+    pub raw: String,
+    pub range: (usize, usize)
+}
 
-    pub fn from_str(str: &str) -> Self {
-        Self {
-            value: str.to_string(),
-            raw_value: str.to_string(),
-            range: (0,0)
-        }
-    }
+#[derive(Debug, Clone)]
+pub struct NullLiteral {
+    pub raw: String,
+    pub range: (usize, usize)
 }
 
 #[derive(Debug, Clone)]
-pub struct BinaryLiteral { // TODO: Change this to a binary literal
-    pub value: String,
-    pub raw_value: String,
+pub struct BigIntLiteral {
+    pub value: BigInt,
+    pub raw: String,
     pub range: (usize, usize)
 }
 
 #[derive(Debug, Clone)]
-pub enum VariableLiteral {
-    Literal(Literal),
-    BinaryLiteral(BinaryLiteral)
+pub struct RegExpLiteral {
+    pub pattern: String,
+    pub flags: String,
+    pub raw: String,
+    pub range: (usize, usize)
 }
 
 #[derive(Debug, Clone)]
 pub struct VariableDeclarator {
-    pub id: Identifier,
-    pub init: VariableLiteral,
-    pub range: (usize, usize)
+    pub id: Pattern,
+    pub init: Option<Expression>,
 }
 
 #[derive(Debug, Clone)]
 pub struct VariableDeclaration {
+    pub kind: VariableKind,
     pub declarations: Vec<VariableDeclarator>,
     pub range: (usize, usize)
 }
 
+// `let` and `const` are scoped to their block and cannot be used before their declaration runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariableKind {
+    Var,
+    Let,
+    Const,
+}
+
+// What a declaration, a parameter or an assignment binds its value to, ESTree's Pattern
+#[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum Pattern {
+    Identifier(Identifier),
+    // Only in assignments, `[a.b, c[0]] = list`
+    MemberExpression(Box<MemberExpression>),
+    ObjectPattern(Box<ObjectPattern>),
+    ArrayPattern(Box<ArrayPattern>),
+    // `target = default`, the default is used when the value is undefined
+    AssignmentPattern(Box<AssignmentPattern>),
+    // `...target`, the last parameter or array element takes the rest of the values
+    RestElement(Box<RestElement>),
+}
+
+impl Pattern {
+    pub fn range(&self) -> (usize, usize) {
+        match self {
+            Pattern::Identifier(identifier) => identifier.range,
+            Pattern::MemberExpression(member) => member.range,
+            Pattern::ObjectPattern(pattern) => pattern.range,
+            Pattern::ArrayPattern(pattern) => pattern.range,
+            Pattern::AssignmentPattern(pattern) => pattern.range,
+            Pattern::RestElement(rest) => rest.range,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ObjectPattern {
+    pub properties: Vec<AssignmentProperty>,
+    // `...rest` gets the properties the pattern did not name, it can only come last
+    pub rest: Option<Box<RestElement>>,
+    pub range: (usize, usize),
+}
+
+// `key: target` or the shorthand `name`, which binds the property to a variable of the same name
+#[derive(Debug, Clone)]
+pub struct AssignmentProperty {
+    pub key: Expression,
+    pub value: Pattern,
+    pub computed: bool,
+}
+
+// A None element is a hole, `[, second] = list` skips the first value
+#[derive(Debug, Clone)]
+pub struct ArrayPattern {
+    pub elements: Vec<Option<Pattern>>,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct AssignmentPattern {
+    pub left: Pattern,
+    pub right: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct RestElement {
+    pub argument: Pattern,
+    pub range: (usize, usize),
+}
+
 #[derive(Debug, Clone)]
 pub struct BlockStatement {
     pub body: Vec<AstNode>,
@@ -66,46 +142,469 @@ pub struct BlockStatement {
 #[derive(Debug, Clone)]
 pub struct FunctionDeclaration {
     pub id: Identifier,
-    pub params: Vec<Identifier>,
+    pub params: Vec<Pattern>,
     pub body: BlockStatement,
+    // `function*`, calling it returns a generator that runs the body as `next` is called
+    pub generator: bool,
+    // `async function`, calling it returns a promise for the result and the body can `await`
+    pub is_async: bool,
+    pub range: (usize, usize)
+}
+
+// Also the value of object and class methods, which never have an id
+#[derive(Debug, Clone)]
+pub struct FunctionExpression {
+    pub id: Option<Identifier>,
+    pub params: Vec<Pattern>,
+    pub body: BlockStatement,
+    pub generator: bool,
+    pub is_async: bool,
+    pub range: (usize, usize)
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrowFunctionExpression {
+    pub params: Vec<Pattern>,
+    pub body: ArrowFunctionBody,
+    pub is_async: bool,
+    pub range: (usize, usize)
+}
+
+#[derive(Debug, Clone)]
+pub enum ArrowFunctionBody {
+    BlockStatement(BlockStatement),
+    // `x => x * 2`, the value of the expression is returned
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone)]
+pub struct ClassDeclaration {
+    pub id: Identifier,
+    pub super_class: Option<Expression>,
+    pub body: ClassBody,
+    pub range: (usize, usize)
+}
+
+#[derive(Debug, Clone)]
+pub struct ClassExpression {
+    pub id: Option<Identifier>,
+    pub super_class: Option<Expression>,
+    pub body: ClassBody,
+    pub range: (usize, usize)
+}
+
+#[derive(Debug, Clone)]
+pub struct ClassBody {
+    pub body: Vec<ClassElement>,
+    pub range: (usize, usize)
+}
+
+#[derive(Debug, Clone)]
+pub enum ClassElement {
+    MethodDefinition(MethodDefinition),
+    PropertyDefinition(PropertyDefinition),
+    StaticBlock(StaticBlock),
+}
+
+// The key is a PrivateIdentifier for `#name() {}`
+#[derive(Debug, Clone)]
+pub struct MethodDefinition {
+    pub key: Expression,
+    pub value: FunctionExpression,
+    pub kind: MethodKind,
+    pub computed: bool,
+    pub is_static: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MethodKind {
+    Constructor,
+    Method,
+    Get,
+    Set,
+}
+
+// A class field, `x = 1;` or `static #count;`
+#[derive(Debug, Clone)]
+pub struct PropertyDefinition {
+    pub key: Expression,
+    pub value: Option<Expression>,
+    pub computed: bool,
+    pub is_static: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct StaticBlock {
+    pub body: Vec<AstNode>,
     pub range: (usize, usize)
 }
 
+// `#name`, as a class element key or after a dot
+#[derive(Debug, Clone)]
+pub struct PrivateIdentifier {
+    pub name: String,
+    pub range: (usize, usize)
+}
+
+// Only valid as `super(...)`, `super.name` or `super[name]`
+#[derive(Debug, Clone)]
+pub struct Super {
+    pub range: (usize, usize)
+}
+
+#[derive(Debug, Clone)]
+pub struct ObjectExpression {
+    pub properties: Vec<ObjectMember>,
+    pub range: (usize, usize),
+}
+
+// `{ ...value }` copies the own enumerable properties of the value
+#[derive(Debug, Clone)]
+pub enum ObjectMember {
+    Property(Property),
+    SpreadElement(SpreadElement),
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrayExpression {
+    // None for a hole, like the middle of `[1, , 3]`
+    pub elements: Vec<Option<Expression>>,
+    pub range: (usize, usize),
+}
+
+// `...value`, only parsed where a list of values or properties is expected
+#[derive(Debug, Clone)]
+pub struct SpreadElement {
+    pub argument: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Property {
+    pub key: Expression,
+    pub value: Expression,
+    pub kind: PropertyKind,
+    pub computed: bool,
+    pub method: bool,
+    pub shorthand: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyKind {
+    Init,
+    Get,
+    Set,
+}
+
 #[derive(Debug, Clone)]
 pub struct CallExpression {
-    pub callee: CallExpressionCallee,
-    pub arguments: Vec<Literal>,
+    pub callee: Expression,
+    pub arguments: Vec<Expression>,
+    // `f?.()`, skips the call when the callee is null or undefined
+    pub optional: bool,
     pub range: (usize, usize),
 }
 
 #[derive(Debug, Clone)]
-pub enum CallExpressionCallee {
-    Identifier(Box<Identifier>),
-    MemberExpression(Box<MemberExpression>),
+pub struct NewExpression {
+    pub callee: Expression,
+    pub arguments: Vec<Expression>,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct ThisExpression {
+    pub range: (usize, usize),
 }
 
 #[derive(Debug, Clone)]
 pub struct MemberExpression {
-    pub object: Option<Box<MemberExpression>>,
-    pub property: Identifier,
+    pub object: Expression,
+    // An Identifier for `a.b`, any expression for `a[b]`
+    pub property: Expression,
+    pub computed: bool,
+    // `a?.b` and `a?.[b]`
+    pub optional: bool,
+    pub range: (usize, usize),
+}
+
+// The whole of `a?.b.c()`, an optional link that meets null or undefined makes all of it undefined
+#[derive(Debug, Clone)]
+pub struct ChainExpression {
+    pub expression: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct BinaryExpression {
+    pub operator: String,
+    pub left: Expression,
+    pub right: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct LogicalExpression {
+    pub operator: String,
+    pub left: Expression,
+    pub right: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct UnaryExpression {
+    pub operator: String,
+    pub argument: Expression,
     pub range: (usize, usize),
 }
 
-impl MemberExpression {
-    pub fn new(property: Identifier) -> Self {
-        let range = property.range.clone();
-        
-        Self {
-            object: None,
-            property,
-            range
+#[derive(Debug, Clone)]
+pub struct UpdateExpression {
+    pub operator: String,
+    pub prefix: bool,
+    pub argument: Expression,
+    pub range: (usize, usize),
+}
+
+// `yield value` and `yield* iterable`, only inside generators
+#[derive(Debug, Clone)]
+pub struct YieldExpression {
+    pub argument: Option<Expression>,
+    // `yield*` passes on every value of the iterable, and whatever the generator is resumed with
+    pub delegate: bool,
+    // In an async generator, which awaits the values it hands out
+    pub is_async: bool,
+    pub range: (usize, usize),
+}
+
+// `await value`, only inside async functions
+#[derive(Debug, Clone)]
+pub struct AwaitExpression {
+    pub argument: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct AssignmentExpression {
+    pub operator: String,
+    // Only `=` takes an object or array pattern, the other operators assign to a variable or a property
+    pub left: Pattern,
+    pub right: Expression,
+    pub range: (usize, usize),
+}
+
+// Variants are named after the ESTree node types they hold
+#[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum Expression {
+    NumericLiteral(NumericLiteral),
+    StringLiteral(StringLiteral),
+    BooleanLiteral(BooleanLiteral),
+    NullLiteral(NullLiteral),
+    BigIntLiteral(BigIntLiteral),
+    RegExpLiteral(RegExpLiteral),
+    Identifier(Identifier),
+    PrivateIdentifier(PrivateIdentifier),
+    ThisExpression(ThisExpression),
+    Super(Super),
+    ObjectExpression(Box<ObjectExpression>),
+    ArrayExpression(Box<ArrayExpression>),
+    SpreadElement(Box<SpreadElement>),
+    FunctionExpression(Box<FunctionExpression>),
+    ArrowFunctionExpression(Box<ArrowFunctionExpression>),
+    ClassExpression(Box<ClassExpression>),
+    CallExpression(Box<CallExpression>),
+    NewExpression(Box<NewExpression>),
+    MemberExpression(Box<MemberExpression>),
+    ChainExpression(Box<ChainExpression>),
+    BinaryExpression(Box<BinaryExpression>),
+    LogicalExpression(Box<LogicalExpression>),
+    UnaryExpression(Box<UnaryExpression>),
+    UpdateExpression(Box<UpdateExpression>),
+    AssignmentExpression(Box<AssignmentExpression>),
+    YieldExpression(Box<YieldExpression>),
+    AwaitExpression(Box<AwaitExpression>),
+    ImportExpression(Box<ImportExpression>),
+    ImportMeta(ImportMeta),
+}
+
+impl Expression {
+    pub fn range(&self) -> (usize, usize) {
+        match self {
+            Expression::NumericLiteral(literal) => literal.range,
+            Expression::StringLiteral(literal) => literal.range,
+            Expression::BooleanLiteral(literal) => literal.range,
+            Expression::NullLiteral(literal) => literal.range,
+            Expression::BigIntLiteral(literal) => literal.range,
+            Expression::RegExpLiteral(literal) => literal.range,
+            Expression::Identifier(identifier) => identifier.range,
+            Expression::PrivateIdentifier(identifier) => identifier.range,
+            Expression::ObjectExpression(object) => object.range,
+            Expression::ArrayExpression(array) => array.range,
+            Expression::SpreadElement(spread) => spread.range,
+            Expression::FunctionExpression(function) => function.range,
+            Expression::ArrowFunctionExpression(function) => function.range,
+            Expression::ClassExpression(class) => class.range,
+            Expression::ThisExpression(this) => this.range,
+            Expression::Super(keyword) => keyword.range,
+            Expression::CallExpression(call) => call.range,
+            Expression::NewExpression(new) => new.range,
+            Expression::MemberExpression(member) => member.range,
+            Expression::ChainExpression(chain) => chain.range,
+            Expression::BinaryExpression(binary) => binary.range,
+            Expression::LogicalExpression(logical) => logical.range,
+            Expression::UnaryExpression(unary) => unary.range,
+            Expression::UpdateExpression(update) => update.range,
+            Expression::AssignmentExpression(assignment) => assignment.range,
+            Expression::YieldExpression(expression) => expression.range,
+            Expression::AwaitExpression(expression) => expression.range,
+            Expression::ImportExpression(expression) => expression.range,
+            Expression::ImportMeta(meta) => meta.range,
         }
     }
 }
 
+// `import(specifier)`, loads a module and gives a promise for its namespace object
+#[derive(Debug, Clone)]
+pub struct ImportExpression {
+    pub source: Expression,
+    pub range: (usize, usize),
+}
+
+// `import.meta`, only in modules
+#[derive(Debug, Clone)]
+pub struct ImportMeta {
+    pub range: (usize, usize),
+}
+
 #[derive(Debug, Clone)]
 pub struct ExpressionStatement {
-    pub expression: CallExpression,
+    pub expression: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct ReturnStatement {
+    pub argument: Option<Expression>,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct IfStatement {
+    pub test: Expression,
+    pub consequent: Box<AstNode>,
+    pub alternate: Option<Box<AstNode>>,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct WhileStatement {
+    pub test: Expression,
+    pub body: Box<AstNode>,
+    pub range: (usize, usize),
+}
+
+// `for (const value of iterable)`, the left side is a declaration when `kind` is set and an assignment target otherwise
+#[derive(Debug, Clone)]
+pub struct ForOfStatement {
+    pub kind: Option<VariableKind>,
+    // `for await`, which steps an async iterator and awaits the values of a sync one
+    pub is_await: bool,
+    pub left: Pattern,
+    pub right: Expression,
+    pub body: Box<AstNode>,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct ThrowStatement {
+    pub argument: Expression,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct TryStatement {
+    pub block: BlockStatement,
+    pub handler: Option<CatchClause>,
+    pub finalizer: Option<BlockStatement>,
+    pub range: (usize, usize),
+}
+
+// `catch (error) { ... }`, the binding is optional
+#[derive(Debug, Clone)]
+pub struct CatchClause {
+    pub param: Option<Pattern>,
+    pub body: BlockStatement,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct BreakStatement {
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct ContinueStatement {
+    pub range: (usize, usize),
+}
+
+// `import x, { a as b } from "./module.js"`, `import * as ns from "./module.js"` or just `import "./module.js"`
+#[derive(Debug, Clone)]
+pub struct ImportDeclaration {
+    pub specifiers: Vec<ImportSpecifier>,
+    pub source: StringLiteral,
+    pub range: (usize, usize),
+}
+
+// The names an import binds, each is a live view of the export it names
+#[derive(Debug, Clone)]
+pub enum ImportSpecifier {
+    // `import x from`, the `default` export
+    Default(Identifier),
+    // `import * as ns from`, the namespace object of the module
+    Namespace(Identifier),
+    // `import { imported as local } from`, the imported name can be a string
+    Named { imported: String, local: Identifier },
+}
+
+// `export const x = 1`, `export { a as b }` or `export { a as b } from "./module.js"`
+#[derive(Debug, Clone)]
+pub struct ExportNamedDeclaration {
+    pub declaration: Option<Box<AstNode>>,
+    pub specifiers: Vec<ExportSpecifier>,
+    // A re-export, the local names are exports of that module
+    pub source: Option<StringLiteral>,
+    pub range: (usize, usize),
+}
+
+// `local as exported`, either name can be a string
+#[derive(Debug, Clone)]
+pub struct ExportSpecifier {
+    pub local: String,
+    pub exported: String,
+    pub range: (usize, usize),
+}
+
+// `export default`, an anonymous function or class declaration is named `default`
+#[derive(Debug, Clone)]
+pub struct ExportDefaultDeclaration {
+    pub declaration: ExportDefaultKind,
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub enum ExportDefaultKind {
+    // A function or class declaration
+    Declaration(Box<AstNode>),
+    // Bound to `default`, a name no code in the module can refer to
+    Expression(Expression),
+}
+
+// `export * from "./module.js"` or `export * as ns from "./module.js"`
+#[derive(Debug, Clone)]
+pub struct ExportAllDeclaration {
+    pub exported: Option<String>,
+    pub source: StringLiteral,
     pub range: (usize, usize),
 }
 
@@ -114,5 +613,42 @@ pub enum AstNode {
     VariableDeclaration(VariableDeclaration),
     BlockStatement(BlockStatement),
     FunctionDeclaration(FunctionDeclaration),
+    ClassDeclaration(ClassDeclaration),
     ExpressionStatement(ExpressionStatement),
-}
\ No newline at end of file
+    ReturnStatement(ReturnStatement),
+    IfStatement(IfStatement),
+    WhileStatement(WhileStatement),
+    ForOfStatement(ForOfStatement),
+    ThrowStatement(ThrowStatement),
+    TryStatement(TryStatement),
+    BreakStatement(BreakStatement),
+    ContinueStatement(ContinueStatement),
+    ImportDeclaration(ImportDeclaration),
+    ExportNamedDeclaration(ExportNamedDeclaration),
+    ExportDefaultDeclaration(ExportDefaultDeclaration),
+    ExportAllDeclaration(ExportAllDeclaration),
+}
+
+impl AstNode {
+    pub fn range(&self) -> (usize, usize) {
+        match self {
+            AstNode::VariableDeclaration(node) => node.range,
+            AstNode::BlockStatement(node) => node.range,
+            AstNode::FunctionDeclaration(node) => node.range,
+            AstNode::ClassDeclaration(node) => node.range,
+            AstNode::ExpressionStatement(node) => node.range,
+            AstNode::ReturnStatement(node) => node.range,
+            AstNode::IfStatement(node) => node.range,
+            AstNode::WhileStatement(node) => node.range,
+            AstNode::ForOfStatement(node) => node.range,
+            AstNode::ThrowStatement(node) => node.range,
+            AstNode::TryStatement(node) => node.range,
+            AstNode::BreakStatement(node) => node.range,
+            AstNode::ContinueStatement(node) => node.range,
+            AstNode::ImportDeclaration(node) => node.range,
+            AstNode::ExportNamedDeclaration(node) => node.range,
+            AstNode::ExportDefaultDeclaration(node) => node.range,
+            AstNode::ExportAllDeclaration(node) => node.range,
+        }
+    }
+}
@@ -1,19 +1,31 @@
-use core::panic;
-
 use crate::tokenizer::Token;
 
-use super::{AstProgram, nodes::AstNode, parsers::{is_expression_statement, is_start_block_statement, is_start_function_declaration, is_variable_declaration, parse_block_statement, parse_expression_statement, parse_function_declaration, parse_variable_declaration}};
+use super::{AstProgram, parsers::{parse_module_item, parse_statement}};
 
 pub struct AstParser<'a> {
     index: usize,
-    tokens: &'a Vec<Token>,
+    tokens: &'a [Token],
+    // `yield` is an expression inside generator bodies and a name everywhere else
+    pub in_generator: bool,
+    // The same for `await` and async function bodies, and the top level of a module
+    pub in_async: bool,
+    // `import.meta` is only valid in modules, and so are imports and exports at the top level
+    pub in_module: bool,
+    // Whether an `await` or `for await` outside any function makes the module async
+    pub in_function: bool,
+    pub has_top_level_await: bool,
 }
 
 impl<'a> AstParser<'a> {
-    pub fn new(tokens: &'a Vec<Token>) -> AstParser {
+    pub fn new(tokens: &'a [Token]) -> AstParser<'a> {
         AstParser {
             index: 0,
-            tokens
+            tokens,
+            in_generator: false,
+            in_async: false,
+            in_module: false,
+            in_function: false,
+            has_top_level_await: false,
         }
     }
 
@@ -25,81 +37,52 @@ impl<'a> AstParser<'a> {
         self.tokens.get(self.index)
     }
 
-    pub fn peek(&self) -> Option<&Token> {
-        self.tokens.get(self.index + 1)
-    }
-
-    pub fn peek_current(&self) -> Option<&Token> {
-        self.tokens.get(self.index)
-    }
-
     pub fn peek_steps(&self, steps: usize) -> Option<&Token> {
         self.tokens.get(self.index + steps)
     }
 
-    pub fn peek_back(&self, steps: usize) -> Option<&Token> {
-        self.tokens.get(self.index - steps)
-    }
-
     pub fn step(&mut self) {
         self.index += 1;
     }
 
-    pub fn step_back(&mut self) {
-        self.index -= 1;
-    }
-
     pub fn consume(&mut self) -> Option<&Token> {
         let value = self.tokens.get(self.index);
-        
+
         self.index += 1;
 
-        return value;
+        value
     }
 }
 
-pub fn parse(tokens: &Vec<Token>) -> AstProgram {
+pub fn parse(tokens: &[Token]) -> AstProgram {
     let mut program = AstProgram::new();
     let mut parser = AstParser::new(tokens);
 
     while parser.has_tokens() {
-
-        let token = parser.token().unwrap();
-
-        if is_start_block_statement(token) {
-            let block_statement = parse_block_statement(&mut parser);
-            let value = AstNode::BlockStatement(block_statement.unwrap());
-            program.body.push(value);
-
-            continue;
+        match parse_statement(&mut parser) {
+            Some(statement) => program.body.push(statement),
+            None => panic!("Unknown token: {:?}", parser.token().unwrap())
         }
+    }
 
-        if is_start_function_declaration(&parser) {
-            let function_declaration = parse_function_declaration(&mut parser);
-            let value = AstNode::FunctionDeclaration(function_declaration.unwrap());
-            program.body.push(value);
-
-            continue;
-        }
+    program
+}
 
-        if is_variable_declaration(&parser) {
-            let variable_declaration = parse_variable_declaration(&mut parser);
-            let value = AstNode::VariableDeclaration(variable_declaration.unwrap());
-            program.body.push(value);
-            
-            continue;
-        }
+// Module code is strict, and `await` can be used at the top level
+pub fn parse_module(tokens: &[Token]) -> AstProgram {
+    let mut program = AstProgram::new();
+    let mut parser = AstParser::new(tokens);
 
-        if is_expression_statement(&parser) {
-            let expression_statement = parse_expression_statement(&mut parser);
-            let value = AstNode::ExpressionStatement(expression_statement.unwrap());
-            program.body.push(value);
+    parser.in_module = true;
+    parser.in_async = true;
 
-            continue;
+    while parser.has_tokens() {
+        match parse_module_item(&mut parser) {
+            Some(statement) => program.body.push(statement),
+            None => panic!("Unknown token: {:?}", parser.token().unwrap())
         }
-
-        panic!("Unknown token: {:?}", parser.token().unwrap().clone());
     }
 
+    program.has_top_level_await = parser.has_top_level_await;
     program
-}
\ No newline at end of file
+}
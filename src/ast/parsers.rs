@@ -1,478 +1,2531 @@
-use std::{cell::RefCell, collections::VecDeque};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 
-use crate::tokenizer::{Token, TokenType};
+use crate::{tokenizer::{Token, TokenType}, util::{is_identifier, is_identifier_part}};
 
-use super::{nodes::AstNode, nodes::BlockStatement, nodes::CallExpression, nodes::ExpressionStatement, nodes::FunctionDeclaration, nodes::Identifier, nodes::Literal, nodes::VariableDeclaration, nodes::VariableDeclarator, nodes::{CallExpressionCallee, MemberExpression, VariableLiteral}, parser::AstParser};
+use super::{nodes::AstNode, nodes::{BigIntLiteral, BooleanLiteral, NullLiteral, NumericLiteral, RegExpLiteral, StringLiteral}, nodes::BlockStatement, nodes::BreakStatement, nodes::CallExpression, nodes::ContinueStatement, nodes::Expression, nodes::ExpressionStatement, nodes::FunctionDeclaration, nodes::FunctionExpression, nodes::Identifier, nodes::{ArrayExpression, ArrowFunctionBody, ArrowFunctionExpression, ClassBody, ClassDeclaration, ClassElement, ClassExpression, MethodDefinition, MethodKind, PrivateIdentifier, PropertyDefinition, StaticBlock, Super, NewExpression, ObjectExpression, ObjectMember, Property, PropertyKind, SpreadElement, ThisExpression}, nodes::IfStatement, nodes::ReturnStatement, nodes::{CatchClause, ThrowStatement, TryStatement}, nodes::{VariableDeclaration, VariableDeclarator, VariableKind}, nodes::{ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern, Pattern, RestElement}, nodes::{ForOfStatement, WhileStatement}, nodes::{ExportAllDeclaration, ExportDefaultDeclaration, ExportDefaultKind, ExportNamedDeclaration, ExportSpecifier, ImportDeclaration, ImportExpression, ImportMeta, ImportSpecifier}, nodes::{AssignmentExpression, BinaryExpression, ChainExpression, LogicalExpression, MemberExpression, UnaryExpression, UpdateExpression, AwaitExpression, YieldExpression}, parser::AstParser};
+
+/*
+Statement
+*/
+pub fn parse_statement(parser: &mut AstParser) -> Option<AstNode> {
+    let token = parser.token()?;
+
+    // The body of a module parses its imports and exports before it gets here
+    if is_import_declaration(parser) || is_keyword(token, "export") {
+        match parser.in_module {
+            true => panic!("Imports and exports can only appear at the top level of a module, found {:?}", token),
+            false => panic!("Cannot use import and export statements outside a module, found {:?}", token),
+        }
+    }
+
+    if is_start_block_statement(token) {
+        return parse_block_statement(parser).map(AstNode::BlockStatement);
+    }
+
+    if is_start_function_declaration(parser) {
+        return parse_function_declaration(parser).map(AstNode::FunctionDeclaration);
+    }
+
+    if is_class_declaration(parser) {
+        return parse_class_declaration(parser).map(AstNode::ClassDeclaration);
+    }
+
+    if is_variable_declaration(parser) {
+        return parse_variable_declaration(parser).map(AstNode::VariableDeclaration);
+    }
+
+    if is_return_statement(parser) {
+        return parse_return_statement(parser).map(AstNode::ReturnStatement);
+    }
+
+    if is_if_statement(parser) {
+        return parse_if_statement(parser).map(AstNode::IfStatement);
+    }
+
+    if is_while_statement(parser) {
+        return parse_while_statement(parser).map(AstNode::WhileStatement);
+    }
+
+    if is_for_of_statement(parser) {
+        return parse_for_of_statement(parser).map(AstNode::ForOfStatement);
+    }
+
+    if is_throw_statement(parser) {
+        return parse_throw_statement(parser).map(AstNode::ThrowStatement);
+    }
+
+    if is_try_statement(parser) {
+        return parse_try_statement(parser).map(AstNode::TryStatement);
+    }
+
+    if is_break_statement(parser) {
+        return parse_break_statement(parser).map(AstNode::BreakStatement);
+    }
+
+    if is_continue_statement(parser) {
+        return parse_continue_statement(parser).map(AstNode::ContinueStatement);
+    }
+
+    if is_expression_statement(parser) {
+        return parse_expression_statement(parser).map(AstNode::ExpressionStatement);
+    }
+
+    None
+}
+
+fn is_keyword(token: &Token, keyword: &str) -> bool {
+    token.token_type == TokenType::Identifier && token.value == keyword
+}
+
+fn is_reserved_word(token: &Token) -> bool {
+    matches!(
+        token.value.as_str(),
+        "var" | "function" | "return" | "if" | "else" | "while" | "for" | "break" | "continue" | "new" | "this" | "instanceof" |
+        "class" | "extends" | "super" | "throw" | "try" | "catch" | "finally" | "const" | "typeof" | "void" | "delete" | "in" |
+        "import" | "export" | "default"
+    )
+}
+
+fn expect_terminator(parser: &mut AstParser) -> (usize, usize) {
+    let terminator = parser.consume().expect("Unexpected end of input, expected \";\"");
+
+    if !is_variable_terminator(terminator) {
+        panic!("Unexpected token: {:?}, expected \";\"", terminator);
+    }
+
+    terminator.range
+}
+
+/*
+Import and export declarations
+*/
+pub fn parse_module_item(parser: &mut AstParser) -> Option<AstNode> {
+    if is_import_declaration(parser) {
+        return Some(AstNode::ImportDeclaration(parse_import_declaration(parser)));
+    }
+
+    let token = parser.token()?;
+
+    if !is_keyword(token, "export") {
+        return parse_statement(parser);
+    }
+
+    let start = token.range.0;
+    parser.step(); // Skip export
+
+    let token = parser.token().expect("Unexpected end of input after \"export\"");
+
+    if is_keyword(token, "default") {
+        return Some(AstNode::ExportDefaultDeclaration(parse_export_default(parser, start)));
+    }
+
+    if token.token_type == TokenType::Operator && token.value == "*" {
+        parser.step(); // Skip *
+
+        let exported = match parser.token() {
+            Some(token) if is_keyword(token, "as") => {
+                parser.step();
+                Some(parse_module_export_name(parser))
+            },
+            _ => None,
+        };
+
+        let source = parse_from_clause(parser);
+        let end = expect_terminator(parser).1;
+
+        return Some(AstNode::ExportAllDeclaration(ExportAllDeclaration { exported, source, range: (start, end) }));
+    }
+
+    if is_start_block_statement(token) {
+        let specifiers = parse_export_specifiers(parser);
+
+        let source = match parser.token() {
+            Some(token) if is_keyword(token, "from") => Some(parse_from_clause(parser)),
+            _ => None,
+        };
+
+        // Only a re-export can name the exports of another module with a string
+        if source.is_none() {
+            if let Some(specifier) = specifiers.iter().find(|specifier| !is_identifier_name(&specifier.local)) {
+                panic!("A string can not name a local binding in an export at {:?}", specifier.range);
+            }
+        }
+
+        let end = expect_terminator(parser).1;
+
+        return Some(AstNode::ExportNamedDeclaration(ExportNamedDeclaration { declaration: None, specifiers, source, range: (start, end) }));
+    }
+
+    let declaration = if is_start_function_declaration(parser) {
+        parse_function_declaration(parser).map(AstNode::FunctionDeclaration)
+    } else if is_class_declaration(parser) {
+        parse_class_declaration(parser).map(AstNode::ClassDeclaration)
+    } else if is_variable_declaration(parser) {
+        parse_variable_declaration(parser).map(AstNode::VariableDeclaration)
+    } else {
+        None
+    };
+
+    let declaration = declaration.unwrap_or_else(|| panic!("Unexpected token: {:?}, expected a declaration after \"export\"", parser.token()));
+    let range = (start, declaration.range().1);
+
+    Some(AstNode::ExportNamedDeclaration(ExportNamedDeclaration { declaration: Some(Box::new(declaration)), specifiers: Vec::new(), source: None, range }))
+}
+
+// `import` followed by `(` or `.` is an expression, a dynamic import or `import.meta`
+fn is_import_declaration(parser: &AstParser) -> bool {
+    parser.token().is_some_and(|token| is_keyword(token, "import")) &&
+        !parser.peek_steps(1).is_some_and(|next| is_function_open_parenthesis(next) || is_expression_seperator(next))
+}
+
+fn parse_import_declaration(parser: &mut AstParser) -> ImportDeclaration {
+    let start = parser.consume().unwrap().range.0; // Skip import
+    let mut specifiers = Vec::new();
+
+    // `import "./module.js"` only runs the module
+    if parser.token().is_some_and(|token| token.token_type == TokenType::String) {
+        let source = parse_module_specifier(parser);
+        let end = expect_terminator(parser).1;
+
+        return ImportDeclaration { specifiers, source, range: (start, end) };
+    }
+
+    // The default import comes first, `*` or `{` can follow it after a comma
+    if parser.token().is_some_and(|token| token.token_type == TokenType::Identifier) {
+        specifiers.push(ImportSpecifier::Default(parse_binding_identifier(parser)));
+
+        match parser.token() {
+            Some(token) if is_expression_param_seperator(token) => parser.step(),
+            _ => {
+                let source = parse_from_clause(parser);
+                let end = expect_terminator(parser).1;
+
+                return ImportDeclaration { specifiers, source, range: (start, end) };
+            },
+        }
+    }
+
+    let token = parser.token().expect("Unexpected end of input in import declaration");
+
+    if token.token_type == TokenType::Operator && token.value == "*" {
+        parser.step(); // Skip *
+
+        match parser.consume() {
+            Some(token) if is_keyword(token, "as") => {},
+            token => panic!("Unexpected token: {:?}, expected \"as\"", token),
+        }
+
+        specifiers.push(ImportSpecifier::Namespace(parse_binding_identifier(parser)));
+    } else if is_start_block_statement(token) {
+        parser.step(); // Skip {
+
+        while !parser.token().is_some_and(is_end_block_statement) {
+            let imported_token = parser.token().expect("Unexpected end of input in import declaration");
+            let binding = (imported_token.token_type == TokenType::Identifier && !is_reserved_word(imported_token)).then_some(imported_token.range);
+            let imported = parse_module_export_name(parser);
+
+            // `{ name }` binds the export under its own name, a string or a reserved word needs an `as`
+            let local = match (parser.token(), binding) {
+                (Some(token), _) if is_keyword(token, "as") => {
+                    parser.step();
+                    parse_binding_identifier(parser)
+                },
+                (_, Some(range)) => Identifier { name: imported.clone(), range },
+                (_, None) => panic!("Expected \"as\" after the import of \"{}\"", imported),
+            };
+
+            specifiers.push(ImportSpecifier::Named { imported, local });
+
+            match parser.token() {
+                Some(token) if is_expression_param_seperator(token) => parser.step(),
+                Some(token) if is_end_block_statement(token) => {},
+                token => panic!("Unexpected token: {:?}, expected \",\" or \"}}\"", token),
+            }
+        }
+
+        parser.step(); // Skip }
+    } else {
+        panic!("Unexpected token: {:?} in import declaration", token);
+    }
+
+    let source = parse_from_clause(parser);
+    let end = expect_terminator(parser).1;
+
+    ImportDeclaration { specifiers, source, range: (start, end) }
+}
+
+// `{ a, b as c, d as "e" }`, the names are only checked once it is known whether the export has a `from`
+fn parse_export_specifiers(parser: &mut AstParser) -> Vec<ExportSpecifier> {
+    let mut specifiers = Vec::new();
+    parser.step(); // Skip {
+
+    while !parser.token().is_some_and(is_end_block_statement) {
+        let (start, mut end) = parser.token().expect("Unexpected end of input in export declaration").range;
+        let local = parse_module_export_name(parser);
+
+        let exported = match parser.token() {
+            Some(token) if is_keyword(token, "as") => {
+                parser.step();
+                end = parser.token().map_or(end, |token| token.range.1);
+                parse_module_export_name(parser)
+            },
+            _ => local.clone(),
+        };
+
+        specifiers.push(ExportSpecifier { local, exported, range: (start, end) });
+
+        match parser.token() {
+            Some(token) if is_expression_param_seperator(token) => parser.step(),
+            Some(token) if is_end_block_statement(token) => {},
+            token => panic!("Unexpected token: {:?}, expected \",\" or \"}}\"", token),
+        }
+    }
+
+    parser.step(); // Skip }
+
+    specifiers
+}
+
+// An anonymous function or class is a declaration named `default`, anything else is an expression
+fn parse_export_default(parser: &mut AstParser, start: usize) -> ExportDefaultDeclaration {
+    parser.step(); // Skip default
+
+    let token = parser.token().expect("Unexpected end of input after \"export default\"");
+
+    let declaration = if is_start_function_declaration(parser) {
+        parse_function_declaration(parser).map(AstNode::FunctionDeclaration)
+    } else if is_keyword(token, "function") || is_async_function(parser) {
+        match parse_function_expression(parser) {
+            Some(Expression::FunctionExpression(function)) => Some(AstNode::FunctionDeclaration(FunctionDeclaration {
+                id: Identifier { name: "default".to_string(), range: function.range },
+                params: function.params,
+                body: function.body,
+                generator: function.generator,
+                is_async: function.is_async,
+                range: function.range,
+            })),
+            _ => unreachable!(),
+        }
+    } else if is_class_declaration(parser) {
+        parse_class_declaration(parser).map(AstNode::ClassDeclaration)
+    } else if is_keyword(token, "class") {
+        match parse_class_expression(parser) {
+            Some(Expression::ClassExpression(class)) => Some(AstNode::ClassDeclaration(ClassDeclaration {
+                id: Identifier { name: "default".to_string(), range: class.range },
+                super_class: class.super_class,
+                body: class.body,
+                range: class.range,
+            })),
+            _ => unreachable!(),
+        }
+    } else {
+        None
+    };
+
+    let (declaration, end) = match declaration {
+        Some(declaration) => {
+            let end = declaration.range().1;
+            (ExportDefaultKind::Declaration(Box::new(declaration)), end)
+        },
+        None => {
+            let expression = parse_assignment_expression(parser).expect("Expected an expression after \"export default\"");
+            let end = expect_terminator(parser).1;
+
+            (ExportDefaultKind::Expression(expression), end)
+        },
+    };
+
+    ExportDefaultDeclaration { declaration, range: (start, end) }
+}
+
+fn parse_from_clause(parser: &mut AstParser) -> StringLiteral {
+    match parser.consume() {
+        Some(token) if is_keyword(token, "from") => {},
+        token => panic!("Unexpected token: {:?}, expected \"from\"", token),
+    }
+
+    parse_module_specifier(parser)
+}
+
+fn parse_module_specifier(parser: &mut AstParser) -> StringLiteral {
+    match parser.consume() {
+        Some(token) if token.token_type == TokenType::String => StringLiteral {
+            value: token.value.clone(),
+            raw: token.raw_value.clone(),
+            range: token.range,
+        },
+        token => panic!("Unexpected token: {:?}, expected a module specifier", token),
+    }
+}
+
+// Imports and exports can be named by any identifier, reserved words included, or by a string
+fn parse_module_export_name(parser: &mut AstParser) -> String {
+    match parser.consume() {
+        Some(token) if matches!(token.token_type, TokenType::Identifier | TokenType::String | TokenType::Boolean | TokenType::Null) => token.value.clone(),
+        token => panic!("Unexpected token: {:?}, expected a name", token),
+    }
+}
+
+fn parse_binding_identifier(parser: &mut AstParser) -> Identifier {
+    match parser.consume() {
+        Some(token) if token.token_type == TokenType::Identifier && !is_reserved_word(token) => Identifier {
+            name: token.value.clone(),
+            range: token.range,
+        },
+        token => panic!("Unexpected token: {:?}, expected a binding", token),
+    }
+}
+
+fn is_identifier_name(name: &str) -> bool {
+    let mut characters = name.chars();
+
+    characters.next().is_some_and(|first| is_identifier(&first)) && characters.all(|character| is_identifier_part(&character))
+}
 
 /*
 Block statement
 */
 pub fn parse_block_statement(parser: &mut AstParser) -> Option<BlockStatement> {
 
-    if !is_start_block_statement(parser.token().unwrap()) {
-        return None;
+    if !is_start_block_statement(parser.token()?) {
+        return None;
+    }
+
+    let mut body = Vec::new();
+
+    // Always skip the first bracket by stepping over it
+    let start = parser.consume().unwrap().range.0;
+
+    loop {
+        let token = parser.token().expect("Unexpected end of input, expected \"}\"");
+
+        if is_end_block_statement(token) {
+            break;
+        }
+
+        match parse_statement(parser) {
+            Some(statement) => body.push(statement),
+            None => panic!("Unknown token: {:?}", parser.token().unwrap())
+        }
+    }
+
+    let end = parser.consume().unwrap().range.1;
+
+    Some(BlockStatement {
+        body,
+        range: (start, end)
+    })
+}
+
+pub fn is_start_block_statement(token: &Token) -> bool {
+    token.token_type == TokenType::CurlyBraces && token.value == "{"
+}
+
+pub fn is_end_block_statement(token: &Token) -> bool {
+    token.token_type == TokenType::CurlyBraces && token.value == "}"
+}
+
+/*
+Function declaration
+*/
+pub fn parse_function_declaration(parser: &mut AstParser) -> Option<FunctionDeclaration> {
+
+    if !is_start_function_declaration(parser) {
+        return None;
+    }
+
+    let start = parser.token().unwrap().range.0;
+    let is_async = parse_async_keyword(parser);
+    parser.step(); // Skip function
+
+    let generator = parse_generator_star(parser);
+    let name = parser.consume().unwrap();
+
+    let identifier = Identifier {
+        name: name.value.clone(),
+        range: name.range
+    };
+
+    let params = parse_function_params(parser);
+
+    let body = parse_function_body(parser, generator, is_async);
+    let end = body.range.1;
+
+    Some(
+        FunctionDeclaration {
+            id: identifier,
+            params,
+            body,
+            generator,
+            is_async,
+            range: (start, end)
+        }
+    )
+}
+
+pub fn is_start_function_declaration(parser: &AstParser) -> bool {
+    // Async keyword
+    let prefix = is_async_function(parser) as usize;
+
+    // Function keyword
+    let keyword = parser.peek_steps(prefix);
+    if keyword.is_none() || !is_function_keyword(keyword.unwrap()) {
+        return false;
+    }
+
+    // Generator star
+    let star = parser.peek_steps(prefix + 1).is_some_and(is_generator_star) as usize;
+
+    // Function name
+    let function_name = parser.peek_steps(prefix + 1 + star);
+    if function_name.is_none() || !is_function_name(function_name.unwrap()) {
+        return false;
+    }
+
+    // Parenthesis
+    let open_parenthesis = parser.peek_steps(prefix + 2 + star);
+    if open_parenthesis.is_none() || !is_function_open_parenthesis(open_parenthesis.unwrap()) {
+        return false;
+    }
+
+    true
+}
+
+// Parameters are binding patterns with optional defaults, a rest parameter has to be the last one
+fn parse_function_params(parser: &mut AstParser) -> Vec<Pattern> {
+    parser.step(); // Step over param open
+
+    let mut params = Vec::new();
+
+    loop {
+        let token = parser.token().expect("Unexpected end of input, expected \")\"");
+
+        if is_function_close_parenthesis(token) {
+            break;
+        }
+
+        if is_spread(token) {
+            params.push(Pattern::RestElement(Box::new(parse_rest_element(parser, false))));
+            break;
+        }
+
+        params.push(parse_pattern_element(parser, false));
+
+        match parser.token() {
+            Some(seperator) if is_expression_param_seperator(seperator) => parser.step(),
+            _ => break
+        }
+    }
+
+    let close = parser.consume().unwrap(); // Step over param close
+
+    if !is_function_close_parenthesis(close) {
+        panic!("Unexpected token: {:?}, expected \")\"", close);
+    }
+
+    params
+}
+
+// The body of a function, `yield` and `await` are only expressions directly inside generator and async bodies
+fn parse_function_body(parser: &mut AstParser, generator: bool, is_async: bool) -> BlockStatement {
+    let in_generator = std::mem::replace(&mut parser.in_generator, generator);
+    let in_async = std::mem::replace(&mut parser.in_async, is_async);
+    let in_function = std::mem::replace(&mut parser.in_function, true);
+    let body = parse_block_statement(parser).expect("Expected a function body");
+    parser.in_generator = in_generator;
+    parser.in_function = in_function;
+    parser.in_async = in_async;
+
+    body
+}
+
+// `async` is a plain name unless it comes right before `function`
+fn is_async_function(parser: &AstParser) -> bool {
+    parser.token().is_some_and(|token| is_keyword(token, "async")) && parser.peek_steps(1).is_some_and(is_function_keyword)
+}
+
+fn parse_async_keyword(parser: &mut AstParser) -> bool {
+    let is_async = is_async_function(parser);
+
+    if is_async {
+        parser.step();
+    }
+
+    is_async
+}
+
+// The `*` of `function*` and of generator methods
+fn parse_generator_star(parser: &mut AstParser) -> bool {
+    let generator = parser.token().is_some_and(is_generator_star);
+
+    if generator {
+        parser.step();
+    }
+
+    generator
+}
+
+// The `async` of async methods, when a name or `*` follows it. `{ async: 1 }` and `async() {}` use it as the name.
+fn parse_async_modifier(parser: &mut AstParser, is_start_key: fn(&Token) -> bool) -> bool {
+    let is_async = parser.token().is_some_and(|token| is_keyword(token, "async")) &&
+        parser.peek_steps(1).is_some_and(|next| is_start_key(next) || is_generator_star(next));
+
+    if is_async {
+        parser.step();
+    }
+
+    is_async
+}
+
+fn is_generator_star(token: &Token) -> bool {
+    token.token_type == TokenType::Operator && token.value == "*"
+}
+
+fn is_function_keyword(token: &Token) -> bool {
+    is_keyword(token, "function")
+}
+
+fn is_function_name(token: &Token) -> bool {
+    token.token_type == TokenType::Identifier
+}
+
+fn is_function_open_parenthesis(token: &Token) -> bool {
+    token.token_type == TokenType::Parenthesis && token.value == "("
+}
+
+fn is_function_close_parenthesis(token: &Token) -> bool {
+    token.token_type == TokenType::Parenthesis && token.value == ")"
+}
+
+/*
+Class declaration and expression
+*/
+pub fn parse_class_declaration(parser: &mut AstParser) -> Option<ClassDeclaration> {
+
+    if !is_class_declaration(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0; // Skip class
+    let name = parser.consume().unwrap();
+
+    let id = Identifier {
+        name: name.value.clone(),
+        range: name.range
+    };
+
+    let super_class = parse_class_heritage(parser);
+    let body = parse_class_body(parser);
+    let end = body.range.1;
+
+    Some(ClassDeclaration {
+        id,
+        super_class,
+        body,
+        range: (start, end)
+    })
+}
+
+pub fn is_class_declaration(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+    let name = parser.peek_steps(1);
+
+    keyword.is_some_and(|keyword| is_keyword(keyword, "class")) &&
+        name.is_some_and(|name| name.token_type == TokenType::Identifier && !is_reserved_word(name))
+}
+
+// The name is optional, `class {}` and `class extends Base {}` are anonymous
+fn parse_class_expression(parser: &mut AstParser) -> Option<Expression> {
+    let start = parser.consume().unwrap().range.0; // Skip class
+
+    let id = match parser.token() {
+        Some(token) if token.token_type == TokenType::Identifier && !is_reserved_word(token) => {
+            let id = Identifier {
+                name: token.value.clone(),
+                range: token.range
+            };
+            parser.step();
+
+            Some(id)
+        },
+        _ => None,
+    };
+
+    let super_class = parse_class_heritage(parser);
+    let body = parse_class_body(parser);
+    let end = body.range.1;
+
+    Some(Expression::ClassExpression(Box::new(ClassExpression {
+        id,
+        super_class,
+        body,
+        range: (start, end)
+    })))
+}
+
+fn parse_class_heritage(parser: &mut AstParser) -> Option<Expression> {
+    match parser.token() {
+        Some(token) if is_keyword(token, "extends") => {
+            parser.step();
+
+            Some(parse_left_hand_side_expression(parser).expect("Expected an expression after \"extends\""))
+        },
+        _ => None,
+    }
+}
+
+fn parse_class_body(parser: &mut AstParser) -> ClassBody {
+    let open = parser.consume().expect("Unexpected end of input, expected \"{\"");
+
+    if !is_start_block_statement(open) {
+        panic!("Unexpected token: {:?}, expected \"{{\"", open);
+    }
+
+    let start = open.range.0;
+    let mut body = Vec::new();
+
+    // Field initializers and static blocks are bodies of their own, a surrounding generator can not yield in them
+    let in_generator = std::mem::replace(&mut parser.in_generator, false);
+    let in_async = std::mem::replace(&mut parser.in_async, false);
+
+    loop {
+        let token = parser.token().expect("Unexpected end of input, expected \"}\"");
+
+        if is_end_block_statement(token) {
+            break;
+        }
+
+        // Stray semicolons between elements are allowed
+        if is_variable_terminator(token) {
+            parser.step();
+            continue;
+        }
+
+        body.push(parse_class_element(parser));
+    }
+
+    parser.in_generator = in_generator;
+    parser.in_async = in_async;
+    let end = parser.consume().unwrap().range.1;
+
+    ClassBody {
+        body,
+        range: (start, end)
+    }
+}
+
+fn parse_class_element(parser: &mut AstParser) -> ClassElement {
+    let token = parser.token().unwrap();
+    let start = token.range.0;
+
+    // `static` is a modifier unless it is the name of the element itself, like `static() {}` or `static = 1;`
+    let is_static = is_keyword(token, "static") && parser.peek_steps(1).is_some_and(|next| is_start_class_element_key(next) || is_generator_star(next));
+
+    if is_keyword(token, "static") && parser.peek_steps(1).is_some_and(is_start_block_statement) {
+        parser.step(); // Skip static
+
+        let block = parse_block_statement(parser).unwrap();
+
+        return ClassElement::StaticBlock(StaticBlock {
+            body: block.body,
+            range: (start, block.range.1)
+        });
+    }
+
+    if is_static {
+        parser.step();
+    }
+
+    let is_async = parse_async_modifier(parser, is_start_class_element_key);
+    let generator = parse_generator_star(parser);
+    let token = parser.token().expect("Unexpected end of input in class body");
+
+    let kind = match parser.peek_steps(1) {
+        _ if generator || is_async => MethodKind::Method,
+        Some(next) if is_keyword(token, "get") && is_start_class_element_key(next) => MethodKind::Get,
+        Some(next) if is_keyword(token, "set") && is_start_class_element_key(next) => MethodKind::Set,
+        _ => MethodKind::Method,
+    };
+
+    if kind != MethodKind::Method {
+        parser.step();
+    }
+
+    let (key, computed) = match parser.token() {
+        Some(token) if token.token_type == TokenType::PrivateName => {
+            let key = Expression::PrivateIdentifier(PrivateIdentifier {
+                name: token.value.clone(),
+                range: token.range
+            });
+            parser.step();
+
+            (key, false)
+        },
+        _ => parse_property_key(parser),
+    };
+
+    let next = parser.token().expect("Unexpected end of input in class body");
+
+    if is_function_open_parenthesis(next) {
+        let params = parse_function_params(parser);
+        let body = parse_function_body(parser, generator, is_async);
+        let range = (key.range().0, body.range.1);
+
+        let is_constructor_name = match &key {
+            Expression::Identifier(identifier) => identifier.name == "constructor",
+            Expression::StringLiteral(literal) => literal.value == "constructor",
+            _ => false,
+        };
+
+        let kind = match kind {
+            _ if generator && is_constructor_name && !computed && !is_static => panic!("Class constructor may not be a generator"),
+            _ if is_async && is_constructor_name && !computed && !is_static => panic!("Class constructor may not be an async method"),
+            MethodKind::Method if is_constructor_name && !computed && !is_static => MethodKind::Constructor,
+            _ if is_constructor_name && !computed && !is_static => panic!("Class constructor may not be an accessor"),
+            kind => kind,
+        };
+
+        return ClassElement::MethodDefinition(MethodDefinition {
+            key,
+            value: FunctionExpression {
+                id: None,
+                params,
+                body,
+                generator,
+                is_async,
+                range
+            },
+            kind,
+            computed,
+            is_static,
+        });
+    }
+
+    if kind != MethodKind::Method || generator || is_async {
+        panic!("Unexpected token: {:?}, expected \"(\"", next);
+    }
+
+    let value = match next {
+        next if is_variable_assignment(next) => {
+            parser.step(); // Skip =
+
+            Some(parse_assignment_expression(parser).expect("Expected an expression after \"=\""))
+        },
+        _ => None,
+    };
+
+    expect_terminator(parser);
+
+    ClassElement::PropertyDefinition(PropertyDefinition {
+        key,
+        value,
+        computed,
+        is_static,
+    })
+}
+
+fn is_start_class_element_key(token: &Token) -> bool {
+    is_start_property_key(token) || token.token_type == TokenType::PrivateName
+}
+
+/*
+Variable declaration
+*/
+pub fn parse_variable_declaration(parser: &mut AstParser) -> Option<VariableDeclaration> {
+    let mut declarations = Vec::<VariableDeclarator>::new();
+
+    if !is_variable_declaration(parser) {
+        return None;
+    }
+
+    let keyword = parser.consume().unwrap();
+    let start = keyword.range.0;
+
+    let kind = match keyword.value.as_str() {
+        "let" => VariableKind::Let,
+        "const" => VariableKind::Const,
+        _ => VariableKind::Var,
+    };
+
+    loop {
+        let id = parse_pattern(parser, false);
+        let mut init = None;
+
+        if parser.token().is_some_and(is_variable_assignment) {
+            parser.step(); // Skip assignment
+
+            init = Some(parse_expression(parser).expect("Expected an expression after \"=\""));
+        }
+
+        // A pattern needs a value to destructure and a constant needs a value to hold
+        if init.is_none() {
+            match (&id, kind) {
+                (Pattern::Identifier(_), VariableKind::Const) => panic!("Missing initializer in const declaration"),
+                (Pattern::Identifier(_), _) => {},
+                _ => panic!("Missing initializer in destructuring declaration"),
+            }
+        }
+
+        declarations.push(VariableDeclarator { id, init });
+
+        match parser.token() {
+            Some(seperator) if is_expression_param_seperator(seperator) => parser.step(),
+            _ => break
+        }
+    }
+
+    let end = expect_terminator(parser).1;
+
+    Some(VariableDeclaration {
+        kind,
+        declarations,
+        range: (start, end)
+    })
+}
+
+// `let` is only a keyword when a binding follows it, `let = 1` assigns to a variable named let
+pub fn is_variable_declaration(parser: &AstParser) -> bool {
+
+    let keyword = parser.peek_steps(0);
+    if keyword.is_none() || !is_variable_keyword(keyword.unwrap()) {
+        return false;
+    }
+
+    let next = parser.peek_steps(1);
+    if next.is_none() {
+        return false;
+    }
+
+    let next = next.unwrap();
+    (is_variable_name(next) && !is_reserved_word(next)) || is_open_square_bracket(next) || is_start_block_statement(next)
+}
+
+fn is_variable_keyword(token: &Token) -> bool {
+    is_keyword(token, "var") || is_keyword(token, "let") || is_keyword(token, "const")
+}
+
+fn is_variable_name(token: &Token) -> bool {
+    token.token_type == TokenType::Identifier
+}
+
+fn is_variable_assignment(token: &Token) -> bool {
+    token.token_type == TokenType::Operator && token.value == "="
+}
+
+fn is_variable_terminator(token: &Token) -> bool {
+    token.token_type == TokenType::Terminator && token.value == ";"
+}
+
+/*
+Binding and assignment patterns
+*/
+// Binding patterns declare names, assignment patterns can also assign to properties, `[a.b, c[0]] = list`
+fn parse_pattern(parser: &mut AstParser, assignment: bool) -> Pattern {
+    let token = parser.token().expect("Unexpected end of input, expected a binding");
+
+    if is_open_square_bracket(token) {
+        return parse_array_pattern(parser, assignment);
+    }
+
+    if is_start_block_statement(token) {
+        return parse_object_pattern(parser, assignment);
+    }
+
+    if assignment {
+        return match parse_left_hand_side_expression(parser) {
+            Some(Expression::Identifier(identifier)) => Pattern::Identifier(identifier),
+            Some(Expression::MemberExpression(member)) => Pattern::MemberExpression(member),
+            target => panic!("Invalid destructuring assignment target at {:?}", target.map(|target| target.range())),
+        };
+    }
+
+    let token = parser.consume().unwrap();
+
+    if !is_variable_name(token) || is_reserved_word(token) {
+        panic!("Unexpected token: {:?}, expected a binding", token);
+    }
+
+    Pattern::Identifier(Identifier {
+        name: token.value.clone(),
+        range: token.range
+    })
+}
+
+// A pattern followed by an optional default value, `target = value`
+fn parse_pattern_element(parser: &mut AstParser, assignment: bool) -> Pattern {
+    let target = parse_pattern(parser, assignment);
+
+    if !parser.token().is_some_and(is_variable_assignment) {
+        return target;
+    }
+
+    parser.step(); // Skip =
+
+    let right = parse_assignment_expression(parser).expect("Expected an expression after \"=\"");
+    let range = (target.range().0, right.range().1);
+
+    Pattern::AssignmentPattern(Box::new(AssignmentPattern {
+        left: target,
+        right,
+        range
+    }))
+}
+
+fn parse_rest_element(parser: &mut AstParser, assignment: bool) -> RestElement {
+    let start = parser.consume().unwrap().range.0; // Skip ...
+    let argument = parse_pattern(parser, assignment);
+
+    RestElement {
+        range: (start, argument.range().1),
+        argument
+    }
+}
+
+fn parse_array_pattern(parser: &mut AstParser, assignment: bool) -> Pattern {
+    let start = parser.consume().unwrap().range.0; // Skip [
+    let mut elements = Vec::new();
+
+    loop {
+        let token = parser.token().expect("Unexpected end of input, expected \"]\"");
+
+        if is_close_square_bracket(token) {
+            break;
+        }
+
+        if is_expression_param_seperator(token) {
+            elements.push(None);
+            parser.step();
+            continue;
+        }
+
+        // Nothing can follow the rest element, not even a trailing comma
+        if is_spread(token) {
+            elements.push(Some(Pattern::RestElement(Box::new(parse_rest_element(parser, assignment)))));
+            break;
+        }
+
+        elements.push(Some(parse_pattern_element(parser, assignment)));
+
+        let token = parser.token().expect("Unexpected end of input, expected \"]\"");
+
+        if is_close_square_bracket(token) {
+            break;
+        }
+
+        if !is_expression_param_seperator(token) {
+            panic!("Unexpected token: {:?}, expected \",\" or \"]\"", token);
+        }
+
+        parser.step();
+    }
+
+    let end = expect_close_square_bracket(parser).1;
+
+    Pattern::ArrayPattern(Box::new(ArrayPattern {
+        elements,
+        range: (start, end)
+    }))
+}
+
+fn parse_object_pattern(parser: &mut AstParser, assignment: bool) -> Pattern {
+    let start = parser.consume().unwrap().range.0; // Skip {
+    let mut properties = Vec::new();
+    let mut rest = None;
+
+    loop {
+        let token = parser.token().expect("Unexpected end of input, expected \"}\"");
+
+        if is_end_block_statement(token) {
+            break;
+        }
+
+        if is_spread(token) {
+            rest = Some(Box::new(parse_rest_element(parser, assignment)));
+            break;
+        }
+
+        properties.push(parse_assignment_property(parser, assignment));
+
+        let token = parser.token().expect("Unexpected end of input, expected \"}\"");
+
+        if is_end_block_statement(token) {
+            break;
+        }
+
+        if !is_expression_param_seperator(token) {
+            panic!("Unexpected token: {:?}, expected \",\" or \"}}\"", token);
+        }
+
+        parser.step();
+    }
+
+    let close = parser.consume().expect("Unexpected end of input, expected \"}\"");
+
+    if !is_end_block_statement(close) {
+        panic!("Unexpected token: {:?}, expected \"}}\"", close);
+    }
+
+    Pattern::ObjectPattern(Box::new(ObjectPattern {
+        properties,
+        rest,
+        range: (start, close.range.1)
+    }))
+}
+
+fn parse_assignment_property(parser: &mut AstParser, assignment: bool) -> AssignmentProperty {
+    let token = parser.token().unwrap();
+
+    // Only used for shorthands, where the first token is the key
+    let is_reserved = is_reserved_word(token);
+
+    let (key, computed) = parse_property_key(parser);
+    let next = parser.token().expect("Unexpected end of input in object pattern");
+
+    if is_property_colon(next) {
+        parser.step(); // Skip :
+
+        let value = parse_pattern_element(parser, assignment);
+
+        return AssignmentProperty { key, value, computed };
+    }
+
+    // `{ a }` is short for `{ a: a }` and `{ a = 1 }` for `{ a: a = 1 }`
+    let identifier = match &key {
+        Expression::Identifier(identifier) if !computed && !is_reserved => identifier.clone(),
+        _ => panic!("Unexpected token: {:?}, expected \":\"", next),
+    };
+
+    let mut value = Pattern::Identifier(identifier);
+
+    if is_variable_assignment(next) {
+        parser.step(); // Skip =
+
+        let right = parse_assignment_expression(parser).expect("Expected an expression after \"=\"");
+        let range = (value.range().0, right.range().1);
+
+        value = Pattern::AssignmentPattern(Box::new(AssignmentPattern {
+            left: value,
+            right,
+            range
+        }));
+    }
+
+    AssignmentProperty { key, value, computed }
+}
+
+/*
+Return statement
+*/
+pub fn parse_return_statement(parser: &mut AstParser) -> Option<ReturnStatement> {
+
+    if !is_return_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let mut argument = None;
+
+    if !is_variable_terminator(parser.token().expect("Unexpected end of input after \"return\"")) {
+        argument = Some(parse_expression(parser).expect("Expected an expression after \"return\""));
+    }
+
+    let end = expect_terminator(parser).1;
+
+    Some(ReturnStatement {
+        argument,
+        range: (start, end)
+    })
+}
+
+pub fn is_return_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "return")
+}
+
+/*
+If statement
+*/
+pub fn parse_if_statement(parser: &mut AstParser) -> Option<IfStatement> {
+
+    if !is_if_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let test = parse_condition(parser);
+
+    let consequent = parse_if_clause(parser, "if");
+    let mut end = consequent.range().1;
+    let mut alternate = None;
+
+    if parser.token().is_some() && is_keyword(parser.token().unwrap(), "else") {
+        parser.step(); // Skip else
+
+        let statement = parse_if_clause(parser, "else");
+        end = statement.range().1;
+        alternate = Some(Box::new(statement));
+    }
+
+    Some(IfStatement {
+        test,
+        consequent: Box::new(consequent),
+        alternate,
+        range: (start, end)
+    })
+}
+
+// A function declaration as the body of an if statement is treated as if it was wrapped in a block (Annex B.3.4)
+fn parse_if_clause(parser: &mut AstParser, keyword: &str) -> AstNode {
+    let statement = parse_statement(parser)
+        .unwrap_or_else(|| panic!("Expected a statement after \"{}\"", keyword));
+
+    match statement {
+        AstNode::FunctionDeclaration(declaration) => {
+            let range = declaration.range;
+
+            AstNode::BlockStatement(BlockStatement {
+                body: vec![AstNode::FunctionDeclaration(declaration)],
+                range
+            })
+        },
+        statement => statement
+    }
+}
+
+pub fn is_if_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+    let open_paren = parser.peek_steps(1);
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "if") &&
+    open_paren.is_some() && is_function_open_parenthesis(open_paren.unwrap())
+}
+
+/*
+While statement
+*/
+pub fn parse_while_statement(parser: &mut AstParser) -> Option<WhileStatement> {
+
+    if !is_while_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let test = parse_condition(parser);
+
+    let body = parse_statement(parser).expect("Expected a statement after \"while\"");
+    let end = body.range().1;
+
+    Some(WhileStatement {
+        test,
+        body: Box::new(body),
+        range: (start, end)
+    })
+}
+
+pub fn is_while_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+    let open_paren = parser.peek_steps(1);
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "while") &&
+    open_paren.is_some() && is_function_open_parenthesis(open_paren.unwrap())
+}
+
+/*
+For of statement
+*/
+pub fn parse_for_of_statement(parser: &mut AstParser) -> Option<ForOfStatement> {
+
+    if !is_for_of_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let is_await = parser.token().is_some_and(|token| is_keyword(token, "await"));
+
+    if is_await {
+        if !parser.in_async {
+            panic!("for await is only valid in async functions and the top level bodies of modules");
+        }
+
+        parser.has_top_level_await |= !parser.in_function;
+        parser.step();
+    }
+
+    parser.step(); // Skip open paren
+
+    // `var`, `let` and `const` declare the binding, anything else is assigned to like the left side of `=`
+    let kind = match is_variable_declaration(parser) {
+        true => Some(match parser.consume().unwrap().value.as_str() {
+            "let" => VariableKind::Let,
+            "const" => VariableKind::Const,
+            _ => VariableKind::Var,
+        }),
+        false => None,
+    };
+
+    let left = parse_pattern(parser, kind.is_none());
+
+    let keyword = parser.consume().expect("Unexpected end of input, expected \"of\"");
+
+    if !is_keyword(keyword, "of") {
+        panic!("Unexpected token: {:?}, expected \"of\"", keyword);
+    }
+
+    let right = parse_assignment_expression(parser).expect("Expected an expression after \"of\"");
+    let close = parser.consume().expect("Unexpected end of input, expected \")\"");
+
+    if !is_function_close_parenthesis(close) {
+        panic!("Unexpected token: {:?}, expected \")\"", close);
+    }
+
+    let body = parse_statement(parser).expect("Expected a statement after \"for\"");
+    let end = body.range().1;
+
+    Some(ForOfStatement {
+        kind,
+        is_await,
+        left,
+        right,
+        body: Box::new(body),
+        range: (start, end)
+    })
+}
+
+pub fn is_for_of_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+    let open_paren = parser.peek_steps(1);
+
+    let open_paren = match open_paren {
+        Some(token) if is_keyword(token, "await") => parser.peek_steps(2),
+        open_paren => open_paren,
+    };
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "for") &&
+    open_paren.is_some() && is_function_open_parenthesis(open_paren.unwrap())
+}
+
+/*
+Throw and try statements
+*/
+pub fn parse_throw_statement(parser: &mut AstParser) -> Option<ThrowStatement> {
+
+    if !is_throw_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let argument = parse_expression(parser).expect("Expected an expression after \"throw\"");
+    let end = expect_terminator(parser).1;
+
+    Some(ThrowStatement {
+        argument,
+        range: (start, end)
+    })
+}
+
+pub fn is_throw_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "throw")
+}
+
+pub fn parse_try_statement(parser: &mut AstParser) -> Option<TryStatement> {
+
+    if !is_try_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let block = parse_clause_block(parser, "try");
+    let mut end = block.range.1;
+
+    let mut handler = None;
+    let mut finalizer = None;
+
+    if parser.token().is_some_and(|token| is_keyword(token, "catch")) {
+        let clause = parse_catch_clause(parser);
+        end = clause.range.1;
+        handler = Some(clause);
+    }
+
+    if parser.token().is_some_and(|token| is_keyword(token, "finally")) {
+        parser.step(); // Skip finally
+
+        let block = parse_clause_block(parser, "finally");
+        end = block.range.1;
+        finalizer = Some(block);
+    }
+
+    if handler.is_none() && finalizer.is_none() {
+        panic!("Missing catch or finally after try");
+    }
+
+    Some(TryStatement {
+        block,
+        handler,
+        finalizer,
+        range: (start, end)
+    })
+}
+
+pub fn is_try_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+    let open_brace = parser.peek_steps(1);
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "try") &&
+    open_brace.is_some() && is_start_block_statement(open_brace.unwrap())
+}
+
+fn parse_catch_clause(parser: &mut AstParser) -> CatchClause {
+    let start = parser.consume().unwrap().range.0;
+    let mut param = None;
+
+    if parser.token().is_some_and(is_function_open_parenthesis) {
+        parser.step(); // Skip open paren
+
+        param = Some(parse_pattern(parser, false));
+
+        let close = parser.consume().expect("Unexpected end of input, expected \")\"");
+
+        if !is_function_close_parenthesis(close) {
+            panic!("Unexpected token: {:?}, expected \")\"", close);
+        }
+    }
+
+    let body = parse_clause_block(parser, "catch");
+
+    CatchClause {
+        param,
+        range: (start, body.range.1),
+        body,
+    }
+}
+
+// The blocks of a try statement have to be actual blocks
+fn parse_clause_block(parser: &mut AstParser, keyword: &str) -> BlockStatement {
+    parse_block_statement(parser).unwrap_or_else(|| panic!("Expected a block after \"{}\"", keyword))
+}
+
+/*
+Break and continue statements
+*/
+pub fn parse_break_statement(parser: &mut AstParser) -> Option<BreakStatement> {
+
+    if !is_break_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let end = expect_terminator(parser).1;
+
+    Some(BreakStatement {
+        range: (start, end)
+    })
+}
+
+pub fn is_break_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "break")
+}
+
+pub fn parse_continue_statement(parser: &mut AstParser) -> Option<ContinueStatement> {
+
+    if !is_continue_statement(parser) {
+        return None;
+    }
+
+    let start = parser.consume().unwrap().range.0;
+    let end = expect_terminator(parser).1;
+
+    Some(ContinueStatement {
+        range: (start, end)
+    })
+}
+
+pub fn is_continue_statement(parser: &AstParser) -> bool {
+    let keyword = parser.peek_steps(0);
+
+    keyword.is_some() && is_keyword(keyword.unwrap(), "continue")
+}
+
+// Parses the parenthesised condition of an if or while statement
+fn parse_condition(parser: &mut AstParser) -> Expression {
+    parser.step(); // Skip open paren
+
+    let test = parse_expression(parser).expect("Expected a condition");
+    let close = parser.consume().expect("Unexpected end of input, expected \")\"");
+
+    if !is_function_close_parenthesis(close) {
+        panic!("Unexpected token: {:?}, expected \")\"", close);
+    }
+
+    test
+}
+
+/*
+Expression statement
+*/
+pub fn parse_expression_statement(parser: &mut AstParser) -> Option<ExpressionStatement> {
+
+    if !is_expression_statement(parser) {
+        return None;
+    }
+
+    let expression = parse_expression(parser)?;
+    let start = expression.range().0;
+    let end = expect_terminator(parser).1;
+
+    Some(ExpressionStatement {
+        expression,
+        range: (start, end)
+    })
+}
+
+pub fn is_expression_statement(parser: &AstParser) -> bool {
+    is_start_expression(parser)
+}
+
+/*
+Expression
+*/
+pub fn parse_expression(parser: &mut AstParser) -> Option<Expression> {
+    parse_assignment_expression(parser)
+}
+
+fn is_start_expression(parser: &AstParser) -> bool {
+    let token = parser.peek_steps(0);
+
+    if token.is_none() {
+        return false;
+    }
+
+    let token = token.unwrap();
+
+    match token.token_type {
+        TokenType::Number |
+        TokenType::BigInt |
+        TokenType::String |
+        TokenType::Boolean |
+        TokenType::Null |
+        TokenType::RegExp => true,
+        TokenType::Identifier => !is_reserved_word(token) || is_unary_operator(token) || ["new", "this", "function", "class", "super", "import"].contains(&token.value.as_str()),
+        TokenType::Parenthesis => token.value == "(",
+        TokenType::SquareBrackets => token.value == "[",
+        TokenType::Operator => is_unary_operator(token) || is_update_operator(token),
+        _ => false
+    }
+}
+
+/*
+Assignment expression
+*/
+fn parse_assignment_expression(parser: &mut AstParser) -> Option<Expression> {
+    if parser.in_generator && parser.token().is_some_and(|token| is_keyword(token, "yield")) {
+        return Some(parse_yield_expression(parser));
+    }
+
+    if is_start_arrow_function(parser) {
+        return Some(parse_arrow_function(parser));
+    }
+
+    let (left, operator) = match is_start_destructuring_assignment(parser) {
+        true => (parse_pattern(parser, true), "=".to_string()),
+        false => {
+            let left = parse_binary_expression(parser, 0)?;
+
+            let operator = match parser.token() {
+                Some(token) if is_assignment_operator(token) => token.value.clone(),
+                _ => return Some(left),
+            };
+
+            let left = match left {
+                Expression::Identifier(identifier) => Pattern::Identifier(identifier),
+                Expression::MemberExpression(member) => Pattern::MemberExpression(member),
+                left => panic!("Invalid left-hand side in assignment at {:?}", left.range()),
+            };
+
+            (left, operator)
+        },
+    };
+
+    parser.step(); // Skip operator
+
+    // Assignment is right associative, so `a = b = c` assigns `c` to `b` first
+    let right = parse_assignment_expression(parser).expect("Expected an expression after assignment");
+    let range = (left.range().0, right.range().1);
+
+    Some(Expression::AssignmentExpression(Box::new(AssignmentExpression {
+        operator,
+        left,
+        right,
+        range
+    })))
+}
+
+// The argument of `yield` is optional, so it only takes one when an expression follows. `yield*` needs one.
+fn parse_yield_expression(parser: &mut AstParser) -> Expression {
+    let (start, mut end) = parser.consume().unwrap().range; // Skip yield
+
+    let delegate = parser.token().is_some_and(is_generator_star);
+
+    if delegate {
+        end = parser.consume().unwrap().range.1; // Skip *
+    }
+
+    let has_argument = delegate || is_start_expression(parser) || parser.token().is_some_and(is_start_block_statement);
+
+    let argument = match has_argument {
+        true => Some(parse_assignment_expression(parser).expect("Expected an expression after \"yield\"")),
+        false => None,
+    };
+
+    if let Some(argument) = &argument {
+        end = argument.range().1;
+    }
+
+    Expression::YieldExpression(Box::new(YieldExpression {
+        argument,
+        delegate,
+        is_async: parser.in_async,
+        range: (start, end)
+    }))
+}
+
+// `[a, b] = list` and `({ a } = object)`, an array or object literal is a pattern when `=` follows its closing bracket
+fn is_start_destructuring_assignment(parser: &AstParser) -> bool {
+    match parser.token() {
+        Some(token) if is_open_square_bracket(token) || is_start_block_statement(token) => {},
+        _ => return false,
+    }
+
+    let mut depth = 0;
+    let mut steps = 0;
+
+    while let Some(token) = parser.peek_steps(steps) {
+        if matches!(token.token_type, TokenType::Parenthesis | TokenType::CurlyBraces | TokenType::SquareBrackets) {
+            depth += if ["(", "{", "["].contains(&token.value.as_str()) { 1 } else { -1 };
+        }
+
+        steps += 1;
+
+        if depth == 0 {
+            return parser.peek_steps(steps).is_some_and(is_variable_assignment);
+        }
+    }
+
+    false
+}
+
+fn is_assignment_operator(token: &Token) -> bool {
+    token.token_type == TokenType::Operator && matches!(
+        token.value.as_str(),
+        "=" | "+=" | "-=" | "*=" | "/=" | "%=" | "**=" | "<<=" | ">>=" | ">>>=" | "&=" | "|=" | "^=" | "&&=" | "||=" | "??="
+    )
+}
+
+/*
+Binary and logical expressions
+*/
+// Precedence climbing, every operator binds tighter than the ones before it in this table
+fn binary_precedence(token: &Token) -> Option<usize> {
+    if is_keyword(token, "instanceof") || is_keyword(token, "in") {
+        return Some(7);
+    }
+
+    if token.token_type != TokenType::Operator {
+        return None;
+    }
+
+    let precedence = match token.value.as_str() {
+        "||" | "??" => 1,
+        "&&" => 2,
+        "|" => 3,
+        "^" => 4,
+        "&" => 5,
+        "==" | "!=" | "===" | "!==" => 6,
+        "<" | ">" | "<=" | ">=" => 7,
+        "<<" | ">>" | ">>>" => 8,
+        "+" | "-" => 9,
+        "*" | "/" | "%" => 10,
+        "**" => 11,
+        _ => return None,
+    };
+
+    Some(precedence)
+}
+
+fn parse_binary_expression(parser: &mut AstParser, min_precedence: usize) -> Option<Expression> {
+    let mut left = match parser.token() {
+        Some(token) if token.token_type == TokenType::PrivateName => parse_private_in_left(parser),
+        _ => parse_unary_expression(parser)?,
+    };
+
+    while let Some(token) = parser.token() {
+        let (operator, precedence) = match binary_precedence(token) {
+            Some(precedence) if precedence > min_precedence => (token.value.clone(), precedence),
+            _ => break,
+        };
+
+        parser.step(); // Skip operator
+
+        // Exponentiation is right associative, all other binary operators are left associative
+        let next_precedence = if operator == "**" { precedence - 1 } else { precedence };
+        let right = parse_binary_expression(parser, next_precedence)
+            .unwrap_or_else(|| panic!("Expected an expression after \"{}\"", operator));
+
+        let range = (left.range().0, right.range().1);
+
+        left = if matches!(operator.as_str(), "&&" | "||" | "??") {
+            Expression::LogicalExpression(Box::new(LogicalExpression { operator, left, right, range }))
+        } else {
+            Expression::BinaryExpression(Box::new(BinaryExpression { operator, left, right, range }))
+        };
+    }
+
+    Some(left)
+}
+
+// `#x in object` is the only place a private name stands on its own
+fn parse_private_in_left(parser: &mut AstParser) -> Expression {
+    let name = parser.consume().unwrap();
+    let identifier = PrivateIdentifier {
+        name: name.value.clone(),
+        range: name.range
+    };
+
+    if !parser.token().is_some_and(|token| is_keyword(token, "in")) {
+        panic!("Unexpected private name {} at {:?}", identifier.name, identifier.range);
+    }
+
+    Expression::PrivateIdentifier(identifier)
+}
+
+/*
+Unary and update expressions
+*/
+fn parse_unary_expression(parser: &mut AstParser) -> Option<Expression> {
+    let token = parser.token()?;
+
+    if parser.in_async && is_keyword(token, "await") {
+        let start = token.range.0;
+        parser.step();
+        parser.has_top_level_await |= !parser.in_function;
+
+        let argument = parse_unary_expression(parser).expect("Expected an expression after \"await\"");
+        let range = (start, argument.range().1);
+
+        return Some(Expression::AwaitExpression(Box::new(AwaitExpression { argument, range })));
+    }
+
+    if is_unary_operator(token) {
+        let operator = token.value.clone();
+        let start = token.range.0;
+        parser.step();
+
+        let argument = parse_unary_expression(parser)
+            .unwrap_or_else(|| panic!("Expected an expression after \"{}\"", operator));
+        let range = (start, argument.range().1);
+
+        if let Expression::MemberExpression(member) = &argument {
+            if operator == "delete" && matches!(member.property, Expression::PrivateIdentifier(_)) {
+                panic!("Private fields can not be deleted at {:?}", range);
+            }
+        }
+
+        return Some(Expression::UnaryExpression(Box::new(UnaryExpression { operator, argument, range })));
+    }
+
+    if is_update_operator(token) {
+        let operator = token.value.clone();
+        let start = token.range.0;
+        parser.step();
+
+        let argument = parse_unary_expression(parser)
+            .unwrap_or_else(|| panic!("Expected an expression after \"{}\"", operator));
+        let range = (start, argument.range().1);
+
+        return Some(Expression::UpdateExpression(Box::new(UpdateExpression { operator, prefix: true, argument, range })));
+    }
+
+    let argument = parse_left_hand_side_expression(parser)?;
+
+    match parser.token() {
+        Some(token) if is_update_operator(token) => {
+            let operator = token.value.clone();
+            let range = (argument.range().0, token.range.1);
+            parser.step();
+
+            Some(Expression::UpdateExpression(Box::new(UpdateExpression { operator, prefix: false, argument, range })))
+        },
+        _ => Some(argument)
+    }
+}
+
+fn is_unary_operator(token: &Token) -> bool {
+    match token.token_type {
+        TokenType::Operator => matches!(token.value.as_str(), "!" | "-" | "+" | "~"),
+        TokenType::Identifier => matches!(token.value.as_str(), "typeof" | "void" | "delete"),
+        _ => false,
+    }
+}
+
+fn is_update_operator(token: &Token) -> bool {
+    token.token_type == TokenType::Operator && matches!(token.value.as_str(), "++" | "--")
+}
+
+/*
+Call and member expressions
+*/
+fn parse_left_hand_side_expression(parser: &mut AstParser) -> Option<Expression> {
+    let mut expression = parse_new_or_primary_expression(parser)?;
+    let mut optional_chain = false;
+
+    while let Some(token) = parser.token() {
+        // `?.` is followed by a name, `[key]` or the arguments of a call
+        let optional = is_optional_chain(token);
+
+        if optional {
+            optional_chain = true;
+            parser.step(); // Skip ?.
+        }
+
+        let token = parser.token().expect("Unexpected end of input after \"?.\"");
+
+        if is_function_open_parenthesis(token) {
+            let (arguments, end) = parse_call_arguments(parser);
+            let range = (expression.range().0, end);
+
+            expression = Expression::CallExpression(Box::new(CallExpression {
+                callee: expression,
+                arguments,
+                optional,
+                range
+            }));
+
+            continue;
+        }
+
+        if optional {
+            expression = if is_open_square_bracket(token) {
+                parse_computed_member(parser, expression, true)
+            } else {
+                parse_named_member(parser, expression, true)
+            };
+
+            continue;
+        }
+
+        match parse_member_property(parser, expression) {
+            Ok(member) => expression = member,
+            Err(object) => {
+                expression = object;
+                break;
+            },
+        }
+    }
+
+    // The chain ends here, so a nullish link short-circuits everything up to this point and nothing after it
+    if optional_chain {
+        let range = expression.range();
+        expression = Expression::ChainExpression(Box::new(ChainExpression { expression, range }));
     }
 
-    let mut body = Vec::new(); 
+    Some(expression)
+}
 
-    // Always skip the first bracket by stepping over it
-    let mut consumed = parser.consume();
-    let start = consumed.unwrap().range.0;
+fn is_optional_chain(token: &Token) -> bool {
+    token.token_type == TokenType::Operator && token.value == "?."
+}
 
-    while consumed.is_some() && !is_end_block_statement(&consumed.unwrap()) {
+// The callee of `new` is a member expression without calls, `new a.B()` but not `new a()()`
+fn parse_new_or_primary_expression(parser: &mut AstParser) -> Option<Expression> {
+    if !is_keyword(parser.token()?, "new") {
+        return parse_primary_expression(parser);
+    }
 
-        // Block statement
-        let block_statement = parse_block_statement(parser);
-        if block_statement.is_some() {
-            let value = AstNode::BlockStatement(block_statement.unwrap());
-            body.push(value);
-        }
+    let start = parser.consume().unwrap().range.0; // Skip new
 
-        // Function declaration
-        let function_declaration = parse_function_declaration(parser);
-        if function_declaration.is_some() {
-            let value = AstNode::FunctionDeclaration(function_declaration.unwrap());
+    let mut callee = parse_new_or_primary_expression(parser).expect("Expected an expression after \"new\"");
 
-            body.push(value);
+    loop {
+        match parse_member_property(parser, callee) {
+            Ok(member) => callee = member,
+            Err(object) => {
+                callee = object;
+                break;
+            },
         }
+    }
 
-        // Variable declaration
-        let variable_declaration = parse_variable_declaration(parser);
-        if variable_declaration.is_some() {
-            let value = AstNode::VariableDeclaration(variable_declaration.unwrap());
+    // `new F` is the same as `new F()`
+    let (arguments, end) = match parser.token() {
+        Some(token) if is_function_open_parenthesis(token) => parse_call_arguments(parser),
+        _ => (Vec::new(), callee.range().1),
+    };
 
-            body.push(value);
-        }
+    Some(Expression::NewExpression(Box::new(NewExpression {
+        callee,
+        arguments,
+        range: (start, end)
+    })))
+}
 
-        // Expression statement
-        let expression_statement = parse_expression_statement(parser);
-        if expression_statement.is_some() {
-            let value = AstNode::ExpressionStatement(expression_statement.unwrap());
+// Extends `object` with `.name` or `[expression]`, or hands it back when neither follows
+fn parse_member_property(parser: &mut AstParser, object: Expression) -> Result<Expression, Expression> {
+    let token = match parser.token() {
+        Some(token) => token,
+        None => return Err(object),
+    };
 
-            body.push(value);
-        }
+    if is_expression_seperator(token) {
+        parser.step(); // Skip .
 
-        consumed = parser.consume();
+        return Ok(parse_named_member(parser, object, false));
     }
 
-    let end = consumed.unwrap().range.1;
+    if is_open_square_bracket(token) {
+        return Ok(parse_computed_member(parser, object, false));
+    }
 
-    Some(BlockStatement {
-        body: body,
-        range: (start, end)
-    })
+    Err(object)
 }
 
-pub fn is_start_block_statement(token: &Token) -> bool {
-    token.token_type == TokenType::CurlyBraces && token.value == String::from("{")
-}
+// The name after `.` or `?.`
+fn parse_named_member(parser: &mut AstParser, object: Expression, optional: bool) -> Expression {
+    let name = parser.consume().expect("Unexpected end of input, expected a property name");
 
-pub fn is_end_block_statement(token: &Token) -> bool {
-    token.token_type == TokenType::CurlyBraces && token.value == String::from("}")
+    // Reserved words are fine as property names, `a.if`
+    let property = match name.token_type {
+        TokenType::PrivateName => Expression::PrivateIdentifier(PrivateIdentifier {
+            name: name.value.clone(),
+            range: name.range
+        }),
+        _ if is_property_name(name) => Expression::Identifier(Identifier {
+            name: name.value.clone(),
+            range: name.range
+        }),
+        _ => panic!("Unexpected token: {:?}, expected a property name", name),
+    };
+    let range = (object.range().0, property.range().1);
+
+    Expression::MemberExpression(Box::new(MemberExpression {
+        object,
+        property,
+        computed: false,
+        optional,
+        range
+    }))
 }
 
-/*
-Function declaration
-*/
-pub fn parse_function_declaration(parser: &mut AstParser) -> Option<FunctionDeclaration> {
+fn parse_computed_member(parser: &mut AstParser, object: Expression, optional: bool) -> Expression {
+    parser.step(); // Skip [
 
-    if !is_start_function_declaration(parser) {
-        return None;
-    }
+    let property = parse_expression(parser).expect("Expected an expression after \"[\"");
+    let end = expect_close_square_bracket(parser).1;
+    let range = (object.range().0, end);
 
-    let keyword = parser.consume().unwrap();
-    let start = keyword.range.0;
+    Expression::MemberExpression(Box::new(MemberExpression {
+        object,
+        property,
+        computed: true,
+        optional,
+        range
+    }))
+}
 
-    let name = parser.consume().unwrap();
+fn parse_call_arguments(parser: &mut AstParser) -> (Vec<Expression>, usize) {
+    let mut arguments = Vec::new();
 
-    let identifier = Identifier {
-        name: name.value.clone(),
-        range: (name.range.0, name.range.1)
-    };
+    parser.step(); // Skip open paren
 
-    parser.step(); // Step over param open
+    loop {
+        let token = parser.token().expect("Unexpected end of input, expected \")\"");
+
+        if is_function_close_parenthesis(token) {
+            break;
+        }
 
-    if has_function_paremeter(parser.token().unwrap()) {
-        // Parse params
+        arguments.push(parse_list_element(parser));
 
+        match parser.token() {
+            Some(seperator) if is_expression_param_seperator(seperator) => parser.step(),
+            _ => break
+        }
     }
 
-    parser.step(); // Step over param close
-    
-    let body = parse_block_statement(parser);
-    
-    if body.is_none() {
-        return None;
-    }
+    let close = parser.consume().expect("Unexpected end of input, expected \")\"");
 
-    let body = body.unwrap();
-    let end = body.range.1;
+    if !is_function_close_parenthesis(close) {
+        panic!("Unexpected token: {:?}, expected \")\"", close);
+    }
 
-    Some(
-        FunctionDeclaration {
-            id: identifier,
-            params: Vec::new(),
-            body,
-            range: (start, end)
-        }
-    )
+    (arguments, close.range.1)
 }
 
-pub fn is_start_function_declaration(parser: &AstParser) -> bool {
-    let mut current_step = 0; 
+/*
+Primary expression
+*/
+fn parse_primary_expression(parser: &mut AstParser) -> Option<Expression> {
+    let token = parser.token()?;
 
-    // Function keyword
-    let keyword = parser.peek_steps(current_step);
-    if keyword.is_none() || !is_function_keyword(keyword.unwrap()) {
-        return false;
-    }
-    
-    current_step += 1;
+    match token.token_type {
+        TokenType::Number |
+        TokenType::BigInt |
+        TokenType::String |
+        TokenType::Boolean |
+        TokenType::Null |
+        TokenType::RegExp => {
+            let literal = parse_literal(token);
+            parser.step();
+
+            Some(literal)
+        },
+        TokenType::Identifier if is_keyword(token, "function") || is_async_function(parser) => parse_function_expression(parser),
+        TokenType::Identifier if is_keyword(token, "class") => parse_class_expression(parser),
+        TokenType::Identifier if is_keyword(token, "super") => {
+            let range = token.range;
+            parser.step();
+
+            // `super` has no value of its own, it has to be called or have a property read
+            match parser.token() {
+                Some(next) if is_function_open_parenthesis(next) || is_expression_seperator(next) || is_open_square_bracket(next) => {},
+                _ => panic!("'super' keyword unexpected here"),
+            }
 
-    // Function name
-    let function_name = parser.peek_steps(current_step);
-    if function_name.is_none() || !is_function_name(function_name.unwrap()) {
-        return false;
+            Some(Expression::Super(Super { range }))
+        },
+        TokenType::Identifier if is_keyword(token, "import") => Some(parse_import_expression(parser)),
+        TokenType::Identifier if is_keyword(token, "this") => {
+            let range = token.range;
+            parser.step();
+
+            Some(Expression::ThisExpression(ThisExpression { range }))
+        },
+        TokenType::Identifier if !is_reserved_word(token) => {
+            let identifier = Identifier {
+                name: token.value.clone(),
+                range: token.range
+            };
+            parser.step();
+
+            Some(Expression::Identifier(identifier))
+        },
+        TokenType::CurlyBraces if token.value == "{" => parse_object_expression(parser),
+        TokenType::SquareBrackets if token.value == "[" => parse_array_expression(parser),
+        TokenType::Parenthesis if token.value == "(" => {
+            parser.step(); // Skip open paren
+
+            let expression = parse_expression(parser).expect("Expected an expression after \"(\"");
+            let close = parser.consume().expect("Unexpected end of input, expected \")\"");
+
+            if !is_function_close_parenthesis(close) {
+                panic!("Unexpected token: {:?}, expected \")\"", close);
+            }
+
+            Some(expression)
+        },
+        _ => None
     }
-    
-    current_step += 1;
+}
 
-    // Parenthesis
-    let open_parenthesis = parser.peek_steps(current_step);
-    if open_parenthesis.is_none() || !is_function_open_parenthesis(open_parenthesis.unwrap()) {
-        return false;
+// `import(specifier)` anywhere, `import.meta` only in modules
+fn parse_import_expression(parser: &mut AstParser) -> Expression {
+    let start = parser.consume().unwrap().range.0; // Skip import
+    let token = parser.consume().expect("Unexpected end of input after \"import\"");
+
+    if is_expression_seperator(token) {
+        let in_module = parser.in_module;
+
+        return match parser.consume() {
+            Some(property) if is_keyword(property, "meta") && in_module => Expression::ImportMeta(ImportMeta { range: (start, property.range.1) }),
+            Some(property) if is_keyword(property, "meta") => panic!("Cannot use \"import.meta\" outside a module"),
+            property => panic!("Unexpected token: {:?}, expected \"meta\"", property),
+        };
     }
 
-    current_step += 1;
+    let source = parse_assignment_expression(parser).expect("Expected a module specifier in \"import()\"");
 
-    // TODO: Also check for params
+    if parser.token().is_some_and(is_expression_param_seperator) {
+        parser.step();
+    }
 
-    true
+    match parser.consume() {
+        Some(close) if is_function_close_parenthesis(close) => {
+            let range = (start, close.range.1);
+            Expression::ImportExpression(Box::new(ImportExpression { source, range }))
+        },
+        token => panic!("Unexpected token: {:?}, expected \")\"", token),
+    }
 }
 
-fn is_function_keyword(token: &Token) -> bool {
-    token.token_type == TokenType::Identifier && token.value == String::from("function")
-}
+fn parse_literal(token: &Token) -> Expression {
+    let raw = token.raw_value.clone();
+    let range = token.range;
+
+    match token.token_type {
+        TokenType::Number => Expression::NumericLiteral(NumericLiteral {
+            value: parse_number_value(&token.value),
+            raw,
+            range
+        }),
+        TokenType::BigInt => Expression::BigIntLiteral(BigIntLiteral {
+            value: parse_bigint_value(&token.value),
+            raw,
+            range
+        }),
+        TokenType::String => Expression::StringLiteral(StringLiteral {
+            value: token.value.clone(),
+            raw,
+            range
+        }),
+        TokenType::Boolean => Expression::BooleanLiteral(BooleanLiteral {
+            value: token.value == "true",
+            raw,
+            range
+        }),
+        TokenType::Null => Expression::NullLiteral(NullLiteral { raw, range }),
+        TokenType::RegExp => {
+            let (pattern, flags) = token.value[1..].rsplit_once('/').unwrap();
+
+            for (index, flag) in flags.char_indices() {
+                if !"dgimsuvy".contains(flag) || flags[..index].contains(flag) {
+                    panic!("Invalid regular expression flags: {}", raw);
+                }
+            }
 
-fn is_function_name(token: &Token) -> bool {
-    token.token_type == TokenType::Identifier
+            Expression::RegExpLiteral(RegExpLiteral {
+                pattern: pattern.to_string(),
+                flags: flags.to_string(),
+                raw,
+                range
+            })
+        },
+        _ => unreachable!(),
+    }
 }
 
-fn is_function_open_parenthesis(token: &Token) -> bool {
-    token.token_type == TokenType::Parenthesis && token.value == String::from("(")
+// The tokenizer leaves out numeric separators and the BigInt suffix, the radix prefix is still there
+fn parse_number_value(value: &str) -> f64 {
+    match parse_radix_integer(value) {
+        Some(integer) => integer.to_f64().unwrap_or(f64::INFINITY),
+        None => value.parse::<f64>().unwrap(),
+    }
 }
 
-fn has_function_paremeter(token: &Token) -> bool {
-    false
+fn parse_bigint_value(value: &str) -> BigInt {
+    parse_radix_integer(value).unwrap_or_else(|| value.parse::<BigInt>().unwrap())
 }
 
-fn is_function_close_parenthesis(token: &Token) -> bool {
-    token.token_type == TokenType::Parenthesis && token.value == String::from(")")
+fn parse_radix_integer(value: &str) -> Option<BigInt> {
+    let radix = match value.get(..2)? {
+        "0x" | "0X" => 16,
+        "0o" | "0O" => 8,
+        "0b" | "0B" => 2,
+        _ => return None,
+    };
+
+    BigInt::parse_bytes(&value.as_bytes()[2..], radix)
 }
 
 /*
-Variable declaration
+Function and arrow function expressions
 */
-pub fn parse_variable_declaration(parser: &mut AstParser) -> Option<VariableDeclaration> {
-    let mut declarations = Vec::<VariableDeclarator>::new();
+// The name of a function expression is only visible inside its own body
+fn parse_function_expression(parser: &mut AstParser) -> Option<Expression> {
+    let start = parser.token().unwrap().range.0;
+    let is_async = parse_async_keyword(parser);
+    parser.step(); // Skip function
+
+    let generator = parse_generator_star(parser);
+
+    let id = match parser.token() {
+        Some(token) if is_function_name(token) && !is_reserved_word(token) => {
+            let id = Identifier {
+                name: token.value.clone(),
+                range: token.range
+            };
+            parser.step();
+
+            Some(id)
+        },
+        _ => None,
+    };
 
-    if !is_variable_declaration(parser) {
-        return None;
+    match parser.token() {
+        Some(token) if is_function_open_parenthesis(token) => {},
+        Some(token) => panic!("Unexpected token: {:?}, expected \"(\"", token),
+        None => panic!("Unexpected end of input, expected \"(\""),
     }
 
-    let keyword = parser.consume().unwrap();
-    let start = keyword.range.0;
+    let params = parse_function_params(parser);
+    let body = parse_function_body(parser, generator, is_async);
+    let end = body.range.1;
 
-    let name = parser.consume().unwrap();
-    let name_range = name.range.clone();
+    Some(Expression::FunctionExpression(Box::new(FunctionExpression {
+        id,
+        params,
+        body,
+        generator,
+        is_async,
+        range: (start, end)
+    })))
+}
 
-    let identifier = Identifier {
-        name: name.value.clone(),
-        range: (name.range.0, name.range.1)
+// Arrow parameters look like a parenthesised expression until the `=>`, so the matching `)` is found first
+fn is_start_arrow_function(parser: &AstParser) -> bool {
+    let prefix = is_async_arrow(parser) as usize;
+
+    let token = match parser.peek_steps(prefix) {
+        Some(token) => token,
+        None => return false,
     };
 
-    parser.step(); // Skip assignment
+    // `x => x * 2`, a single parameter needs no parentheses
+    if token.token_type == TokenType::Identifier && !is_reserved_word(token) {
+        return parser.peek_steps(prefix + 1).is_some_and(is_arrow);
+    }
 
-    let value = parser.consume().unwrap();
-    let value_range = value.range.clone();
+    if !is_function_open_parenthesis(token) {
+        return false;
+    }
 
-    let literal = match value.token_type {
-        TokenType::Number |
-        TokenType::String => { 
-            Some(VariableLiteral::Literal(Literal{
-                value: value.value.clone(),
-                raw_value: value.raw_value.clone(),
-                range: value.range, 
-            }))
-        },
-        _ => { None }
-    };
-    
-    let literal = literal.unwrap();
+    let mut depth = 0;
+    let mut steps = prefix;
 
-    let declarator = VariableDeclarator {
-        id: identifier,
-        init: literal,
-        range: (name_range.0, value_range.1)
-    };
+    while let Some(token) = parser.peek_steps(steps) {
+        if token.token_type == TokenType::Parenthesis {
+            depth += if token.value == "(" { 1 } else { -1 };
+        }
 
-    declarations.push(declarator);
+        steps += 1;
 
-    let terminator = parser.consume().unwrap();
-    let end = terminator.range.1;
+        if depth == 0 {
+            return parser.peek_steps(steps).is_some_and(is_arrow);
+        }
+    }
 
-    Some(VariableDeclaration {
-        declarations: declarations,
-        range: (start, end)
-    })
+    false
 }
 
-// TODO: Currently we only support single variables
-pub fn is_variable_declaration(parser: &AstParser) -> bool {
+// `async` before the parameters, as long as it is not the only parameter itself like in `async => async`
+fn is_async_arrow(parser: &AstParser) -> bool {
+    parser.token().is_some_and(|token| is_keyword(token, "async")) && parser.peek_steps(1).is_some_and(|next| !is_arrow(next))
+}
 
-    let keyword = parser.peek_steps(0);
-    if keyword.is_none() || !is_variable_keyword(keyword.unwrap()) {
-        return false;
-    }
+fn parse_arrow_function(parser: &mut AstParser) -> Expression {
+    let start = parser.token().unwrap().range.0;
+    let is_async = is_async_arrow(parser);
 
-    let name  = parser.peek_steps(1);
-    if name.is_none() || !is_variable_name(name.unwrap()) {
-        return false;
+    if is_async {
+        parser.step();
     }
 
-    let assignment_operator = parser.peek_steps(2);
-    if assignment_operator.is_none() || !is_variable_assignment(assignment_operator.unwrap()) {
-        return false;
-    }
+    let token = parser.token().unwrap();
 
-    let init =  parser.peek_steps(3);
-    if init.is_none() {
-        return false;
-    }
+    let params = match is_function_open_parenthesis(token) {
+        true => parse_function_params(parser),
+        false => {
+            let param = parser.consume().unwrap();
 
-    let terminator = parser.peek_steps(4);
-    if terminator.is_none() || !is_variable_terminator(terminator.unwrap()) {
-        return false;
-    }
+            vec![Pattern::Identifier(Identifier {
+                name: param.value.clone(),
+                range: param.range
+            })]
+        },
+    };
 
-    true
-}
+    parser.step(); // Skip =>
 
-fn is_variable_keyword(token: &Token) -> bool {
-    token.token_type == TokenType::Identifier && token.value == String::from("var")
-}
+    let token = parser.token().expect("Unexpected end of input after \"=>\"");
 
-fn is_variable_name(token: &Token) -> bool {
-    token.token_type == TokenType::Identifier
-}
+    // Arrow functions are never generators, not even inside one
+    let body = match is_start_block_statement(token) {
+        true => ArrowFunctionBody::BlockStatement(parse_function_body(parser, false, is_async)),
+        false => {
+            let in_generator = std::mem::replace(&mut parser.in_generator, false);
+            let in_async = std::mem::replace(&mut parser.in_async, is_async);
+            let in_function = std::mem::replace(&mut parser.in_function, true);
+            let body = parse_assignment_expression(parser).expect("Expected an expression after \"=>\"");
+            parser.in_generator = in_generator;
+            parser.in_async = in_async;
+            parser.in_function = in_function;
 
-fn is_variable_assignment(token: &Token) -> bool {
-    token.token_type == TokenType::Operator && token.value == String::from("=")
+            ArrowFunctionBody::Expression(body)
+        },
+    };
+
+    let end = match &body {
+        ArrowFunctionBody::BlockStatement(block) => block.range.1,
+        ArrowFunctionBody::Expression(expression) => expression.range().1,
+    };
+
+    Expression::ArrowFunctionExpression(Box::new(ArrowFunctionExpression {
+        params,
+        body,
+        is_async,
+        range: (start, end)
+    }))
 }
 
-fn is_variable_terminator(token: &Token) -> bool {
-    token.token_type == TokenType::Terminator && token.value == String::from(";")
+fn is_arrow(token: &Token) -> bool {
+    token.token_type == TokenType::Operator && token.value == "=>"
 }
 
 /*
-Expression statement
+Array expression
 */
-pub fn parse_expression_statement(parser: &mut AstParser) -> Option<ExpressionStatement> {
-
-    let mut identifiers = VecDeque::new();
-    let mut arguments = Vec::new();
-
-    let start = parser.peek_current()
-        .unwrap()
-        .range
-        .clone().0;
+fn parse_array_expression(parser: &mut AstParser) -> Option<Expression> {
+    let start = parser.consume().unwrap().range.0; // Skip [
+    let mut elements = Vec::new();
 
     loop {
-        let name = parser.consume().unwrap();
+        let token = parser.token().expect("Unexpected end of input, expected \"]\"");
 
-        let identifier = Box::new(Identifier {
-            name: name.value.clone(),
-            range: name.range.clone()
-        });
+        if is_close_square_bracket(token) {
+            break;
+        }
+
+        // A comma without an element before it leaves a hole
+        if is_expression_param_seperator(token) {
+            elements.push(None);
+            parser.step();
+            continue;
+        }
 
-        identifiers.push_back(identifier);
+        elements.push(Some(parse_list_element(parser)));
 
-        let seperator = parser.peek_current().unwrap();
+        // A trailing comma is allowed before the closing bracket and does not add a hole
+        let token = parser.token().expect("Unexpected end of input, expected \"]\"");
 
-        if !is_expression_seperator(seperator) {
+        if is_close_square_bracket(token) {
             break;
         }
 
+        if !is_expression_param_seperator(token) {
+            panic!("Unexpected token: {:?}, expected \",\" or \"]\"", token);
+        }
+
         parser.step();
     }
 
-    parser.step(); // Skip open paren
+    let end = expect_close_square_bracket(parser).1;
 
-    if parser.token().is_some() && is_expression_param(parser.token().unwrap()) {
-        
-        loop {
-            let param = parser.consume();
-            if param.is_some() {
-                let param = param.unwrap();
-
-                arguments.push(
-                    Literal {
-                        raw_value: param.raw_value.clone(),
-                        value: param.value.clone(),
-                        range: param.range,
-                    }
-                );
-            }
+    Some(Expression::ArrayExpression(Box::new(ArrayExpression {
+        elements,
+        range: (start, end)
+    })))
+}
 
-            let seperator = parser.token();
+// An element of an array literal or an argument of a call, either of which can be spread
+fn parse_list_element(parser: &mut AstParser) -> Expression {
+    let token = parser.token().unwrap();
 
-            if seperator.is_none() {
-                break;
-            }
+    if !is_spread(token) {
+        return parse_assignment_expression(parser)
+            .unwrap_or_else(|| panic!("Unexpected token: {:?}", parser.token().unwrap()));
+    }
 
-            if seperator.is_some() && is_expression_param_seperator(seperator.unwrap()) {
-                parser.step();
-            } else {
-                break;
-            }
-        }
+    Expression::SpreadElement(Box::new(parse_spread_element(parser)))
+}
 
-    }
+fn parse_spread_element(parser: &mut AstParser) -> SpreadElement {
+    let start = parser.consume().unwrap().range.0; // Skip ...
 
-    parser.step(); // Skip close paren
-    
-    let terminator = parser.consume().unwrap();
-    let end = terminator.range.1;
+    let argument = parse_assignment_expression(parser).expect("Expected an expression after \"...\"");
+    let end = argument.range().1;
 
-    Some(ExpressionStatement {
-        expression: CallExpression {
-            callee: build_callee(&mut identifiers),
-            arguments,
-            range: (start, end)
-        },
+    SpreadElement {
+        argument,
         range: (start, end)
-    })
+    }
 }
 
-fn build_callee(identifiers: &mut VecDeque<Box<Identifier>>) -> CallExpressionCallee {
-
-    // If the identifiers length == 1, return an CallExpression
-    // Otherwise build a stack from the MemberExpressions
-    if identifiers.len() == 1 {
-        let identifier = identifiers.remove(0).unwrap();
-        return CallExpressionCallee::Identifier(identifier);
+fn is_spread(token: &Token) -> bool {
+    token.token_type == TokenType::Separator && token.value == "..."
+}
 
-    } else {
-        let identifier = identifiers.pop_front().unwrap();
+/*
+Object expression
+*/
+fn parse_object_expression(parser: &mut AstParser) -> Option<Expression> {
+    let start = parser.consume().unwrap().range.0; // Skip {
+    let mut properties = Vec::new();
 
-        // Use RefCell for dynamic borrow checking, as the while loop doesn't really like the static borrow checker from the compiler.
-        let root_member_expression = RefCell::new(MemberExpression::new(*identifier));
+    loop {
+        let token = parser.token().expect("Unexpected end of input, expected \"}\"");
 
-        // Use this reference of a RefCell to keep track of the lastest item in the stack.
-        let last_expression_ref = &root_member_expression;
+        if is_end_block_statement(token) {
+            break;
+        }
 
-        while identifiers.len() > 0 {
-            let identifier = identifiers.pop_front().unwrap();
+        let member = if is_spread(token) {
+            ObjectMember::SpreadElement(parse_spread_element(parser))
+        } else {
+            ObjectMember::Property(parse_property(parser))
+        };
 
-            let member_expression = RefCell::new(MemberExpression::new(*identifier));
+        properties.push(member);
 
-            last_expression_ref.swap(&member_expression);
+        // A trailing comma is allowed before the closing brace
+        let token = parser.token().expect("Unexpected end of input, expected \"}\"");
 
-            // Wrap the value in a box, to store it on the heap
-            let member_expression_object = Some(
-                Box::new(
-                    member_expression.borrow_mut().to_owned()
-                )
-            );
+        if is_end_block_statement(token) {
+            break;
+        }
 
-            last_expression_ref.try_borrow_mut()
-                .unwrap()
-                .object = member_expression_object;
+        if !is_expression_param_seperator(token) {
+            panic!("Unexpected token: {:?}, expected \",\" or \"}}\"", token);
         }
 
-        let boxed_root_member_expression = Box::new(
-            root_member_expression.borrow_mut().to_owned()
-        );
-        return CallExpressionCallee::MemberExpression(boxed_root_member_expression);
+        parser.step();
     }
+
+    let end = parser.consume().unwrap().range.1;
+
+    Some(Expression::ObjectExpression(Box::new(ObjectExpression {
+        properties,
+        range: (start, end)
+    })))
 }
 
-pub fn is_expression_statement(parser: &AstParser) -> bool {
+fn parse_property(parser: &mut AstParser) -> Property {
+    let is_async = parse_async_modifier(parser, is_start_property_key);
+    let generator = parse_generator_star(parser);
+    let token = parser.token().expect("Unexpected end of input in object literal");
+
+    // `get` and `set` only start an accessor when a property name follows, `{ get: 1 }` and `{ get() {} }` are plain
+    let kind = match parser.peek_steps(1) {
+        _ if generator || is_async => PropertyKind::Init,
+        Some(next) if is_keyword(token, "get") && is_start_property_key(next) => PropertyKind::Get,
+        Some(next) if is_keyword(token, "set") && is_start_property_key(next) => PropertyKind::Set,
+        _ => PropertyKind::Init,
+    };
 
-    // Check if the expression statement has MemberExpressions
-    // Defined by a chain of 'Literal' -> '.' until the final expression
-    // For example: console.log('foobar');
-    // Where console is the MemberExpression and log is the last expression (So the call expression)
+    // Only used for shorthands, where the first token is the key
+    let is_reserved = is_reserved_word(token);
 
-    let mut offset = 0;
-    
-    loop {
-        let name = parser.peek_steps(offset);
-        if name.is_none() || !is_expression_name(name.unwrap()) {
-            break;
-        }
+    if kind != PropertyKind::Init {
+        parser.step();
+    }
 
-        offset += 1;
+    let (key, computed) = parse_property_key(parser);
+    let next = parser.token().expect("Unexpected end of input in object literal");
 
-        let seperator = parser.peek_steps(offset);
-        if seperator.is_none() || !is_expression_seperator(seperator.unwrap()) {
-            break;
-        }
+    if is_function_open_parenthesis(next) {
+        let params = parse_function_params(parser);
+        let body = parse_function_body(parser, generator, is_async);
+        let range = (key.range().0, body.range.1);
 
-        offset += 1;
+        let value = Expression::FunctionExpression(Box::new(FunctionExpression {
+            id: None,
+            params,
+            body,
+            generator,
+            is_async,
+            range
+        }));
+
+        return Property {
+            key,
+            value,
+            method: kind == PropertyKind::Init,
+            kind,
+            computed,
+            shorthand: false,
+        };
     }
 
-    let open_paren = parser.peek_steps(offset);
-    if open_paren.is_none() || !is_function_open_parenthesis(open_paren.unwrap()) {
-        return false;
+    if kind != PropertyKind::Init || generator || is_async {
+        panic!("Unexpected token: {:?}, expected \"(\"", next);
     }
 
-    // Check for parameters
-    let param = parser.peek_steps(offset + 1);
-
-    if param.is_some() && is_expression_param(param.unwrap()) {
-        loop {
-            let param = parser.peek_steps(offset + 1);
-    
-            if param.is_none() {
-                return false;
-            }
+    if is_property_colon(next) {
+        parser.step(); // Skip :
 
-            if param.is_some() && is_function_close_parenthesis(param.unwrap()) {
-                break;
-            }
+        let value = parse_assignment_expression(parser).expect("Expected an expression after \":\"");
 
-            offset += 1;
-        }
+        return Property { key, value, kind, computed, method: false, shorthand: false };
     }
 
-    let close_paren = parser.peek_steps(offset + 1);
-    if close_paren.is_none() || !is_function_close_parenthesis(close_paren.unwrap()) {
-        return false;
+    // `{ a }` is short for `{ a: a }`
+    match &key {
+        Expression::Identifier(identifier) if !computed && !is_reserved => Property {
+            value: Expression::Identifier(identifier.clone()),
+            key,
+            kind,
+            computed,
+            method: false,
+            shorthand: true,
+        },
+        _ => panic!("Unexpected token: {:?}, expected \":\"", next),
     }
+}
 
-    let terminator = parser.peek_steps(offset + 2);
-    if terminator.is_none() || !is_variable_terminator(terminator.unwrap()) {
-        return false;
+// Identifiers, strings and numbers name a property directly, `[expression]` computes it
+fn parse_property_key(parser: &mut AstParser) -> (Expression, bool) {
+    let token = parser.token().expect("Unexpected end of input, expected a property name");
+
+    if is_open_square_bracket(token) {
+        parser.step(); // Skip [
+
+        let key = parse_assignment_expression(parser).expect("Expected an expression after \"[\"");
+        expect_close_square_bracket(parser);
+
+        return (key, true);
     }
 
-    true
+    let key = match token.token_type {
+        TokenType::String | TokenType::Number | TokenType::BigInt => parse_literal(token),
+        _ if is_property_name(token) => Expression::Identifier(Identifier {
+            name: token.value.clone(),
+            range: token.range
+        }),
+        _ => panic!("Unexpected token: {:?}, expected a property name", token),
+    };
+
+    parser.step();
+
+    (key, false)
+}
+
+fn is_start_property_key(token: &Token) -> bool {
+    is_property_name(token) || is_open_square_bracket(token) ||
+        matches!(token.token_type, TokenType::String | TokenType::Number | TokenType::BigInt)
+}
+
+fn is_property_name(token: &Token) -> bool {
+    matches!(token.token_type, TokenType::Identifier | TokenType::Boolean | TokenType::Null)
+}
+
+fn is_property_colon(token: &Token) -> bool {
+    token.token_type == TokenType::Separator && token.value == ":"
+}
+
+fn is_open_square_bracket(token: &Token) -> bool {
+    token.token_type == TokenType::SquareBrackets && token.value == "["
+}
+
+fn is_close_square_bracket(token: &Token) -> bool {
+    token.token_type == TokenType::SquareBrackets && token.value == "]"
 }
 
-fn is_expression_param(token: &Token) -> bool {
-    token.token_type == TokenType::Identifier || 
-    token.token_type == TokenType::Number || 
-    token.token_type == TokenType::String
+fn expect_close_square_bracket(parser: &mut AstParser) -> (usize, usize) {
+    let close = parser.consume().expect("Unexpected end of input, expected \"]\"");
+
+    if !is_close_square_bracket(close) {
+        panic!("Unexpected token: {:?}, expected \"]\"", close);
+    }
+
+    close.range
 }
 
 fn is_expression_seperator(token: &Token) -> bool {
-    token.token_type == TokenType::Separator && token.value == ".".to_string()
+    token.token_type == TokenType::Separator && token.value == "."
 }
 
 fn is_expression_param_seperator(token: &Token) -> bool {
-    token.token_type == TokenType::Separator && token.value == ",".to_string()
+    token.token_type == TokenType::Separator && token.value == ","
 }
-
-fn is_expression_name(token: &Token) -> bool {
-    token.token_type == TokenType::Identifier
-}
\ No newline at end of file
@@ -5,12 +5,85 @@ mod ast;
 mod runtime;
 mod util;
 
+// js-runtime [--virtual-time] [--module] [--max-coroutine-stacks=<count>] [file]
+// Without a file the script baked into the binary runs. Files ending in .mjs, or any file with `--module`, run as modules.
 fn main() {
+    // The runtime measures how deep scripts recurse against a stack of known size, the main thread's depends on the platform
+    let thread = std::thread::Builder::new()
+        .stack_size(runtime::STACK_SIZE)
+        .spawn(run)
+        .expect("Failed to start the runtime thread");
+
+    // A panic has already been reported by the thread, exit the way it would have on the main thread
+    if thread.join().is_err() {
+        std::process::exit(101);
+    }
+}
+
+fn run() {
     println!("Running Joey-Script 1.0");
 
-    let input = String::from(include_str!("main.js"));
+    // Uncaught errors are reported the way Node reports them, an unsettled top-level await exits with 13 like there
+    if let Err(uncaught) = run_file() {
+        eprintln!("{}", uncaught);
+        std::process::exit(uncaught.exit_code());
+    }
+}
+
+fn run_file() -> Result<(), runtime::Uncaught> {
+    let (flags, files): (Vec<String>, Vec<String>) = std::env::args().skip(1).partition(|argument| argument.starts_with("--"));
+
+    // With `--virtual-time` the clock jumps ahead to the next timer instead of sleeping, so runs are deterministic
+    let clock: Box<dyn runtime::Clock> = match flags.iter().any(|flag| flag == "--virtual-time") {
+        true => Box::new(runtime::VirtualClock::default()),
+        false => Box::new(runtime::SystemClock::default()),
+    };
+
+    let mut runtime = runtime::Runtime::new(clock, Box::new(runtime::FileSystemLoader));
+
+    // Every suspended generator and async function holds a native stack, past the limit new ones throw a RangeError.
+    // The default leaves most of the process's memory mappings to everything else, raising it can run out of them.
+    if let Some(limit) = flags.iter().find_map(|flag| flag.strip_prefix("--max-coroutine-stacks=")) {
+        match limit.parse() {
+            Ok(limit) => runtime.coroutine_stacks.set_limit(limit),
+            Err(_) => {
+                eprintln!("Invalid --max-coroutine-stacks: {}", limit);
+                std::process::exit(1);
+            },
+        }
+    }
+
+    let path = match files.first() {
+        Some(path) => path,
+        None => {
+            let input = String::from(include_str!("main.js"));
+            let tokens = tokenizer::tokenize(&input);
+            let ast = ast::parse(&tokens);
+
+            return runtime::run(runtime, &ast, "main.js", &input);
+        },
+    };
+
+    // Module specifiers are relative to the working directory, a bare file name is not
+    if path.ends_with(".mjs") || flags.iter().any(|flag| flag == "--module") {
+        let specifier = match path.starts_with('/') || path.starts_with("./") || path.starts_with("../") {
+            true => path.clone(),
+            false => format!("./{}", path),
+        };
+
+        return runtime::run_module(runtime, &specifier);
+    }
+
+    let input = match std::fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Cannot read {}: {}", path, error);
+            std::process::exit(1);
+        },
+    };
+
     let tokens = tokenizer::tokenize(&input);
     let ast = ast::parse(&tokens);
 
-    runtime::run(&ast);
+    runtime::run(runtime, &ast, path, &input)
 }
@@ -0,0 +1,831 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::RuntimeResult, object::{find_property, get, set, ObjectRef, PropertyKey}, operations::{call, create_array_from_list, get_iterator_from_method, get_method, is_array, is_callable, is_strictly_equal, iterator_close, iterator_step_value, length_of_array_like, relative_index, same_value_zero, to_boolean, to_integer_or_infinity, to_number, to_object, to_string, to_uint32}, value::Value, Runtime};
+
+use super::{argument, iterator::{create_array_iterator, IterationKind}, native_constructor, native_function};
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    // A single number is the length of a new array with holes, anything else lists the elements
+    let array = native_constructor(intrinsics, "Array", &["items"], &intrinsics.array_prototype, |runtime, _, x| {
+        match x {
+            [Value::Number(length)] => {
+                if *length != to_uint32(*length) as f64 {
+                    return runtime.range_error("Invalid array length");
+                }
+
+                let array = create_array_from_list(runtime, vec![]);
+                set_length(runtime, &as_object(&array), *length as usize)?;
+
+                Ok(Some(array))
+            },
+            values => Ok(Some(create_array_from_list(runtime, values.to_vec()))),
+        }
+    });
+
+    let is_array_function = native_function(intrinsics, "isArray", &["value"], |_, _, x| {
+        Ok(Some(Value::Boolean(is_array(&argument(x, 0)))))
+    });
+
+    let of = native_function(intrinsics, "of", &[], |runtime, _, x| {
+        Ok(Some(create_array_from_list(runtime, x.to_vec())))
+    });
+
+    // Iterables give their values, anything else is read as an array-like
+    let from = native_function(intrinsics, "from", &["items"], |runtime, _, x| {
+        let items = argument(x, 0);
+        let mapper = argument(x, 1);
+        let this_arg = argument(x, 2);
+
+        if !matches!(mapper, Value::Undefined) && !is_callable(&mapper) {
+            return runtime.type_error(&format!("{} is not a function", mapper.to_display_string()));
+        }
+
+        if matches!(items, Value::Undefined | Value::Null) {
+            return runtime.type_error(&format!("{} is not iterable", items.to_display_string()));
+        }
+
+        let key = PropertyKey::from(&runtime.intrinsics.iterator_symbol);
+        let mut values = Vec::new();
+
+        match get_method(runtime, &items, &key)? {
+            // The mapper runs as the values come in, the iterator is closed if it throws
+            Some(method) => {
+                let record = get_iterator_from_method(runtime, &items, &method)?;
+
+                while let Some(value) = iterator_step_value(runtime, &record)? {
+                    match map_value(runtime, &mapper, &this_arg, value, values.len()) {
+                        Ok(value) => values.push(value),
+                        Err(error) => return iterator_close(runtime, &record, Err(error)),
+                    }
+                }
+            },
+            None => {
+                let object = to_object(runtime, &items)?;
+                let length = length_of_array_like(runtime, &object)?;
+
+                for index in 0..length {
+                    let value = get_index(runtime, &object, index)?;
+                    values.push(map_value(runtime, &mapper, &this_arg, value, index)?);
+                }
+            },
+        }
+
+        Ok(Some(create_array_from_list(runtime, values)))
+    });
+
+    if let Value::Object(constructor) = &array {
+        let mut constructor = constructor.borrow_mut();
+
+        constructor.define_builtin("isArray", is_array_function);
+        constructor.define_builtin("of", of);
+        constructor.define_builtin("from", from);
+    }
+
+    register_prototype(intrinsics);
+
+    variables.insert("Array".to_string(), array);
+}
+
+// The optional mapper of Array.from, called with the value and its index
+fn map_value(runtime: &mut Runtime, mapper: &Value, this_arg: &Value, value: Value, index: usize) -> RuntimeResult<Value> {
+    match mapper {
+        Value::Undefined => Ok(value),
+        mapper => call(runtime, mapper, this_arg.clone(), vec![value, Value::Number(index as f64)]),
+    }
+}
+
+// The methods are generic, they work on any object with a `length` and index properties
+fn register_prototype(intrinsics: &Intrinsics) {
+    let push = native_function(intrinsics, "push", &["items"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+
+        for (index, value) in x.iter().enumerate() {
+            set_index(runtime, &object, length + index, value.clone())?;
+        }
+
+        set_length(runtime, &object, length + x.len())?;
+
+        Ok(Some(Value::Number((length + x.len()) as f64)))
+    });
+
+    let pop = native_function(intrinsics, "pop", &[], |runtime, this, _| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+
+        if length == 0 {
+            set_length(runtime, &object, 0)?;
+            return Ok(Some(Value::Undefined));
+        }
+
+        let element = get_index(runtime, &object, length - 1)?;
+        delete_index(runtime, &object, length - 1)?;
+        set_length(runtime, &object, length - 1)?;
+
+        Ok(Some(element))
+    });
+
+    let shift = native_function(intrinsics, "shift", &[], |runtime, this, _| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+
+        if length == 0 {
+            set_length(runtime, &object, 0)?;
+            return Ok(Some(Value::Undefined));
+        }
+
+        let first = get_index(runtime, &object, 0)?;
+
+        for index in 1..length {
+            move_index(runtime, &object, index, index - 1)?;
+        }
+
+        delete_index(runtime, &object, length - 1)?;
+        set_length(runtime, &object, length - 1)?;
+
+        Ok(Some(first))
+    });
+
+    let unshift = native_function(intrinsics, "unshift", &["items"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+
+        if !x.is_empty() {
+            for index in (0..length).rev() {
+                move_index(runtime, &object, index, index + x.len())?;
+            }
+
+            for (index, value) in x.iter().enumerate() {
+                set_index(runtime, &object, index, value.clone())?;
+            }
+        }
+
+        set_length(runtime, &object, length + x.len())?;
+
+        Ok(Some(Value::Number((length + x.len()) as f64)))
+    });
+
+    let splice = native_function(intrinsics, "splice", &["start", "deleteCount"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+        let start = relative_index(runtime, &argument(x, 0), length, 0)?;
+
+        // Without a count everything from `start` goes, an undefined count removes nothing
+        let delete_count = match x.len() {
+            0 => 0,
+            1 => length - start,
+            _ => to_integer_or_infinity(runtime, &x[1])?.clamp(0.0, (length - start) as f64) as usize,
+        };
+
+        let items = x.get(2..).unwrap_or_default();
+
+        let removed = create_array_from_list(runtime, vec![]);
+        let removed_object = as_object(&removed);
+
+        for offset in 0..delete_count {
+            if has_index(&object, start + offset) {
+                let value = get_index(runtime, &object, start + offset)?;
+                removed_object.borrow_mut().create_data_property(PropertyKey::from(offset), value);
+            }
+        }
+
+        set_length(runtime, &removed_object, delete_count)?;
+
+        if items.len() < delete_count {
+            for index in start..length - delete_count {
+                move_index(runtime, &object, index + delete_count, index + items.len())?;
+            }
+
+            for index in (length - delete_count + items.len()..length).rev() {
+                delete_index(runtime, &object, index)?;
+            }
+        } else if items.len() > delete_count {
+            for index in (start..length - delete_count).rev() {
+                move_index(runtime, &object, index + delete_count, index + items.len())?;
+            }
+        }
+
+        for (offset, value) in items.iter().enumerate() {
+            set_index(runtime, &object, start + offset, value.clone())?;
+        }
+
+        set_length(runtime, &object, length - delete_count + items.len())?;
+
+        Ok(Some(removed))
+    });
+
+    let slice = native_function(intrinsics, "slice", &["start", "end"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+        let start = relative_index(runtime, &argument(x, 0), length, 0)?;
+        let end = relative_index(runtime, &argument(x, 1), length, length)?;
+
+        let result = create_array_from_list(runtime, vec![]);
+        let result_object = as_object(&result);
+
+        for index in start..end.max(start) {
+            if has_index(&object, index) {
+                let value = get_index(runtime, &object, index)?;
+                result_object.borrow_mut().create_data_property(PropertyKey::from(index - start), value);
+            }
+        }
+
+        set_length(runtime, &result_object, end.saturating_sub(start))?;
+
+        Ok(Some(result))
+    });
+
+    // Arrays are spread into the result, keeping their holes, other values are added as they are
+    let concat = native_function(intrinsics, "concat", &["items"], |runtime, this, x| {
+        let object = Value::Object(to_object(runtime, this)?);
+
+        let result = create_array_from_list(runtime, vec![]);
+        let result_object = as_object(&result);
+        let mut next = 0;
+
+        for item in std::iter::once(&object).chain(x.iter()) {
+            match item {
+                Value::Object(source) if source.borrow().is_array() => {
+                    let length = length_of_array_like(runtime, source)?;
+
+                    for index in 0..length {
+                        if has_index(source, index) {
+                            let value = get_index(runtime, source, index)?;
+                            result_object.borrow_mut().create_data_property(PropertyKey::from(next + index), value);
+                        }
+                    }
+
+                    next += length;
+                },
+                value => {
+                    result_object.borrow_mut().create_data_property(PropertyKey::from(next), value.clone());
+                    next += 1;
+                },
+            }
+        }
+
+        set_length(runtime, &result_object, next)?;
+
+        Ok(Some(result))
+    });
+
+    let index_of = native_function(intrinsics, "indexOf", &["searchElement"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+        let search = argument(x, 0);
+        let start = relative_index(runtime, &argument(x, 1), length, 0)?;
+
+        for index in start..length {
+            if has_index(&object, index) && is_strictly_equal(&get_index(runtime, &object, index)?, &search) {
+                return Ok(Some(Value::Number(index as f64)));
+            }
+        }
+
+        Ok(Some(Value::Number(-1.0)))
+    });
+
+    let last_index_of = native_function(intrinsics, "lastIndexOf", &["searchElement"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+        let search = argument(x, 0);
+
+        let from = match x.get(1) {
+            Some(from) => to_integer_or_infinity(runtime, from)?,
+            None => length as f64 - 1.0,
+        };
+
+        let from = if from < 0.0 { length as f64 + from } else { from.min(length as f64 - 1.0) };
+
+        if from < 0.0 {
+            return Ok(Some(Value::Number(-1.0)));
+        }
+
+        for index in (0..=from as usize).rev() {
+            if has_index(&object, index) && is_strictly_equal(&get_index(runtime, &object, index)?, &search) {
+                return Ok(Some(Value::Number(index as f64)));
+            }
+        }
+
+        Ok(Some(Value::Number(-1.0)))
+    });
+
+    // Unlike indexOf, holes read as undefined and NaN finds NaN
+    let includes = native_function(intrinsics, "includes", &["searchElement"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+        let search = argument(x, 0);
+        let start = relative_index(runtime, &argument(x, 1), length, 0)?;
+
+        for index in start..length {
+            if same_value_zero(&get_index(runtime, &object, index)?, &search) {
+                return Ok(Some(Value::Boolean(true)));
+            }
+        }
+
+        Ok(Some(Value::Boolean(false)))
+    });
+
+    let join = native_function(intrinsics, "join", &["separator"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+
+        Ok(Some(Value::string(&join_elements(runtime, &object, &argument(x, 0))?)))
+    });
+
+    // Falls back to Object.prototype.toString when `join` has been replaced by something that is not callable
+    let to_string_function = native_function(intrinsics, "toString", &[], |runtime, this, _| {
+        let object = to_object(runtime, this)?;
+        let this_object = Value::Object(object.clone());
+        let join = get(runtime, &object, &PropertyKey::from("join"), &this_object)?;
+
+        if is_callable(&join) {
+            return Ok(Some(call(runtime, &join, this_object, vec![])?));
+        }
+
+        let tag = if object.borrow().is_array() { "Array" } else { "Object" };
+
+        Ok(Some(Value::string(&format!("[object {}]", tag))))
+    });
+
+    let reverse = native_function(intrinsics, "reverse", &[], |runtime, this, _| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+
+        for lower in 0..length / 2 {
+            let upper = length - 1 - lower;
+
+            let lower_value = has_index(&object, lower).then(|| get_index(runtime, &object, lower)).transpose()?;
+            let upper_value = has_index(&object, upper).then(|| get_index(runtime, &object, upper)).transpose()?;
+
+            for (index, value) in [(lower, upper_value), (upper, lower_value)] {
+                match value {
+                    Some(value) => set_index(runtime, &object, index, value)?,
+                    None => delete_index(runtime, &object, index)?,
+                }
+            }
+        }
+
+        Ok(Some(Value::Object(object)))
+    });
+
+    let sort = native_function(intrinsics, "sort", &["comparefn"], |runtime, this, x| {
+        let comparator = argument(x, 0);
+
+        if !matches!(comparator, Value::Undefined) && !is_callable(&comparator) {
+            return runtime.type_error("The comparison function must be either a function or undefined");
+        }
+
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+
+        // Holes are left out of the sort and end up after every value
+        let values: Vec<Value> = (0..length)
+            .filter(|index| has_index(&object, *index))
+            .map(|index| get_index(runtime, &object, index))
+            .collect::<RuntimeResult<_>>()?;
+
+        let count = values.len();
+        let sorted = merge_sort(values, &mut |left, right| sort_compare(runtime, &comparator, left, right))?;
+
+        for (index, value) in sorted.into_iter().enumerate() {
+            set_index(runtime, &object, index, value)?;
+        }
+
+        for index in count..length {
+            delete_index(runtime, &object, index)?;
+        }
+
+        Ok(Some(Value::Object(object)))
+    });
+
+    let flat = native_function(intrinsics, "flat", &[], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+
+        let depth = match argument(x, 0) {
+            Value::Undefined => 1.0,
+            depth => to_integer_or_infinity(runtime, &depth)?.max(0.0),
+        };
+
+        let result = create_array_from_list(runtime, vec![]);
+        flatten_into_array(runtime, &as_object(&result), &object, length, 0, depth, None)?;
+
+        Ok(Some(result))
+    });
+
+    let flat_map = native_function(intrinsics, "flatMap", &["callback"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+        let mapper = argument(x, 0);
+
+        if !is_callable(&mapper) {
+            return runtime.type_error("flatMap mapper function is not callable");
+        }
+
+        let result = create_array_from_list(runtime, vec![]);
+        flatten_into_array(runtime, &as_object(&result), &object, length, 0, 1.0, Some((&mapper, &argument(x, 1))))?;
+
+        Ok(Some(result))
+    });
+
+    let at = native_function(intrinsics, "at", &["index"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)? as f64;
+        let relative = to_integer_or_infinity(runtime, &argument(x, 0))?;
+        let index = if relative < 0.0 { length + relative } else { relative };
+
+        if index < 0.0 || index >= length {
+            return Ok(Some(Value::Undefined));
+        }
+
+        Ok(Some(get_index(runtime, &object, index as usize)?))
+    });
+
+    let fill = native_function(intrinsics, "fill", &["value"], |runtime, this, x| {
+        let object = to_object(runtime, this)?;
+        let length = length_of_array_like(runtime, &object)?;
+        let start = relative_index(runtime, &argument(x, 1), length, 0)?;
+        let end = relative_index(runtime, &argument(x, 2), length, length)?;
+
+        for index in start..end.max(start) {
+            set_index(runtime, &object, index, argument(x, 0))?;
+        }
+
+        Ok(Some(Value::Object(object)))
+    });
+
+    // The iterating methods read `length` once, skip holes and call back with (value, index, array)
+    let for_each = native_function(intrinsics, "forEach", &["callback"], |runtime, this, x| {
+        let (object, length, callback, this_arg) = callback_setup(runtime, this, x)?;
+
+        for index in 0..length {
+            if has_index(&object, index) {
+                call_callback(runtime, &callback, &this_arg, &object, index)?;
+            }
+        }
+
+        Ok(None)
+    });
+
+    // The result keeps the holes of the source
+    let map = native_function(intrinsics, "map", &["callback"], |runtime, this, x| {
+        let (object, length, callback, this_arg) = callback_setup(runtime, this, x)?;
+
+        let result = create_array_from_list(runtime, vec![]);
+        let result_object = as_object(&result);
+        set_length(runtime, &result_object, length)?;
+
+        for index in 0..length {
+            if has_index(&object, index) {
+                let value = call_callback(runtime, &callback, &this_arg, &object, index)?.1;
+                result_object.borrow_mut().create_data_property(PropertyKey::from(index), value);
+            }
+        }
+
+        Ok(Some(result))
+    });
+
+    let filter = native_function(intrinsics, "filter", &["callback"], |runtime, this, x| {
+        let (object, length, callback, this_arg) = callback_setup(runtime, this, x)?;
+        let mut selected = Vec::new();
+
+        for index in 0..length {
+            if has_index(&object, index) {
+                let (value, keep) = call_callback(runtime, &callback, &this_arg, &object, index)?;
+
+                if to_boolean(&keep) {
+                    selected.push(value);
+                }
+            }
+        }
+
+        Ok(Some(create_array_from_list(runtime, selected)))
+    });
+
+    let some = native_function(intrinsics, "some", &["callback"], |runtime, this, x| {
+        let (object, length, callback, this_arg) = callback_setup(runtime, this, x)?;
+
+        for index in 0..length {
+            if has_index(&object, index) && to_boolean(&call_callback(runtime, &callback, &this_arg, &object, index)?.1) {
+                return Ok(Some(Value::Boolean(true)));
+            }
+        }
+
+        Ok(Some(Value::Boolean(false)))
+    });
+
+    let every = native_function(intrinsics, "every", &["callback"], |runtime, this, x| {
+        let (object, length, callback, this_arg) = callback_setup(runtime, this, x)?;
+
+        for index in 0..length {
+            if has_index(&object, index) && !to_boolean(&call_callback(runtime, &callback, &this_arg, &object, index)?.1) {
+                return Ok(Some(Value::Boolean(false)));
+            }
+        }
+
+        Ok(Some(Value::Boolean(true)))
+    });
+
+    // The find family visits holes too, they read as undefined
+    let find = native_function(intrinsics, "find", &["predicate"], |runtime, this, x| {
+        let (object, length, predicate, this_arg) = callback_setup(runtime, this, x)?;
+
+        Ok(Some(find_from(runtime, &object, 0..length, &predicate, &this_arg)?.map(|(_, value)| value).unwrap_or(Value::Undefined)))
+    });
+
+    let find_index = native_function(intrinsics, "findIndex", &["predicate"], |runtime, this, x| {
+        let (object, length, predicate, this_arg) = callback_setup(runtime, this, x)?;
+
+        Ok(Some(Value::Number(find_from(runtime, &object, 0..length, &predicate, &this_arg)?.map(|(index, _)| index as f64).unwrap_or(-1.0))))
+    });
+
+    let find_last = native_function(intrinsics, "findLast", &["predicate"], |runtime, this, x| {
+        let (object, length, predicate, this_arg) = callback_setup(runtime, this, x)?;
+
+        Ok(Some(find_from(runtime, &object, (0..length).rev(), &predicate, &this_arg)?.map(|(_, value)| value).unwrap_or(Value::Undefined)))
+    });
+
+    let find_last_index = native_function(intrinsics, "findLastIndex", &["predicate"], |runtime, this, x| {
+        let (object, length, predicate, this_arg) = callback_setup(runtime, this, x)?;
+
+        Ok(Some(Value::Number(find_from(runtime, &object, (0..length).rev(), &predicate, &this_arg)?.map(|(index, _)| index as f64).unwrap_or(-1.0))))
+    });
+
+    let reduce = native_function(intrinsics, "reduce", &["callback"], |runtime, this, x| {
+        let (object, length, callback, _) = callback_setup(runtime, this, x)?;
+        let initial = x.get(1).cloned();
+
+        Ok(Some(reduce_indices(runtime, &object, (0..length).collect(), &callback, initial)?))
+    });
+
+    let reduce_right = native_function(intrinsics, "reduceRight", &["callback"], |runtime, this, x| {
+        let (object, length, callback, _) = callback_setup(runtime, this, x)?;
+        let initial = x.get(1).cloned();
+
+        Ok(Some(reduce_indices(runtime, &object, (0..length).rev().collect(), &callback, initial)?))
+    });
+
+    let keys = native_function(intrinsics, "keys", &[], |runtime, this, _| {
+        let object = to_object(runtime, this)?;
+        Ok(Some(create_array_iterator(runtime, object, IterationKind::Keys)))
+    });
+
+    let values = native_function(intrinsics, "values", &[], |runtime, this, _| {
+        let object = to_object(runtime, this)?;
+        Ok(Some(create_array_iterator(runtime, object, IterationKind::Values)))
+    });
+
+    let entries = native_function(intrinsics, "entries", &[], |runtime, this, _| {
+        let object = to_object(runtime, this)?;
+        Ok(Some(create_array_iterator(runtime, object, IterationKind::Entries)))
+    });
+
+    let mut prototype = intrinsics.array_prototype.borrow_mut();
+
+    prototype.define_builtin("push", push);
+    prototype.define_builtin("pop", pop);
+    prototype.define_builtin("shift", shift);
+    prototype.define_builtin("unshift", unshift);
+    prototype.define_builtin("splice", splice);
+    prototype.define_builtin("slice", slice);
+    prototype.define_builtin("concat", concat);
+    prototype.define_builtin("indexOf", index_of);
+    prototype.define_builtin("lastIndexOf", last_index_of);
+    prototype.define_builtin("includes", includes);
+    prototype.define_builtin("join", join);
+    prototype.define_builtin("toString", to_string_function);
+    prototype.define_builtin("reverse", reverse);
+    prototype.define_builtin("sort", sort);
+    prototype.define_builtin("flat", flat);
+    prototype.define_builtin("flatMap", flat_map);
+    prototype.define_builtin("at", at);
+    prototype.define_builtin("fill", fill);
+    prototype.define_builtin("forEach", for_each);
+    prototype.define_builtin("map", map);
+    prototype.define_builtin("filter", filter);
+    prototype.define_builtin("some", some);
+    prototype.define_builtin("every", every);
+    prototype.define_builtin("find", find);
+    prototype.define_builtin("findIndex", find_index);
+    prototype.define_builtin("findLast", find_last);
+    prototype.define_builtin("findLastIndex", find_last_index);
+    prototype.define_builtin("reduce", reduce);
+    prototype.define_builtin("reduceRight", reduce_right);
+    prototype.define_builtin("keys", keys);
+    prototype.define_builtin("values", values.clone());
+    prototype.define_builtin("entries", entries);
+    // `values` is also what for-of and spread use
+    prototype.define_builtin(&intrinsics.iterator_symbol, values);
+}
+
+fn as_object(value: &Value) -> ObjectRef {
+    match value {
+        Value::Object(object) => object.clone(),
+        _ => unreachable!(),
+    }
+}
+
+fn has_index(object: &ObjectRef, index: usize) -> bool {
+    find_property(object, &PropertyKey::from(index)).is_some()
+}
+
+fn get_index(runtime: &mut Runtime, object: &ObjectRef, index: usize) -> RuntimeResult<Value> {
+    get(runtime, object, &PropertyKey::from(index), &Value::Object(object.clone()))
+}
+
+// Set(O, P, V, true), the methods throw where a plain assignment would silently fail
+fn set_index(runtime: &mut Runtime, object: &ObjectRef, index: usize, value: Value) -> RuntimeResult<()> {
+    set_or_throw(runtime, object, PropertyKey::from(index), value)
+}
+
+fn set_length(runtime: &mut Runtime, object: &ObjectRef, length: usize) -> RuntimeResult<()> {
+    set_or_throw(runtime, object, PropertyKey::from("length"), Value::Number(length as f64))
+}
+
+fn set_or_throw(runtime: &mut Runtime, object: &ObjectRef, key: PropertyKey, value: Value) -> RuntimeResult<()> {
+    if !set(runtime, object, key.clone(), value, &Value::Object(object.clone()))? {
+        return runtime.type_error(&format!("Cannot assign to read only property '{}' of object", key));
+    }
+
+    Ok(())
+}
+
+fn delete_index(runtime: &Runtime, object: &ObjectRef, index: usize) -> RuntimeResult<()> {
+    if !object.borrow_mut().delete(&PropertyKey::from(index)) {
+        return runtime.type_error(&format!("Cannot delete property '{}' of {}", index, Value::Object(object.clone()).to_display_string()));
+    }
+
+    Ok(())
+}
+
+// Copies an element to another index, a hole at `from` becomes a hole at `to`
+fn move_index(runtime: &mut Runtime, object: &ObjectRef, from: usize, to: usize) -> RuntimeResult<()> {
+    if has_index(object, from) {
+        let value = get_index(runtime, object, from)?;
+        set_index(runtime, object, to, value)?;
+    } else {
+        delete_index(runtime, object, to)?;
+    }
+
+    Ok(())
+}
+
+// The receiver, its length, the callback and the `this` it is called with
+fn callback_setup(runtime: &mut Runtime, this: &Value, x: &[Value]) -> RuntimeResult<(ObjectRef, usize, Value, Value)> {
+    let object = to_object(runtime, this)?;
+    let length = length_of_array_like(runtime, &object)?;
+    let callback = argument(x, 0);
+
+    if !is_callable(&callback) {
+        return runtime.type_error(&format!("{} is not a function", callback.to_display_string()));
+    }
+
+    Ok((object, length, callback, argument(x, 1)))
+}
+
+// Calls back with (value, index, array), returning the element along with the result
+fn call_callback(runtime: &mut Runtime, callback: &Value, this_arg: &Value, object: &ObjectRef, index: usize) -> RuntimeResult<(Value, Value)> {
+    let value = get_index(runtime, object, index)?;
+    let arguments = vec![value.clone(), Value::Number(index as f64), Value::Object(object.clone())];
+
+    Ok((value, call(runtime, callback, this_arg.clone(), arguments)?))
+}
+
+fn find_from(runtime: &mut Runtime, object: &ObjectRef, indices: impl Iterator<Item = usize>, predicate: &Value, this_arg: &Value) -> RuntimeResult<Option<(usize, Value)>> {
+    for index in indices {
+        let (value, found) = call_callback(runtime, predicate, this_arg, object, index)?;
+
+        if to_boolean(&found) {
+            return Ok(Some((index, value)));
+        }
+    }
+
+    Ok(None)
+}
+
+// Without an initial value the first element present starts the accumulator
+fn reduce_indices(runtime: &mut Runtime, object: &ObjectRef, indices: Vec<usize>, callback: &Value, initial: Option<Value>) -> RuntimeResult<Value> {
+    let mut indices = indices.into_iter();
+
+    let mut accumulator = match initial {
+        Some(initial) => initial,
+        None => match indices.by_ref().find(|index| has_index(object, *index)) {
+            Some(index) => get_index(runtime, object, index)?,
+            None => return runtime.type_error("Reduce of empty array with no initial value"),
+        },
+    };
+
+    for index in indices {
+        if has_index(object, index) {
+            let value = get_index(runtime, object, index)?;
+            let arguments = vec![accumulator, value, Value::Number(index as f64), Value::Object(object.clone())];
+
+            accumulator = call(runtime, callback, Value::Undefined, arguments)?;
+        }
+    }
+
+    Ok(accumulator)
+}
+
+fn join_elements(runtime: &mut Runtime, object: &ObjectRef, separator: &Value) -> RuntimeResult<String> {
+    let length = length_of_array_like(runtime, object)?;
+
+    let separator = match separator {
+        Value::Undefined => ",".to_string(),
+        separator => to_string(runtime, separator)?,
+    };
+
+    let mut parts = Vec::with_capacity(length);
+
+    // Undefined and null elements, like holes, join as empty strings
+    for index in 0..length {
+        let part = match get_index(runtime, object, index)? {
+            Value::Undefined | Value::Null => String::new(),
+            element => to_string(runtime, &element)?,
+        };
+
+        parts.push(part);
+    }
+
+    Ok(parts.join(&separator))
+}
+
+// SortCompare, undefined sorts last and without a comparator values compare as strings
+fn sort_compare(runtime: &mut Runtime, comparator: &Value, left: &Value, right: &Value) -> RuntimeResult<Ordering> {
+    match (left, right) {
+        (Value::Undefined, Value::Undefined) => return Ok(Ordering::Equal),
+        (Value::Undefined, _) => return Ok(Ordering::Greater),
+        (_, Value::Undefined) => return Ok(Ordering::Less),
+        _ => {},
+    }
+
+    if matches!(comparator, Value::Undefined) {
+        let left = to_string(runtime, left)?;
+        let right = to_string(runtime, right)?;
+
+        return Ok(left.encode_utf16().cmp(right.encode_utf16()));
+    }
+
+    let result = call(runtime, comparator, Value::Undefined, vec![left.clone(), right.clone()])?;
+    let result = to_number(runtime, &result)?;
+
+    Ok(result.partial_cmp(&0.0).unwrap_or(Ordering::Equal))
+}
+
+// A stable merge sort that tolerates inconsistent comparators, which user code is free to pass
+fn merge_sort(mut values: Vec<Value>, compare: &mut impl FnMut(&Value, &Value) -> RuntimeResult<Ordering>) -> RuntimeResult<Vec<Value>> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+
+    let right = values.split_off(values.len() / 2);
+    let left = merge_sort(values, compare)?;
+    let right = merge_sort(right, compare)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(left_value), Some(right_value)) = (left.peek(), right.peek()) {
+        // Equal elements keep their order by taking from the left first
+        if compare(left_value, right_value)? == Ordering::Greater {
+            merged.push(right.next().unwrap());
+        } else {
+            merged.push(left.next().unwrap());
+        }
+    }
+
+    merged.extend(left);
+    merged.extend(right);
+
+    Ok(merged)
+}
+
+// FlattenIntoArray, returns the index after the last element it wrote
+fn flatten_into_array(runtime: &mut Runtime, target: &ObjectRef, source: &ObjectRef, length: usize, start: usize, depth: f64, mapper: Option<(&Value, &Value)>) -> RuntimeResult<usize> {
+    let mut target_index = start;
+
+    for index in 0..length {
+        if !has_index(source, index) {
+            continue;
+        }
+
+        let mut element = get_index(runtime, source, index)?;
+
+        if let Some((mapper, this_arg)) = mapper {
+            element = call(runtime, mapper, this_arg.clone(), vec![element, Value::Number(index as f64), Value::Object(source.clone())])?;
+        }
+
+        match &element {
+            Value::Object(nested) if depth > 0.0 && nested.borrow().is_array() => {
+                let nested_length = length_of_array_like(runtime, nested)?;
+                target_index = flatten_into_array(runtime, target, nested, nested_length, target_index, depth - 1.0, None)?;
+            },
+            _ => {
+                target.borrow_mut().create_data_property(PropertyKey::from(target_index), element);
+                target_index += 1;
+            },
+        }
+    }
+
+    Ok(target_index)
+}
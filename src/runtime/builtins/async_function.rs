@@ -0,0 +1,53 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::runtime::{coroutine::{Coroutine, CoroutineState, ResumeAction, Suspension}, object::ObjectRef, value::Value, Runtime};
+
+use super::promise::{new_promise, on_settled, reject_promise, resolve_promise};
+
+// An async function call that is waiting for a promise, its coroutine is resumed when the promise settles
+struct AsyncFunction {
+    coroutine: RefCell<Option<Coroutine>>,
+    promise: ObjectRef,
+}
+
+// AsyncFunctionStart, the body runs until its first `await` before the call returns its promise
+pub fn start_async_function(runtime: &mut Runtime, coroutine: Coroutine) -> Value {
+    let promise = new_promise(runtime);
+
+    let function = Rc::new(AsyncFunction {
+        coroutine: RefCell::new(Some(coroutine)),
+        promise: promise.clone(),
+    });
+
+    async_function_resume(runtime, function, ResumeAction::Next(Value::Undefined));
+
+    Value::Object(promise)
+}
+
+// Runs the body up to the next `await` or to its end, which settles the promise of the call
+fn async_function_resume(runtime: &mut Runtime, function: Rc<AsyncFunction>, action: ResumeAction) {
+    let mut coroutine = function.coroutine.borrow_mut().take().expect("An async function is resumed once per await");
+
+    let caller_environment = runtime.environment.clone();
+    let state = coroutine.resume(runtime, action);
+    runtime.enter_environment(caller_environment);
+
+    match state {
+        CoroutineState::Suspended(Suspension::Await(promise)) => {
+            *function.coroutine.borrow_mut() = Some(coroutine);
+
+            on_settled(runtime, &promise, move |runtime, result| {
+                let action = match result {
+                    Ok(value) => ResumeAction::Next(value),
+                    Err(reason) => ResumeAction::Throw(reason),
+                };
+
+                async_function_resume(runtime, function.clone(), action);
+            });
+        },
+        // `yield` is not an expression in the body of an async function that is not a generator
+        CoroutineState::Suspended(Suspension::Yield(_)) => unreachable!(),
+        CoroutineState::Completed(Ok(value)) => resolve_promise(runtime, &function.promise, value),
+        CoroutineState::Completed(Err(error)) => reject_promise(runtime, &function.promise, error),
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+
+use crate::runtime::{coroutine::{Coroutine, CoroutineState, ResumeAction, Suspension}, intrinsics::Intrinsics, object::{ObjectKind, ObjectRef, PropertyDescriptor, PropertyKey}, operations::create_iter_result_object, value::Value, Runtime};
+
+use super::{argument, create_error, generator_return_value, native_function, promise::{new_promise, on_settled, promise_resolve, reject_promise, resolve_promise}, ErrorKind};
+
+// Calls to `next`, `return` and `throw` are queued and answered in order, each with a promise of its own
+pub struct AsyncGeneratorData {
+    state: AsyncGeneratorState,
+    // Lent to `async_generator_step` while the body runs, it stays here while the body awaits
+    coroutine: Option<Coroutine>,
+    queue: VecDeque<AsyncGeneratorRequest>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AsyncGeneratorState {
+    SuspendedStart,
+    SuspendedYield,
+    Executing,
+    // A finished generator awaiting the value of a `return` call before it answers it
+    AwaitingReturn,
+    Completed,
+}
+
+struct AsyncGeneratorRequest {
+    action: ResumeAction,
+    promise: ObjectRef,
+}
+
+impl AsyncGeneratorData {
+    pub fn new(coroutine: Coroutine) -> AsyncGeneratorData {
+        AsyncGeneratorData {
+            state: AsyncGeneratorState::SuspendedStart,
+            coroutine: Some(coroutine),
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+pub fn register(intrinsics: &Intrinsics) {
+    let next = native_function(intrinsics, "next", &["value"], |runtime, this, x| {
+        Ok(Some(async_generator_enqueue(runtime, this, "next", ResumeAction::Next(argument(x, 0)))))
+    });
+
+    let return_function = native_function(intrinsics, "return", &["value"], |runtime, this, x| {
+        Ok(Some(async_generator_enqueue(runtime, this, "return", ResumeAction::Return(argument(x, 0)))))
+    });
+
+    let throw = native_function(intrinsics, "throw", &["exception"], |runtime, this, x| {
+        Ok(Some(async_generator_enqueue(runtime, this, "throw", ResumeAction::Throw(argument(x, 0)))))
+    });
+
+    // Async iterators are async iterable themselves, so they can be used directly in for await
+    let async_iterator = native_function(intrinsics, "[Symbol.asyncIterator]", &[], |_, this, _| {
+        Ok(Some(this.clone()))
+    });
+
+    intrinsics.async_iterator_prototype.borrow_mut().define_builtin(&intrinsics.async_iterator_symbol, async_iterator);
+
+    // %AsyncGeneratorFunction.prototype% and %AsyncGeneratorPrototype% point at each other like their sync counterparts
+    let link = |value: &ObjectRef| PropertyDescriptor {
+        value: Some(Value::Object(value.clone())),
+        writable: Some(false),
+        enumerable: Some(false),
+        configurable: Some(true),
+        ..Default::default()
+    };
+
+    {
+        let mut prototype = intrinsics.async_generator_prototype.borrow_mut();
+
+        prototype.define_builtin("next", next);
+        prototype.define_builtin("return", return_function);
+        prototype.define_builtin("throw", throw);
+        prototype.define_own_property(PropertyKey::from("constructor"), link(&intrinsics.async_generator_function_prototype));
+    }
+
+    intrinsics.async_generator_function_prototype.borrow_mut()
+        .define_own_property(PropertyKey::from("prototype"), link(&intrinsics.async_generator_prototype));
+}
+
+// AsyncGeneratorEnqueue, an incompatible receiver rejects the promise instead of throwing
+fn async_generator_enqueue(runtime: &mut Runtime, generator: &Value, method: &str, action: ResumeAction) -> Value {
+    let promise = new_promise(runtime);
+
+    let generator = match generator {
+        Value::Object(object) if matches!(object.borrow().kind, ObjectKind::AsyncGenerator(_)) => object.clone(),
+        _ => {
+            let message = format!("{} method called on incompatible receiver {}", method, generator.to_display_string());
+            let error = create_error(runtime, ErrorKind::TypeError, &message);
+            reject_promise(runtime, &promise, error);
+
+            return Value::Object(promise);
+        },
+    };
+
+    with_generator(&generator, |data| data.queue.push_back(AsyncGeneratorRequest { action, promise: promise.clone() }));
+    async_generator_resume_next(runtime, &generator);
+
+    Value::Object(promise)
+}
+
+// Answers queued requests until one has to wait, either for the body to run or for a returned value to settle
+fn async_generator_resume_next(runtime: &mut Runtime, generator: &ObjectRef) {
+    loop {
+        let (state, action) = match with_generator(generator, |data| (data.state, data.queue.front().map(|request| request.action.clone()))) {
+            (AsyncGeneratorState::Executing | AsyncGeneratorState::AwaitingReturn, _) | (_, None) => return,
+            (state, Some(action)) => (state, action),
+        };
+
+        // `return` and `throw` finish a generator that has not started without running any of its body
+        if state == AsyncGeneratorState::SuspendedStart && !matches!(action, ResumeAction::Next(_)) {
+            set_state(generator, AsyncGeneratorState::Completed);
+            continue;
+        }
+
+        if state != AsyncGeneratorState::Completed {
+            return async_generator_step(runtime, generator, action);
+        }
+
+        match action {
+            ResumeAction::Next(_) => {
+                let result = create_iter_result_object(runtime, Value::Undefined, true);
+                complete_request(runtime, generator, Ok(result));
+            },
+            ResumeAction::Throw(error) => complete_request(runtime, generator, Err(error)),
+            // AsyncGeneratorAwaitReturn
+            ResumeAction::Return(value) => {
+                let promise = match promise_resolve(runtime, None, value) {
+                    Ok(promise) => promise,
+                    Err(error) => {
+                        complete_request(runtime, generator, Err(error));
+                        continue;
+                    },
+                };
+
+                set_state(generator, AsyncGeneratorState::AwaitingReturn);
+
+                let generator = generator.clone();
+
+                return on_settled(runtime, &promise, move |runtime, result| {
+                    set_state(&generator, AsyncGeneratorState::Completed);
+
+                    let result = result.map(|value| create_iter_result_object(runtime, value, true));
+                    complete_request(runtime, &generator, result);
+                    async_generator_resume_next(runtime, &generator);
+                });
+            },
+        }
+    }
+}
+
+// Runs the body until it awaits, yields or finishes. Awaiting keeps the request at the front of the queue.
+fn async_generator_step(runtime: &mut Runtime, generator: &ObjectRef, action: ResumeAction) {
+    let mut coroutine = with_generator(generator, |data| {
+        data.state = AsyncGeneratorState::Executing;
+        data.coroutine.take().expect("A suspended async generator has its coroutine")
+    });
+
+    let caller_environment = runtime.environment.clone();
+    let state = coroutine.resume(runtime, action);
+    runtime.enter_environment(caller_environment);
+
+    match state {
+        CoroutineState::Suspended(Suspension::Await(promise)) => {
+            with_generator(generator, |data| data.coroutine = Some(coroutine));
+
+            let generator = generator.clone();
+
+            on_settled(runtime, &promise, move |runtime, result| {
+                let action = match result {
+                    Ok(value) => ResumeAction::Next(value),
+                    Err(reason) => ResumeAction::Throw(reason),
+                };
+
+                async_generator_step(runtime, &generator, action);
+            });
+        },
+        CoroutineState::Suspended(Suspension::Yield(value)) => {
+            with_generator(generator, |data| {
+                data.coroutine = Some(coroutine);
+                data.state = AsyncGeneratorState::SuspendedYield;
+            });
+
+            let result = create_iter_result_object(runtime, value, false);
+            complete_request(runtime, generator, Ok(result));
+            async_generator_resume_next(runtime, generator);
+        },
+        CoroutineState::Completed(result) => {
+            set_state(generator, AsyncGeneratorState::Completed);
+
+            let result = match result {
+                Ok(value) => Ok(create_iter_result_object(runtime, value, true)),
+                Err(error) => match generator_return_value(&error) {
+                    Some(value) => Ok(create_iter_result_object(runtime, value, true)),
+                    None => Err(error),
+                },
+            };
+
+            complete_request(runtime, generator, result);
+            async_generator_resume_next(runtime, generator);
+        },
+    }
+}
+
+// AsyncGeneratorCompleteStep, settles the promise of the oldest request
+fn complete_request(runtime: &mut Runtime, generator: &ObjectRef, result: Result<Value, Value>) {
+    let request = with_generator(generator, |data| data.queue.pop_front()).expect("A request is waiting for the step");
+
+    match result {
+        Ok(value) => resolve_promise(runtime, &request.promise, value),
+        Err(error) => reject_promise(runtime, &request.promise, error),
+    }
+}
+
+fn set_state(generator: &ObjectRef, state: AsyncGeneratorState) {
+    with_generator(generator, |data| data.state = state);
+}
+
+fn with_generator<T>(generator: &ObjectRef, f: impl FnOnce(&mut AsyncGeneratorData) -> T) -> T {
+    match &mut generator.borrow_mut().kind {
+        ObjectKind::AsyncGenerator(data) => f(data),
+        _ => unreachable!(),
+    }
+}
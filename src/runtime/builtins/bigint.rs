@@ -0,0 +1,30 @@
+use std::{collections::HashMap, rc::Rc};
+
+use num_bigint::BigInt;
+use num_traits::FromPrimitive;
+
+use crate::runtime::{intrinsics::Intrinsics, operations::{number_to_string, to_bigint, to_primitive, PreferredType}, value::Value};
+
+use super::{argument, native_function};
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    let bigint = native_function(intrinsics, "BigInt", &["value"], |runtime, _, x| {
+        let value = &argument(x, 0);
+
+        // Numbers must be integral, everything else goes through ToBigInt
+        let result = match to_primitive(runtime, value, PreferredType::Number)? {
+            Value::Number(number) => match BigInt::from_f64(number).filter(|_| number.fract() == 0.0) {
+                Some(result) => Rc::new(result),
+                None => {
+                    let message = format!("The number {} cannot be converted to a BigInt because it is not an integer", number_to_string(number));
+                    return runtime.range_error(&message);
+                },
+            },
+            primitive => to_bigint(runtime, &primitive)?,
+        };
+
+        Ok(Some(Value::BigInt(result)))
+    });
+
+    variables.insert("BigInt".to_string(), bigint);
+}
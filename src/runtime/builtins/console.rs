@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use crate::runtime::{intrinsics::Intrinsics, object::Object, value::Value};
+
+use super::native_function;
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    let console = Object::new_ordinary(Some(intrinsics.object_prototype.clone()));
+
+    // Every argument is printed, separated by spaces
+    let log = native_function(intrinsics, "log", &[], |_, _, x| {
+        let output: Vec<String> = x.iter()
+            .map(|value| value.to_display_string())
+            .collect();
+
+        println!("{}", output.join(" "));
+
+        Ok(None)
+    });
+
+    console.borrow_mut().define_builtin("log", log);
+
+    variables.insert("console".to_string(), Value::Object(console));
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use crate::runtime::{call_stack::stack_trace, intrinsics::Intrinsics, nodes::RuntimeResult, object::{find_property, get, Object, ObjectRef, PropertyKey}, operations::{create_array_from_list, iterable_to_list, to_string}, value::Value, Runtime};
+
+use super::{argument, native_constructor, native_function};
+
+// Error and the native errors the runtime throws, each has its own constructor and prototype
+#[derive(Clone, Copy)]
+pub enum ErrorKind {
+    Error,
+    TypeError,
+    ReferenceError,
+    RangeError,
+    SyntaxError,
+    EvalError,
+    URIError,
+    // Several errors at once, like the rejections when every promise given to Promise.any rejects
+    AggregateError,
+}
+
+impl ErrorKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::Error => "Error",
+            ErrorKind::TypeError => "TypeError",
+            ErrorKind::ReferenceError => "ReferenceError",
+            ErrorKind::RangeError => "RangeError",
+            ErrorKind::SyntaxError => "SyntaxError",
+            ErrorKind::EvalError => "EvalError",
+            ErrorKind::URIError => "URIError",
+            ErrorKind::AggregateError => "AggregateError",
+        }
+    }
+}
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    let kinds = [
+        ErrorKind::Error,
+        ErrorKind::TypeError,
+        ErrorKind::ReferenceError,
+        ErrorKind::RangeError,
+        ErrorKind::SyntaxError,
+        ErrorKind::EvalError,
+        ErrorKind::URIError,
+        ErrorKind::AggregateError,
+    ];
+    let mut error_constructor = None;
+
+    for kind in kinds {
+        let prototype = intrinsics.error_prototype_for(kind);
+
+        let parameters: &[&str] = match kind {
+            ErrorKind::AggregateError => &["errors", "message"],
+            _ => &["message"],
+        };
+
+        // Calling an error constructor without `new` also creates an error, AggregateError takes its errors before the message
+        let constructor = native_constructor(intrinsics, kind.name(), parameters, &prototype, move |runtime, _, x| {
+            let rest = match kind {
+                ErrorKind::AggregateError => x.get(1..).unwrap_or_default(),
+                _ => x,
+            };
+
+            let message = match &argument(rest, 0) {
+                Value::Undefined => None,
+                message => Some(to_string(runtime, message)?),
+            };
+
+            let error = create_error_object(runtime, kind, message.as_deref());
+            install_error_cause(runtime, &error, &argument(rest, 1))?;
+
+            if let ErrorKind::AggregateError = kind {
+                let errors = iterable_to_list(runtime, &argument(x, 0))?;
+                error.borrow_mut().define_builtin("errors", create_array_from_list(runtime, errors));
+            }
+
+            Ok(Some(Value::Object(error)))
+        });
+
+        // The native errors inherit the static members of Error
+        match (&error_constructor, &constructor) {
+            (Some(Value::Object(error)), Value::Object(constructor)) => constructor.borrow_mut().prototype = Some(error.clone()),
+            _ => error_constructor = Some(constructor.clone()),
+        }
+
+        prototype.borrow_mut().define_builtin("name", Value::string(kind.name()));
+        prototype.borrow_mut().define_builtin("message", Value::string(""));
+
+        variables.insert(kind.name().to_string(), constructor);
+    }
+
+    // Error.prototype.toString, "name: message" with either part left out when it is empty
+    let to_string_function = native_function(intrinsics, "toString", &[], |runtime, this, _| {
+        let object = match this {
+            Value::Object(object) => object.clone(),
+            _ => return runtime.type_error("Error.prototype.toString requires that 'this' be an Object"),
+        };
+
+        let name = match get(runtime, &object, &PropertyKey::from("name"), this)? {
+            Value::Undefined => "Error".to_string(),
+            name => to_string(runtime, &name)?,
+        };
+
+        let message = match get(runtime, &object, &PropertyKey::from("message"), this)? {
+            Value::Undefined => String::new(),
+            message => to_string(runtime, &message)?,
+        };
+
+        Ok(Some(Value::string(&error_text(&name, &message))))
+    });
+
+    intrinsics.error_prototype_for(ErrorKind::Error).borrow_mut().define_builtin("toString", to_string_function);
+}
+
+// The errors the runtime throws itself, like the TypeError for calling a value that is not a function
+pub fn create_error(runtime: &Runtime, kind: ErrorKind, message: &str) -> Value {
+    Value::Object(create_error_object(runtime, kind, Some(message)))
+}
+
+// The message is an own, non-enumerable property, without one the prototype's empty message shows through.
+// The `stack` is taken when the error is created, from wherever the script is at that moment.
+fn create_error_object(runtime: &Runtime, kind: ErrorKind, message: Option<&str>) -> ObjectRef {
+    let error = Object::new_error(&runtime.intrinsics.error_prototype_for(kind));
+    let stack = format!("{}{}", error_text(kind.name(), message.unwrap_or_default()), stack_trace(runtime));
+
+    {
+        let mut error = error.borrow_mut();
+
+        if let Some(message) = message {
+            error.define_builtin("message", Value::string(message));
+        }
+
+        error.define_builtin("stack", Value::string(&stack));
+    }
+
+    error
+}
+
+// InstallErrorCause, only an options object that has a `cause` gives the error one
+fn install_error_cause(runtime: &mut Runtime, error: &ObjectRef, options: &Value) -> RuntimeResult<()> {
+    if let Value::Object(options) = options {
+        let key = PropertyKey::from("cause");
+
+        if find_property(options, &key).is_some() {
+            let cause = get(runtime, options, &key, &Value::Object(options.clone()))?;
+            error.borrow_mut().define_builtin("cause", cause);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn error_text(name: &str, message: &str) -> String {
+    match (name.is_empty(), message.is_empty()) {
+        (true, _) => message.to_string(),
+        (false, true) => name.to_string(),
+        (false, false) => format!("{}: {}", name, message),
+    }
+}
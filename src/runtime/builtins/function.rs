@@ -0,0 +1,123 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::{BoundFunction, FunctionCall, FunctionCallType, RuntimeResult}, object::{get, Object, PropertyDescriptor, PropertyKey}, operations::{get_property, ordinary_has_instance, to_length}, parsers::call_expression::call_function, value::Value, Runtime};
+
+use super::{argument, native_constructor, native_function, ErrorKind};
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    // Functions cannot be compiled from strings, the constructor only exists for `instanceof` and `.prototype`
+    let function = native_constructor(intrinsics, "Function", &[], &intrinsics.function_prototype, |runtime, _, _| {
+        runtime.throw_error(ErrorKind::EvalError, "Creating functions from strings is not supported")
+    });
+
+    let to_string = native_function(intrinsics, "toString", &[], |runtime, this, _| {
+        match this.as_function() {
+            Some(function) => Ok(Some(Value::string(&format!("function {}() {{ [native code] }}", function.name)))),
+            None => runtime.type_error("Function.prototype.toString requires that 'this' be a Function"),
+        }
+    });
+
+    let call = native_function(intrinsics, "call", &["thisArg"], |runtime, this, x| {
+        let function = callable_this(runtime, this, "call")?;
+        let arguments = x.iter().skip(1).cloned().collect();
+
+        Ok(Some(call_function(runtime, &function, argument(x, 0), arguments)?))
+    });
+
+    let apply = native_function(intrinsics, "apply", &["thisArg", "argArray"], |runtime, this, x| {
+        let function = callable_this(runtime, this, "apply")?;
+        let arguments = match argument(x, 1) {
+            Value::Undefined | Value::Null => vec![],
+            array => list_from_array_like(runtime, &array)?,
+        };
+
+        Ok(Some(call_function(runtime, &function, argument(x, 0), arguments)?))
+    });
+
+    let bind = native_function(intrinsics, "bind", &["thisArg"], |runtime, this, x| {
+        Ok(Some(bind_function(runtime, this, argument(x, 0), x.iter().skip(1).cloned().collect())?))
+    });
+
+    // The default `instanceof` behaviour, which a class can override with a static method of its own
+    let has_instance = native_function(intrinsics, "[Symbol.hasInstance]", &["V"], |runtime, this, x| {
+        Ok(Some(Value::Boolean(ordinary_has_instance(runtime, this, &argument(x, 0))?)))
+    });
+
+    let mut prototype = intrinsics.function_prototype.borrow_mut();
+
+    prototype.define_builtin("toString", to_string);
+    prototype.define_builtin("call", call);
+    prototype.define_builtin("apply", apply);
+    prototype.define_builtin("bind", bind);
+    prototype.define_own_property(PropertyKey::Symbol(intrinsics.has_instance_symbol.clone()), PropertyDescriptor {
+        value: Some(has_instance),
+        writable: Some(false),
+        enumerable: Some(false),
+        configurable: Some(false),
+        ..Default::default()
+    });
+
+    variables.insert("Function".to_string(), function);
+}
+
+fn callable_this(runtime: &Runtime, this: &Value, method: &str) -> RuntimeResult<Rc<FunctionCall>> {
+    match this.as_function() {
+        Some(function) => Ok(function),
+        None => runtime.type_error(&format!("Function.prototype.{} was called on {}, which is not a function", method, this.to_display_string())),
+    }
+}
+
+// CreateListFromArrayLike, reads `length` and then every index below it
+fn list_from_array_like(runtime: &mut Runtime, value: &Value) -> RuntimeResult<Vec<Value>> {
+    if !matches!(value, Value::Object(_)) {
+        return runtime.type_error("CreateListFromArrayLike called on non-object");
+    }
+
+    let length = get_property(runtime, value, &PropertyKey::from("length"))?;
+    let length = to_length(runtime, &length)?;
+
+    (0..length)
+        .map(|index| get_property(runtime, value, &PropertyKey::from(index.to_string().as_str())))
+        .collect()
+}
+
+// BoundFunctionCreate, the result takes its prototype and constructability from the target
+fn bind_function(runtime: &mut Runtime, target: &Value, this_value: Value, arguments: Vec<Value>) -> RuntimeResult<Value> {
+    let (target_object, target_function) = match (target, target.as_function()) {
+        (Value::Object(object), Some(function)) => (object.clone(), function),
+        _ => return runtime.type_error("Bind must be called on a function"),
+    };
+
+    let name = match get(runtime, &target_object, &PropertyKey::from("name"), target)? {
+        Value::String(name) => name.to_string(),
+        _ => String::new(),
+    };
+
+    let length = match get(runtime, &target_object, &PropertyKey::from("length"), target)? {
+        Value::Number(length) if length.is_finite() => (length.trunc() - arguments.len() as f64).max(0.0),
+        Value::Number(length) if length > 0.0 => length,
+        _ => 0.0,
+    };
+
+    let prototype = target_object.borrow().prototype.clone().unwrap_or_else(|| runtime.intrinsics.function_prototype.clone());
+
+    let function = FunctionCall {
+        name: format!("bound {}", name),
+        function_type: FunctionCallType::BoundFunction(BoundFunction {
+            target: target.clone(),
+            this_value,
+            arguments,
+        }),
+        length: 0,
+        constructor: target_function.constructor,
+    };
+
+    let bound = Object::new_function(function, &prototype);
+
+    bound.borrow_mut().define_own_property(PropertyKey::from("length"), PropertyDescriptor {
+        value: Some(Value::Number(length)),
+        ..Default::default()
+    });
+
+    Ok(Value::Object(bound))
+}
@@ -0,0 +1,120 @@
+use crate::runtime::{coroutine::{Coroutine, CoroutineState, ResumeAction, Suspension}, intrinsics::Intrinsics, nodes::RuntimeResult, object::{Object, ObjectKind, ObjectRef, PropertyDescriptor, PropertyKey}, operations::create_iter_result_object, value::Value, Runtime};
+
+use super::{argument, native_function};
+
+// A suspended generator owns the coroutine its body runs on, a running one has lent it to `generator_resume`
+pub enum GeneratorState {
+    // The body has not started, the first `next` runs it from the top
+    SuspendedStart(Coroutine),
+    SuspendedYield(Coroutine),
+    Executing,
+    Completed,
+}
+
+pub fn register(intrinsics: &Intrinsics) {
+    let next = native_function(intrinsics, "next", &["value"], |runtime, this, x| {
+        Ok(Some(generator_resume(runtime, this, "next", ResumeAction::Next(argument(x, 0)))?))
+    });
+
+    let return_function = native_function(intrinsics, "return", &["value"], |runtime, this, x| {
+        Ok(Some(generator_resume(runtime, this, "return", ResumeAction::Return(argument(x, 0)))?))
+    });
+
+    let throw = native_function(intrinsics, "throw", &["exception"], |runtime, this, x| {
+        Ok(Some(generator_resume(runtime, this, "throw", ResumeAction::Throw(argument(x, 0)))?))
+    });
+
+    // %GeneratorFunction.prototype% and %GeneratorPrototype% point at each other, both links are read-only
+    let link = |value: &ObjectRef| PropertyDescriptor {
+        value: Some(Value::Object(value.clone())),
+        writable: Some(false),
+        enumerable: Some(false),
+        configurable: Some(true),
+        ..Default::default()
+    };
+
+    {
+        let mut prototype = intrinsics.generator_prototype.borrow_mut();
+
+        prototype.define_builtin("next", next);
+        prototype.define_builtin("return", return_function);
+        prototype.define_builtin("throw", throw);
+        prototype.define_own_property(PropertyKey::from("constructor"), link(&intrinsics.generator_function_prototype));
+    }
+
+    intrinsics.generator_function_prototype.borrow_mut()
+        .define_own_property(PropertyKey::from("prototype"), link(&intrinsics.generator_prototype));
+}
+
+// GeneratorResume and GeneratorResumeAbrupt. The body runs until its next `yield` or until it finishes,
+// and the environment of the caller is put back afterwards.
+fn generator_resume(runtime: &mut Runtime, generator: &Value, method: &str, action: ResumeAction) -> RuntimeResult<Value> {
+    let object = match generator {
+        Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Generator(_)) => object.clone(),
+        _ => return runtime.type_error(&format!("{} method called on incompatible receiver {}", method, generator.to_display_string())),
+    };
+
+    let state = match &mut object.borrow_mut().kind {
+        ObjectKind::Generator(state) => std::mem::replace(state, GeneratorState::Executing),
+        _ => unreachable!(),
+    };
+
+    let (mut coroutine, action) = match (state, action) {
+        (GeneratorState::Executing, _) => return runtime.type_error("Generator is already running"),
+        (GeneratorState::SuspendedStart(coroutine), action @ ResumeAction::Next(_)) | (GeneratorState::SuspendedYield(coroutine), action) => (coroutine, action),
+        // `return` and `throw` finish a generator that has not started without running any of its body
+        (GeneratorState::SuspendedStart(_) | GeneratorState::Completed, action) => {
+            set_state(&object, GeneratorState::Completed);
+
+            return match action {
+                ResumeAction::Next(_) => Ok(create_iter_result_object(runtime, Value::Undefined, true)),
+                ResumeAction::Return(value) => Ok(create_iter_result_object(runtime, value, true)),
+                ResumeAction::Throw(error) => Err(error),
+            };
+        },
+    };
+
+    let caller_environment = runtime.environment.clone();
+    let state = coroutine.resume(runtime, action);
+    runtime.enter_environment(caller_environment);
+
+    match state {
+        CoroutineState::Suspended(Suspension::Yield(result)) => {
+            set_state(&object, GeneratorState::SuspendedYield(coroutine));
+            Ok(result)
+        },
+        // `await` is not an expression in the body of a generator that is not async
+        CoroutineState::Suspended(Suspension::Await(_)) => unreachable!(),
+        CoroutineState::Completed(result) => {
+            set_state(&object, GeneratorState::Completed);
+
+            match result {
+                Ok(value) => Ok(create_iter_result_object(runtime, value, true)),
+                Err(error) => match generator_return_value(&error) {
+                    Some(value) => Ok(create_iter_result_object(runtime, value, true)),
+                    None => Err(error),
+                },
+            }
+        },
+    }
+}
+
+fn set_state(generator: &ObjectRef, state: GeneratorState) {
+    generator.borrow_mut().kind = ObjectKind::Generator(state);
+}
+
+// What `return` throws through the body of a suspended generator, only `finally` blocks stop it on the way out
+pub fn generator_return(value: Value) -> Value {
+    Value::Object(Object::new(ObjectKind::GeneratorReturn(value), None))
+}
+
+// The value of a generator's `return` when `error` is the exception it threw through the body
+pub fn generator_return_value(error: &Value) -> Option<Value> {
+    match error {
+        Value::Object(object) => match &object.borrow().kind {
+            ObjectKind::GeneratorReturn(value) => Some(value.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,10 @@
+use std::collections::HashMap;
+
+use crate::runtime::value::Value;
+
+// The value properties of the global object
+pub fn register(variables: &mut HashMap<String, Value>) {
+    variables.insert("undefined".to_string(), Value::Undefined);
+    variables.insert("NaN".to_string(), Value::Number(f64::NAN));
+    variables.insert("Infinity".to_string(), Value::Number(f64::INFINITY));
+}
@@ -0,0 +1,201 @@
+use std::rc::Rc;
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::RuntimeResult, object::{get, Object, ObjectKind, ObjectRef, PropertyKey}, operations::{create_array_from_list, create_iter_result_object, length_of_array_like, to_string}, value::Value, Runtime};
+
+use super::native_function;
+
+// What the iterators of arrays, Maps and Sets give for each element
+#[derive(Clone, Copy)]
+pub enum IterationKind {
+    Keys,
+    Values,
+    Entries,
+}
+
+pub fn register(intrinsics: &Intrinsics) {
+    // Iterators are iterable themselves, so they can be used directly in for-of and spread
+    let iterator = native_function(intrinsics, "[Symbol.iterator]", &[], |_, this, _| {
+        Ok(Some(this.clone()))
+    });
+
+    intrinsics.iterator_prototype.borrow_mut().define_builtin(&intrinsics.iterator_symbol, iterator);
+
+    let array_next = native_function(intrinsics, "next", &[], |runtime, this, _| {
+        Ok(Some(array_iterator_next(runtime, this)?))
+    });
+
+    let string_next = native_function(intrinsics, "next", &[], |runtime, this, _| {
+        Ok(Some(string_iterator_next(runtime, this)?))
+    });
+
+    let map_next = native_function(intrinsics, "next", &[], |runtime, this, _| {
+        Ok(Some(map_iterator_next(runtime, this)?))
+    });
+
+    let set_next = native_function(intrinsics, "next", &[], |runtime, this, _| {
+        Ok(Some(set_iterator_next(runtime, this)?))
+    });
+
+    intrinsics.array_iterator_prototype.borrow_mut().define_builtin("next", array_next);
+    intrinsics.string_iterator_prototype.borrow_mut().define_builtin("next", string_next);
+    intrinsics.map_iterator_prototype.borrow_mut().define_builtin("next", map_next);
+    intrinsics.set_iterator_prototype.borrow_mut().define_builtin("next", set_next);
+
+    // Strings iterate by code point, so a surrogate pair is a single value
+    let string_iterator = native_function(intrinsics, "[Symbol.iterator]", &[], |runtime, this, _| {
+        let text = match this {
+            Value::Undefined | Value::Null => return runtime.type_error(&format!("String.prototype[Symbol.iterator] called on {}", this.to_display_string())),
+            value => to_string(runtime, value)?,
+        };
+
+        let iterator = ObjectKind::StringIterator { text: Some(Rc::from(text)), position: 0 };
+
+        Ok(Some(Value::Object(Object::new(iterator, Some(runtime.intrinsics.string_iterator_prototype.clone())))))
+    });
+
+    intrinsics.string_prototype.borrow_mut().define_builtin(&intrinsics.iterator_symbol, string_iterator);
+}
+
+pub fn create_array_iterator(runtime: &Runtime, iterated: ObjectRef, kind: IterationKind) -> Value {
+    let iterator = ObjectKind::ArrayIterator { iterated: Some(iterated), index: 0, kind };
+
+    Value::Object(Object::new(iterator, Some(runtime.intrinsics.array_iterator_prototype.clone())))
+}
+
+pub fn create_map_iterator(runtime: &Runtime, map: ObjectRef, kind: IterationKind) -> Value {
+    let iterator = ObjectKind::MapIterator { map: Some(map), index: 0, kind };
+
+    Value::Object(Object::new(iterator, Some(runtime.intrinsics.map_iterator_prototype.clone())))
+}
+
+pub fn create_set_iterator(runtime: &Runtime, set: ObjectRef, kind: IterationKind) -> Value {
+    let iterator = ObjectKind::SetIterator { set: Some(set), index: 0, kind };
+
+    Value::Object(Object::new(iterator, Some(runtime.intrinsics.set_iterator_prototype.clone())))
+}
+
+// The length is read again on every step, so elements pushed while iterating are visited too
+fn array_iterator_next(runtime: &mut Runtime, this: &Value) -> RuntimeResult<Value> {
+    let iterator = iterator_object(runtime, this, "Array Iterator", |kind| matches!(kind, ObjectKind::ArrayIterator { .. }))?;
+
+    let (iterated, index, kind) = match &iterator.borrow().kind {
+        ObjectKind::ArrayIterator { iterated: Some(iterated), index, kind } => (iterated.clone(), *index, *kind),
+        ObjectKind::ArrayIterator { iterated: None, .. } => return Ok(create_iter_result_object(runtime, Value::Undefined, true)),
+        _ => unreachable!(),
+    };
+
+    if index >= length_of_array_like(runtime, &iterated)? {
+        if let ObjectKind::ArrayIterator { iterated, .. } = &mut iterator.borrow_mut().kind {
+            *iterated = None;
+        }
+
+        return Ok(create_iter_result_object(runtime, Value::Undefined, true));
+    }
+
+    if let ObjectKind::ArrayIterator { index, .. } = &mut iterator.borrow_mut().kind {
+        *index += 1;
+    }
+
+    let key = Value::Number(index as f64);
+
+    let value = match kind {
+        IterationKind::Keys => key,
+        IterationKind::Values => get(runtime, &iterated, &PropertyKey::from(index), &Value::Object(iterated.clone()))?,
+        IterationKind::Entries => {
+            let value = get(runtime, &iterated, &PropertyKey::from(index), &Value::Object(iterated.clone()))?;
+            create_array_from_list(runtime, vec![key, value])
+        },
+    };
+
+    Ok(create_iter_result_object(runtime, value, false))
+}
+
+fn string_iterator_next(runtime: &mut Runtime, this: &Value) -> RuntimeResult<Value> {
+    let iterator = iterator_object(runtime, this, "String Iterator", |kind| matches!(kind, ObjectKind::StringIterator { .. }))?;
+
+    let next = match &mut iterator.borrow_mut().kind {
+        ObjectKind::StringIterator { text: text @ Some(_), position } => {
+            let next = text.as_ref().and_then(|text| text[*position..].chars().next());
+
+            match next {
+                Some(char) => *position += char.len_utf8(),
+                None => *text = None,
+            }
+
+            next
+        },
+        _ => None,
+    };
+
+    Ok(match next {
+        Some(char) => create_iter_result_object(runtime, Value::string(&char.to_string()), false),
+        None => create_iter_result_object(runtime, Value::Undefined, true),
+    })
+}
+
+// Entries deleted while iterating are skipped, entries added are still visited
+fn map_iterator_next(runtime: &mut Runtime, this: &Value) -> RuntimeResult<Value> {
+    let iterator = iterator_object(runtime, this, "Map Iterator", |kind| matches!(kind, ObjectKind::MapIterator { .. }))?;
+
+    let entry = match &mut iterator.borrow_mut().kind {
+        ObjectKind::MapIterator { map, index, kind } => next_entry(map, index).map(|entry| (entry, *kind)),
+        _ => unreachable!(),
+    };
+
+    Ok(match entry {
+        Some(((key, value), kind)) => {
+            let value = match kind {
+                IterationKind::Keys => key,
+                IterationKind::Values => value,
+                IterationKind::Entries => create_array_from_list(runtime, vec![key, value]),
+            };
+
+            create_iter_result_object(runtime, value, false)
+        },
+        None => create_iter_result_object(runtime, Value::Undefined, true),
+    })
+}
+
+fn set_iterator_next(runtime: &mut Runtime, this: &Value) -> RuntimeResult<Value> {
+    let iterator = iterator_object(runtime, this, "Set Iterator", |kind| matches!(kind, ObjectKind::SetIterator { .. }))?;
+
+    let entry = match &mut iterator.borrow_mut().kind {
+        ObjectKind::SetIterator { set, index, kind } => next_entry(set, index).map(|(value, _)| (value, *kind)),
+        _ => unreachable!(),
+    };
+
+    Ok(match entry {
+        Some((value, IterationKind::Entries)) => {
+            let entry = create_array_from_list(runtime, vec![value.clone(), value]);
+            create_iter_result_object(runtime, entry, false)
+        },
+        Some((value, _)) => create_iter_result_object(runtime, value, false),
+        None => create_iter_result_object(runtime, Value::Undefined, true),
+    })
+}
+
+// The next live entry of a Map or Set at or after `index`, the collection is dropped once there are none left
+fn next_entry(collection: &mut Option<ObjectRef>, index: &mut usize) -> Option<(Value, Value)> {
+    let entry = collection.as_ref().and_then(|collection| match &collection.borrow().kind {
+        ObjectKind::Map(data) | ObjectKind::Set(data) => data.entry(*index),
+        _ => unreachable!(),
+    });
+
+    match entry {
+        Some((position, key, value)) => {
+            *index = position + 1;
+            Some((key, value))
+        },
+        None => {
+            *collection = None;
+            None
+        },
+    }
+}
+
+fn iterator_object(runtime: &Runtime, this: &Value, name: &str, is_kind: impl Fn(&ObjectKind) -> bool) -> RuntimeResult<ObjectRef> {
+    match this {
+        Value::Object(object) if is_kind(&object.borrow().kind) => Ok(object.clone()),
+        _ => runtime.type_error(&format!("Method {}.prototype.next called on incompatible receiver {}", name, this.to_display_string())),
+    }
+}
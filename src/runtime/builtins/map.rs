@@ -0,0 +1,259 @@
+use std::{collections::HashMap, rc::Rc};
+
+use num_bigint::BigInt;
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::RuntimeResult, object::{Object, ObjectKind, ObjectRef, PropertyDescriptor, PropertyKey}, operations::{call, get_iterator, get_property, is_callable, iterator_close, iterator_step_value}, value::Value, Runtime};
+
+use super::{argument, iterator::{create_map_iterator, IterationKind}, native_constructor, native_function};
+
+// The entries of a Map or Set in insertion order. Deleting leaves a hole behind,
+// so iterators that are part-way through keep their place.
+#[derive(Default)]
+pub struct MapData {
+    entries: Vec<Option<(Value, Value)>>,
+    index: HashMap<MapKey, usize>,
+}
+
+// SameValueZero as a hash key, all NaNs are the same key and -0 is the same as +0
+#[derive(PartialEq, Eq, Hash)]
+enum MapKey {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(u64),
+    BigInt(Rc<BigInt>),
+    String(Rc<str>),
+    // Symbols and objects are compared by identity
+    Reference(usize),
+}
+
+impl MapKey {
+    fn new(value: &Value) -> MapKey {
+        match value {
+            Value::Undefined => MapKey::Undefined,
+            Value::Null => MapKey::Null,
+            Value::Boolean(value) => MapKey::Boolean(*value),
+            Value::Number(number) if number.is_nan() => MapKey::Number(f64::NAN.to_bits()),
+            Value::Number(number) => MapKey::Number((number + 0.0).to_bits()),
+            Value::BigInt(value) => MapKey::BigInt(value.clone()),
+            Value::String(value) => MapKey::String(value.clone()),
+            Value::Symbol(symbol) => MapKey::Reference(Rc::as_ptr(symbol) as usize),
+            Value::Object(object) => MapKey::Reference(Rc::as_ptr(object) as *const () as usize),
+        }
+    }
+}
+
+impl MapData {
+    pub fn get(&self, key: &Value) -> Option<Value> {
+        let index = self.index.get(&MapKey::new(key))?;
+        self.entries[*index].as_ref().map(|(_, value)| value.clone())
+    }
+
+    pub fn has(&self, key: &Value) -> bool {
+        self.index.contains_key(&MapKey::new(key))
+    }
+
+    // A key of -0 is stored as +0
+    pub fn set(&mut self, key: Value, value: Value) {
+        let key = match key {
+            Value::Number(number) => Value::Number(number + 0.0),
+            key => key,
+        };
+
+        match self.index.get(&MapKey::new(&key)) {
+            Some(index) => self.entries[*index] = Some((key, value)),
+            None => {
+                self.index.insert(MapKey::new(&key), self.entries.len());
+                self.entries.push(Some((key, value)));
+            },
+        }
+    }
+
+    pub fn delete(&mut self, key: &Value) -> bool {
+        match self.index.remove(&MapKey::new(key)) {
+            Some(index) => {
+                self.entries[index] = None;
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.index.clear();
+
+        for entry in self.entries.iter_mut() {
+            *entry = None;
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.index.len()
+    }
+
+    // The first entry at or after `from` that has not been deleted, with its position
+    pub fn entry(&self, from: usize) -> Option<(usize, Value, Value)> {
+        self.entries.iter()
+            .enumerate()
+            .skip(from)
+            .find_map(|(index, entry)| entry.as_ref().map(|(key, value)| (index, key.clone(), value.clone())))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Value, Value)> {
+        self.entries.iter().flatten()
+    }
+}
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    // new Map(iterable), the entries are added through `set`, so a subclass can see them go in
+    let map = native_constructor(intrinsics, "Map", &[], &intrinsics.map_prototype, |runtime, this, x| {
+        if !matches!(this, Value::Object(_)) {
+            return runtime.type_error("Constructor Map requires 'new'");
+        }
+
+        let map = Value::Object(Object::new(ObjectKind::Map(MapData::default()), Some(runtime.intrinsics.map_prototype.clone())));
+
+        let iterable = argument(x, 0);
+
+        if matches!(iterable, Value::Undefined | Value::Null) {
+            return Ok(Some(map));
+        }
+
+        let adder = get_property(runtime, &map, &PropertyKey::from("set"))?;
+
+        if !is_callable(&adder) {
+            return runtime.type_error(&format!("'{}' returned for property 'set' of object '#<Map>' is not a function", adder.to_display_string()));
+        }
+
+        let record = get_iterator(runtime, &iterable)?;
+
+        while let Some(entry) = iterator_step_value(runtime, &record)? {
+            if let Err(error) = add_entry(runtime, &map, &adder, &entry) {
+                return iterator_close(runtime, &record, Err(error));
+            }
+        }
+
+        Ok(Some(map))
+    });
+
+    let get = native_function(intrinsics, "get", &["key"], |runtime, this, x| {
+        let map = this_map(runtime, this, "get")?;
+        let value = with_map(&map, |data| data.get(&argument(x, 0)));
+
+        Ok(Some(value.unwrap_or(Value::Undefined)))
+    });
+
+    let set = native_function(intrinsics, "set", &["key", "value"], |runtime, this, x| {
+        let map = this_map(runtime, this, "set")?;
+        with_map(&map, |data| data.set(argument(x, 0), argument(x, 1)));
+
+        Ok(Some(this.clone()))
+    });
+
+    let has = native_function(intrinsics, "has", &["key"], |runtime, this, x| {
+        let map = this_map(runtime, this, "has")?;
+
+        Ok(Some(Value::Boolean(with_map(&map, |data| data.has(&argument(x, 0))))))
+    });
+
+    let delete = native_function(intrinsics, "delete", &["key"], |runtime, this, x| {
+        let map = this_map(runtime, this, "delete")?;
+
+        Ok(Some(Value::Boolean(with_map(&map, |data| data.delete(&argument(x, 0))))))
+    });
+
+    let clear = native_function(intrinsics, "clear", &[], |runtime, this, _| {
+        let map = this_map(runtime, this, "clear")?;
+        with_map(&map, |data| data.clear());
+
+        Ok(Some(Value::Undefined))
+    });
+
+    // Entries added by the callback are visited as well, deleted ones are not
+    let for_each = native_function(intrinsics, "forEach", &["callbackfn"], |runtime, this, x| {
+        let map = this_map(runtime, this, "forEach")?;
+        let callback = argument(x, 0);
+
+        if !is_callable(&callback) {
+            return runtime.type_error(&format!("{} is not a function", callback.to_display_string()));
+        }
+
+        let mut index = 0;
+
+        while let Some((position, key, value)) = with_map(&map, |data| data.entry(index)) {
+            call(runtime, &callback, argument(x, 1), vec![value, key, this.clone()])?;
+            index = position + 1;
+        }
+
+        Ok(Some(Value::Undefined))
+    });
+
+    let keys = native_function(intrinsics, "keys", &[], |runtime, this, _| {
+        let map = this_map(runtime, this, "keys")?;
+        Ok(Some(create_map_iterator(runtime, map, IterationKind::Keys)))
+    });
+
+    let values = native_function(intrinsics, "values", &[], |runtime, this, _| {
+        let map = this_map(runtime, this, "values")?;
+        Ok(Some(create_map_iterator(runtime, map, IterationKind::Values)))
+    });
+
+    let entries = native_function(intrinsics, "entries", &[], |runtime, this, _| {
+        let map = this_map(runtime, this, "entries")?;
+        Ok(Some(create_map_iterator(runtime, map, IterationKind::Entries)))
+    });
+
+    let size = native_function(intrinsics, "get size", &[], |runtime, this, _| {
+        let map = this_map(runtime, this, "size")?;
+        Ok(Some(Value::Number(with_map(&map, |data| data.size()) as f64)))
+    });
+
+    let mut prototype = intrinsics.map_prototype.borrow_mut();
+
+    prototype.define_builtin("get", get);
+    prototype.define_builtin("set", set);
+    prototype.define_builtin("has", has);
+    prototype.define_builtin("delete", delete);
+    prototype.define_builtin("clear", clear);
+    prototype.define_builtin("forEach", for_each);
+    prototype.define_builtin("keys", keys);
+    prototype.define_builtin("values", values);
+    prototype.define_builtin("entries", entries.clone());
+    prototype.define_builtin(&intrinsics.iterator_symbol, entries);
+    prototype.define_own_property(PropertyKey::from("size"), PropertyDescriptor {
+        get: Some(size),
+        enumerable: Some(false),
+        configurable: Some(true),
+        ..Default::default()
+    });
+
+    variables.insert("Map".to_string(), map);
+}
+
+// Each entry of the iterable is an object whose elements 0 and 1 are the key and the value
+fn add_entry(runtime: &mut Runtime, map: &Value, adder: &Value, entry: &Value) -> RuntimeResult<()> {
+    if !matches!(entry, Value::Object(_)) {
+        return runtime.type_error(&format!("Iterator value {} is not an entry object", entry.to_display_string()));
+    }
+
+    let key = get_property(runtime, entry, &PropertyKey::from(0))?;
+    let value = get_property(runtime, entry, &PropertyKey::from(1))?;
+
+    call(runtime, adder, map.clone(), vec![key, value])?;
+
+    Ok(())
+}
+
+fn this_map(runtime: &Runtime, this: &Value, method: &str) -> RuntimeResult<ObjectRef> {
+    match this {
+        Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Map(_)) => Ok(object.clone()),
+        _ => runtime.type_error(&format!("Method Map.prototype.{} called on incompatible receiver {}", method, this.to_display_string())),
+    }
+}
+
+fn with_map<T>(map: &ObjectRef, f: impl FnOnce(&mut MapData) -> T) -> T {
+    match &mut map.borrow_mut().kind {
+        ObjectKind::Map(data) => f(data),
+        _ => unreachable!(),
+    }
+}
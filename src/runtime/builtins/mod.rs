@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+pub use self::{async_function::start_async_function, async_generator::AsyncGeneratorData, error::{create_error, error_text, ErrorKind}, generator::{generator_return, generator_return_value, GeneratorState}, iterator::IterationKind, map::MapData, promise::{new_promise, on_settled, promise_resolve, promise_result, reject_promise, resolve_promise, PromiseData, PromiseState}};
+
+use super::{intrinsics::Intrinsics, nodes::{FunctionCall, FunctionCallType, RuntimeResult, SystemCall}, object::{ObjectRef, PropertyDescriptor, PropertyKey}, value::Value, Runtime};
+
+mod array;
+mod async_function;
+mod async_generator;
+mod bigint;
+mod console;
+mod error;
+mod function;
+mod generator;
+mod globals;
+mod iterator;
+mod map;
+mod object;
+mod promise;
+mod regexp;
+mod set;
+mod symbol;
+mod timers;
+
+pub fn register_builtins(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    array::register(variables, intrinsics);
+    async_generator::register(intrinsics);
+    bigint::register(variables, intrinsics);
+    console::register(variables, intrinsics);
+    error::register(variables, intrinsics);
+    function::register(variables, intrinsics);
+    generator::register(intrinsics);
+    globals::register(variables);
+    iterator::register(intrinsics);
+    map::register(variables, intrinsics);
+    object::register(variables, intrinsics);
+    promise::register(variables, intrinsics);
+    regexp::register(intrinsics);
+    set::register(variables, intrinsics);
+    symbol::register(variables, intrinsics);
+    timers::register(variables, intrinsics);
+}
+
+// Wraps a native closure as a function value, `arguments` names its parameters and gives it a `length`
+fn native_function(intrinsics: &Intrinsics, name: &str, arguments: &[&str], func: impl Fn(&mut Runtime, &Value, &[Value]) -> RuntimeResult<Option<Value>> + 'static) -> Value {
+    create_native(intrinsics, name, arguments, func, false)
+}
+
+// A native function that also works with `new`, linked both ways with its prototype object
+fn native_constructor(intrinsics: &Intrinsics, name: &str, arguments: &[&str], prototype: &ObjectRef, func: impl Fn(&mut Runtime, &Value, &[Value]) -> RuntimeResult<Option<Value>> + 'static) -> Value {
+    let constructor = create_native(intrinsics, name, arguments, func, true);
+
+    if let Value::Object(object) = &constructor {
+        object.borrow_mut().define_own_property(PropertyKey::from("prototype"), PropertyDescriptor {
+            value: Some(Value::Object(prototype.clone())),
+            writable: Some(false),
+            enumerable: Some(false),
+            configurable: Some(false),
+            ..Default::default()
+        });
+    }
+
+    prototype.borrow_mut().define_builtin("constructor", constructor.clone());
+
+    constructor
+}
+
+fn create_native(intrinsics: &Intrinsics, name: &str, arguments: &[&str], func: impl Fn(&mut Runtime, &Value, &[Value]) -> RuntimeResult<Option<Value>> + 'static, constructor: bool) -> Value {
+    let function = FunctionCall {
+        name: name.to_string(),
+        function_type: FunctionCallType::SystemCall(SystemCall::new(func)),
+        length: arguments.len(),
+        constructor,
+    };
+
+    Value::function(function, &intrinsics.function_prototype)
+}
+
+// Arguments that were not passed are undefined
+fn argument(arguments: &[Value], index: usize) -> Value {
+    arguments.get(index).cloned().unwrap_or(Value::Undefined)
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::RuntimeResult, object::{check_array_length, get, has_in_prototype_chain, set_prototype_of, Object, ObjectKind, ObjectRef, Property, PropertyDescriptor, PropertyKey}, operations::{same_value, string_property, to_boolean, to_property_key}, value::Value, Runtime};
+
+use super::{argument, native_constructor, native_function};
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    // Without wrapper objects, primitives turn into empty objects
+    let object = native_constructor(intrinsics, "Object", &["value"], &intrinsics.object_prototype, |runtime, _, x| {
+        match &argument(x, 0) {
+            Value::Object(object) => Ok(Some(Value::Object(object.clone()))),
+            _ => Ok(Some(Value::Object(Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()))))),
+        }
+    });
+
+    let is = native_function(intrinsics, "is", &["left", "right"], |_, _, x| {
+        let left = &argument(x, 0);
+        let right = &argument(x, 1);
+
+        Ok(Some(Value::Boolean(same_value(left, right))))
+    });
+
+    let create = native_function(intrinsics, "create", &["prototype", "properties"], |runtime, _, x| {
+        let prototype = match &argument(x, 0) {
+            Value::Object(prototype) => Some(prototype.clone()),
+            Value::Null => None,
+            value => return runtime.type_error(&format!("Object prototype may only be an Object or null: {}", value.to_display_string())),
+        };
+
+        let object = Object::new_ordinary(prototype);
+
+        match &argument(x, 1) {
+            Value::Undefined => {},
+            properties => define_properties(runtime, &object, properties)?,
+        }
+
+        Ok(Some(Value::Object(object)))
+    });
+
+    let get_prototype_of = native_function(intrinsics, "getPrototypeOf", &["object"], |runtime, _, x| {
+        let value = &argument(x, 0);
+
+        let prototype = match value {
+            Value::Object(object) => object.borrow().prototype.clone(),
+            Value::Undefined | Value::Null => return runtime.type_error("Cannot convert undefined or null to object"),
+            _ => runtime.intrinsics.primitive_prototype(value),
+        };
+
+        Ok(Some(prototype.map(Value::Object).unwrap_or(Value::Null)))
+    });
+
+    let set_prototype = native_function(intrinsics, "setPrototypeOf", &["object", "prototype"], |runtime, _, x| {
+        let prototype = match &argument(x, 1) {
+            Value::Object(prototype) => Some(prototype.clone()),
+            Value::Null => None,
+            value => return runtime.type_error(&format!("Object prototype may only be an Object or null: {}", value.to_display_string())),
+        };
+
+        // Primitives have no prototype slot of their own, the call leaves them untouched
+        match &argument(x, 0) {
+            Value::Object(object) => {
+                if !set_prototype_of(object, prototype) {
+                    return runtime.type_error("Cyclic __proto__ value");
+                }
+
+                Ok(Some(Value::Object(object.clone())))
+            },
+            Value::Undefined | Value::Null => runtime.type_error("Object.setPrototypeOf called on null or undefined"),
+            value => Ok(Some(value.clone())),
+        }
+    });
+
+    let define_property = native_function(intrinsics, "defineProperty", &["object", "key", "attributes"], |runtime, _, x| {
+        let object = match &argument(x, 0) {
+            Value::Object(object) => object.clone(),
+            _ => return runtime.type_error("Object.defineProperty called on non-object"),
+        };
+
+        let key = to_property_key(runtime, &argument(x, 1))?;
+        let descriptor = to_property_descriptor(runtime, &argument(x, 2))?;
+
+        define_property_or_throw(runtime, &object, key, descriptor)?;
+
+        Ok(Some(Value::Object(object)))
+    });
+
+    let define_properties_function = native_function(intrinsics, "defineProperties", &["object", "properties"], |runtime, _, x| {
+        let object = match &argument(x, 0) {
+            Value::Object(object) => object.clone(),
+            _ => return runtime.type_error("Object.defineProperties called on non-object"),
+        };
+
+        define_properties(runtime, &object, &argument(x, 1))?;
+
+        Ok(Some(Value::Object(object)))
+    });
+
+    let get_own_property_descriptor = native_function(intrinsics, "getOwnPropertyDescriptor", &["object", "key"], |runtime, _, x| {
+        let object = match &argument(x, 0) {
+            Value::Object(object) => object.clone(),
+            value => return runtime.type_error(&format!("Cannot convert {} to object", value.to_display_string())),
+        };
+
+        let key = to_property_key(runtime, &argument(x, 1))?;
+        let property = object.borrow().get_own_property(&key);
+
+        Ok(Some(match property {
+            Some(property) => from_property(runtime, property),
+            None => Value::Undefined,
+        }))
+    });
+
+    if let Value::Object(constructor) = &object {
+        let mut constructor = constructor.borrow_mut();
+
+        constructor.define_builtin("is", is);
+        constructor.define_builtin("create", create);
+        constructor.define_builtin("getPrototypeOf", get_prototype_of);
+        constructor.define_builtin("setPrototypeOf", set_prototype);
+        constructor.define_builtin("defineProperty", define_property);
+        constructor.define_builtin("defineProperties", define_properties_function);
+        constructor.define_builtin("getOwnPropertyDescriptor", get_own_property_descriptor);
+    }
+
+    register_prototype(intrinsics);
+
+    variables.insert("Object".to_string(), object);
+}
+
+fn register_prototype(intrinsics: &Intrinsics) {
+    let has_own_property = native_function(intrinsics, "hasOwnProperty", &["key"], |runtime, this, x| {
+        let key = to_property_key(runtime, &argument(x, 0))?;
+
+        Ok(Some(Value::Boolean(own_property(runtime, this, &key)?.is_some())))
+    });
+
+    let property_is_enumerable = native_function(intrinsics, "propertyIsEnumerable", &["key"], |runtime, this, x| {
+        let key = to_property_key(runtime, &argument(x, 0))?;
+
+        Ok(Some(Value::Boolean(own_property(runtime, this, &key)?.is_some_and(|property| property.enumerable()))))
+    });
+
+    let is_prototype_of = native_function(intrinsics, "isPrototypeOf", &["object"], |runtime, this, x| {
+        let object = match argument(x, 0) {
+            Value::Object(object) => object,
+            _ => return Ok(Some(Value::Boolean(false))),
+        };
+
+        Ok(Some(Value::Boolean(match this {
+            Value::Object(prototype) => has_in_prototype_chain(&object, prototype),
+            Value::Undefined | Value::Null => return runtime.type_error("Cannot convert undefined or null to object"),
+            _ => false,
+        })))
+    });
+
+    // Object.prototype.toString, the tag comes from the kind of value without Symbol.toStringTag
+    let to_string = native_function(intrinsics, "toString", &[], |_, this, _| {
+        let tag = match this {
+            Value::Undefined => "Undefined",
+            Value::Null => "Null",
+            Value::Boolean(_) => "Boolean",
+            Value::Number(_) => "Number",
+            Value::BigInt(_) => "BigInt",
+            Value::String(_) => "String",
+            Value::Symbol(_) => "Symbol",
+            Value::Object(object) => match object.borrow().kind {
+                ObjectKind::Ordinary => "Object",
+                ObjectKind::Function(_) => "Function",
+                ObjectKind::Array => "Array",
+                ObjectKind::RegExp { .. } => "RegExp",
+                ObjectKind::Arguments => "Arguments",
+                ObjectKind::Error => "Error",
+                ObjectKind::Generator(_) => "Generator",
+                ObjectKind::GeneratorReturn(_) => "Object",
+                ObjectKind::AsyncGenerator(_) => "AsyncGenerator",
+                ObjectKind::Promise(_) => "Promise",
+                ObjectKind::Map(_) => "Map",
+                ObjectKind::Set(_) => "Set",
+                ObjectKind::ArrayIterator { .. } => "Array Iterator",
+                ObjectKind::StringIterator { .. } => "String Iterator",
+                ObjectKind::MapIterator { .. } => "Map Iterator",
+                ObjectKind::SetIterator { .. } => "Set Iterator",
+                ObjectKind::ModuleNamespace(_) => "Module",
+            },
+        };
+
+        Ok(Some(Value::string(&format!("[object {}]", tag))))
+    });
+
+    let value_of = native_function(intrinsics, "valueOf", &[], |runtime, this, _| {
+        match this {
+            Value::Undefined | Value::Null => runtime.type_error("Cannot convert undefined or null to object"),
+            value => Ok(Some(value.clone())),
+        }
+    });
+
+    let mut prototype = intrinsics.object_prototype.borrow_mut();
+
+    prototype.define_builtin("hasOwnProperty", has_own_property);
+    prototype.define_builtin("propertyIsEnumerable", property_is_enumerable);
+    prototype.define_builtin("isPrototypeOf", is_prototype_of);
+    prototype.define_builtin("toString", to_string);
+    prototype.define_builtin("valueOf", value_of);
+}
+
+// The own property behind `key` as if `value` were converted to an object, strings own their indices and length
+fn own_property(runtime: &Runtime, value: &Value, key: &PropertyKey) -> RuntimeResult<Option<Property>> {
+    Ok(match value {
+        Value::Object(object) => object.borrow().get_own_property(key),
+        Value::Undefined | Value::Null => return runtime.type_error("Cannot convert undefined or null to object"),
+        Value::String(text) => {
+            let value = string_property(text, key);
+            let enumerable = key.array_index().is_some();
+
+            (!matches!(value, Value::Undefined)).then(|| Property::Data { value, writable: false, enumerable, configurable: false })
+        },
+        _ => None,
+    })
+}
+
+// ObjectDefineProperties, every descriptor is read before any of them is applied
+fn define_properties(runtime: &mut Runtime, object: &ObjectRef, properties: &Value) -> RuntimeResult<()> {
+    let properties = match properties {
+        Value::Object(properties) => properties.clone(),
+        Value::Undefined | Value::Null => return runtime.type_error("Cannot convert undefined or null to object"),
+        _ => return Ok(()),
+    };
+
+    let keys: Vec<PropertyKey> = {
+        let properties = properties.borrow();
+
+        properties.own_property_keys().into_iter()
+            .filter(|key| properties.get_own_property(key).is_some_and(|property| property.enumerable()))
+            .collect()
+    };
+
+    let descriptors: Vec<(PropertyKey, PropertyDescriptor)> = keys.into_iter()
+        .map(|key| {
+            let attributes = get(runtime, &properties, &key, &Value::Object(properties.clone()))?;
+            Ok((key, to_property_descriptor(runtime, &attributes)?))
+        })
+        .collect::<RuntimeResult<_>>()?;
+
+    for (key, descriptor) in descriptors {
+        define_property_or_throw(runtime, object, key, descriptor)?;
+    }
+
+    Ok(())
+}
+
+// DefinePropertyOrThrow
+fn define_property_or_throw(runtime: &Runtime, object: &ObjectRef, key: PropertyKey, descriptor: PropertyDescriptor) -> RuntimeResult<()> {
+    if let Some(value) = &descriptor.value {
+        check_array_length(runtime, object, &key, value)?;
+    }
+
+    if !object.borrow_mut().define_own_property(key.clone(), descriptor) {
+        return runtime.type_error(&format!("Cannot redefine property: {}", key));
+    }
+
+    Ok(())
+}
+
+// ToPropertyDescriptor, reads the fields that are present on an attributes object
+fn to_property_descriptor(runtime: &mut Runtime, attributes: &Value) -> RuntimeResult<PropertyDescriptor> {
+    let attributes = match attributes {
+        Value::Object(attributes) => attributes.clone(),
+        value => return runtime.type_error(&format!("Property description must be an object: {}", value.to_display_string())),
+    };
+
+    let field = |runtime: &mut Runtime, name: &str| -> RuntimeResult<Option<Value>> {
+        let key = PropertyKey::from(name);
+        let exists = attributes.borrow().get_own_property(&key).is_some();
+
+        exists.then(|| get(runtime, &attributes, &key, &Value::Object(attributes.clone()))).transpose()
+    };
+
+    let descriptor = PropertyDescriptor {
+        enumerable: field(runtime, "enumerable")?.map(|value| to_boolean(&value)),
+        configurable: field(runtime, "configurable")?.map(|value| to_boolean(&value)),
+        value: field(runtime, "value")?,
+        writable: field(runtime, "writable")?.map(|value| to_boolean(&value)),
+        get: field(runtime, "get")?,
+        set: field(runtime, "set")?,
+    };
+
+    for accessor in descriptor.get.iter().chain(descriptor.set.iter()) {
+        if !matches!(accessor, Value::Undefined) && accessor.as_function().is_none() {
+            return runtime.type_error(&format!("Getter and setter must be functions: {}", accessor.to_display_string()));
+        }
+    }
+
+    if descriptor.is_accessor() && (descriptor.value.is_some() || descriptor.writable.is_some()) {
+        return runtime.type_error("Invalid property descriptor. Cannot both specify accessors and a value or writable attribute");
+    }
+
+    Ok(descriptor)
+}
+
+// FromPropertyDescriptor, the plain object `getOwnPropertyDescriptor` returns
+fn from_property(runtime: &Runtime, property: Property) -> Value {
+    let descriptor: ObjectRef = Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()));
+
+    let fields = match property {
+        Property::Data { value, writable, enumerable, configurable } => vec![
+            ("value", value),
+            ("writable", Value::Boolean(writable)),
+            ("enumerable", Value::Boolean(enumerable)),
+            ("configurable", Value::Boolean(configurable)),
+        ],
+        Property::Accessor { get, set, enumerable, configurable } => vec![
+            ("get", get.unwrap_or(Value::Undefined)),
+            ("set", set.unwrap_or(Value::Undefined)),
+            ("enumerable", Value::Boolean(enumerable)),
+            ("configurable", Value::Boolean(configurable)),
+        ],
+    };
+
+    for (name, value) in fields {
+        descriptor.borrow_mut().create_data_property(PropertyKey::from(name), value);
+    }
+
+    Value::Object(descriptor)
+}
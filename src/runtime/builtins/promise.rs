@@ -0,0 +1,655 @@
+use std::{cell::{Cell, RefCell}, collections::HashMap, rc::Rc};
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::{FunctionCallType, RuntimeResult}, object::{Object, ObjectKind, ObjectRef, Property, PropertyKey}, operations::{call, create_array_from_list, get_iterator, get_property, is_callable, iterator_close, iterator_step_value, same_value}, parsers::new_expression::{construct, is_constructor}, value::Value, Runtime};
+
+use super::{argument, create_error, native_constructor, native_function, ErrorKind};
+
+pub struct PromiseData {
+    pub state: PromiseState,
+    fulfill_reactions: Vec<PromiseReaction>,
+    reject_reactions: Vec<PromiseReaction>,
+    // Whether a handler was ever added, rejections without one are reported to the host
+    is_handled: bool,
+}
+
+#[derive(Clone)]
+pub enum PromiseState {
+    Pending,
+    Fulfilled(Value),
+    Rejected(Value),
+}
+
+// A promise and the functions that settle it, what `new C(executor)` hands out for any promise constructor C
+#[derive(Clone)]
+pub struct PromiseCapability {
+    pub promise: Value,
+    pub resolve: Value,
+    pub reject: Value,
+}
+
+// A handler waiting for a promise to settle. Without a handler the value or reason is passed on as it is,
+// without a capability the handler is only run for what it does, like resuming an async function.
+#[derive(Clone)]
+struct PromiseReaction {
+    capability: Option<PromiseCapability>,
+    kind: ReactionKind,
+    handler: Option<Value>,
+}
+
+#[derive(Clone, Copy)]
+enum ReactionKind {
+    Fulfill,
+    Reject,
+}
+
+#[derive(Clone, Copy)]
+enum Combinator {
+    All,
+    AllSettled,
+    Any,
+    Race,
+}
+
+// What Promise.all and its relatives share between the functions they add to each promise
+struct CombinatorState {
+    combinator: Combinator,
+    capability: PromiseCapability,
+    values: RefCell<Vec<Value>>,
+    // Starts at one for the iteration itself, so the result is not settled before every promise is in
+    remaining: Cell<usize>,
+}
+
+impl PromiseData {
+    fn new() -> PromiseData {
+        PromiseData {
+            state: PromiseState::Pending,
+            fulfill_reactions: Vec::new(),
+            reject_reactions: Vec::new(),
+            is_handled: false,
+        }
+    }
+}
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    // The executor gets the resolving functions, an exception it throws rejects the promise
+    let promise = native_constructor(intrinsics, "Promise", &["executor"], &intrinsics.promise_prototype, |runtime, this, x| {
+        if !matches!(this, Value::Object(_)) {
+            return runtime.type_error("Promise constructor cannot be invoked without 'new'");
+        }
+
+        let executor = argument(x, 0);
+
+        if !is_callable(&executor) {
+            return runtime.type_error(&format!("Promise resolver {} is not a function", executor.to_display_string()));
+        }
+
+        let promise = new_promise(runtime);
+        let (resolve, reject) = create_resolving_functions(runtime, &promise);
+
+        if let Err(error) = call(runtime, &executor, Value::Undefined, vec![resolve, reject.clone()]) {
+            call(runtime, &reject, Value::Undefined, vec![error])?;
+        }
+
+        Ok(Some(Value::Object(promise)))
+    });
+
+    let resolve = native_function(intrinsics, "resolve", &["value"], |runtime, this, x| {
+        this_constructor(runtime, this)?;
+        Ok(Some(promise_resolve(runtime, Some(this), argument(x, 0))?))
+    });
+
+    let reject = native_function(intrinsics, "reject", &["reason"], |runtime, this, x| {
+        let capability = new_promise_capability(runtime, Some(this))?;
+        call(runtime, &capability.reject, Value::Undefined, vec![argument(x, 0)])?;
+
+        Ok(Some(capability.promise))
+    });
+
+    // { promise, resolve, reject }, for settling a promise from outside an executor
+    let with_resolvers = native_function(intrinsics, "withResolvers", &[], |runtime, this, _| {
+        let capability = new_promise_capability(runtime, Some(this))?;
+        let result = Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()));
+
+        {
+            let mut result = result.borrow_mut();
+
+            result.create_data_property(PropertyKey::from("promise"), capability.promise);
+            result.create_data_property(PropertyKey::from("resolve"), capability.resolve);
+            result.create_data_property(PropertyKey::from("reject"), capability.reject);
+        }
+
+        Ok(Some(Value::Object(result)))
+    });
+
+    let all = native_function(intrinsics, "all", &["iterable"], |runtime, this, x| {
+        Ok(Some(promise_combinator(runtime, this, &argument(x, 0), Combinator::All)?))
+    });
+
+    let all_settled = native_function(intrinsics, "allSettled", &["iterable"], |runtime, this, x| {
+        Ok(Some(promise_combinator(runtime, this, &argument(x, 0), Combinator::AllSettled)?))
+    });
+
+    let any = native_function(intrinsics, "any", &["iterable"], |runtime, this, x| {
+        Ok(Some(promise_combinator(runtime, this, &argument(x, 0), Combinator::Any)?))
+    });
+
+    let race = native_function(intrinsics, "race", &["iterable"], |runtime, this, x| {
+        Ok(Some(promise_combinator(runtime, this, &argument(x, 0), Combinator::Race)?))
+    });
+
+    if let Value::Object(constructor) = &promise {
+        let mut constructor = constructor.borrow_mut();
+
+        constructor.define_builtin("resolve", resolve);
+        constructor.define_builtin("reject", reject);
+        constructor.define_builtin("withResolvers", with_resolvers);
+        constructor.define_builtin("all", all);
+        constructor.define_builtin("allSettled", all_settled);
+        constructor.define_builtin("any", any);
+        constructor.define_builtin("race", race);
+    }
+
+    register_prototype(intrinsics);
+
+    variables.insert("Promise".to_string(), promise);
+}
+
+fn register_prototype(intrinsics: &Intrinsics) {
+    // The promise it returns is created by the constructor of this one, so subclasses get instances of themselves
+    let then = native_function(intrinsics, "then", &["onFulfilled", "onRejected"], |runtime, this, x| {
+        let promise = this_promise(runtime, this, "then")?;
+        let constructor = species_constructor(runtime, this)?;
+        let capability = new_promise_capability(runtime, constructor.as_ref())?;
+
+        perform_promise_then(runtime, &promise, argument(x, 0), argument(x, 1), Some(capability.clone()));
+
+        Ok(Some(capability.promise))
+    });
+
+    let catch = native_function(intrinsics, "catch", &["onRejected"], |runtime, this, x| {
+        Ok(Some(invoke_then(runtime, this, Value::Undefined, argument(x, 0))?))
+    });
+
+    // The callback runs either way without an argument, and the value or reason passes through it
+    // unless it throws or returns a promise that rejects
+    let finally = native_function(intrinsics, "finally", &["onFinally"], |runtime, this, x| {
+        if !matches!(this, Value::Object(_)) {
+            return runtime.type_error(&format!("Method Promise.prototype.finally called on incompatible receiver {}", this.to_display_string()));
+        }
+
+        let on_finally = argument(x, 0);
+
+        if !is_callable(&on_finally) {
+            return Ok(Some(invoke_then(runtime, this, on_finally.clone(), on_finally)?));
+        }
+
+        let constructor = species_constructor(runtime, this)?;
+
+        let then_finally = {
+            let (on_finally, constructor) = (on_finally.clone(), constructor.clone());
+
+            native_function(&runtime.intrinsics, "", &["value"], move |runtime, _, x| {
+                let value = argument(x, 0);
+                let result = call(runtime, &on_finally, Value::Undefined, vec![])?;
+                let promise = promise_resolve(runtime, constructor.as_ref(), result)?;
+                let value_thunk = native_function(&runtime.intrinsics, "", &[], move |_, _, _| Ok(Some(value.clone())));
+
+                Ok(Some(invoke_then(runtime, &promise, value_thunk, Value::Undefined)?))
+            })
+        };
+
+        let catch_finally = native_function(&runtime.intrinsics, "", &["reason"], move |runtime, _, x| {
+            let reason = argument(x, 0);
+            let result = call(runtime, &on_finally, Value::Undefined, vec![])?;
+            let promise = promise_resolve(runtime, constructor.as_ref(), result)?;
+            let thrower = native_function(&runtime.intrinsics, "", &[], move |_, _, _| Err(reason.clone()));
+
+            Ok(Some(invoke_then(runtime, &promise, thrower, Value::Undefined)?))
+        });
+
+        Ok(Some(invoke_then(runtime, this, then_finally, catch_finally)?))
+    });
+
+    let mut prototype = intrinsics.promise_prototype.borrow_mut();
+
+    prototype.define_builtin("then", then);
+    prototype.define_builtin("catch", catch);
+    prototype.define_builtin("finally", finally);
+}
+
+pub fn new_promise(runtime: &Runtime) -> ObjectRef {
+    Object::new(ObjectKind::Promise(PromiseData::new()), Some(runtime.intrinsics.promise_prototype.clone()))
+}
+
+// The value or reason of a settled promise, undefined while it is pending
+pub fn promise_result(promise: &ObjectRef) -> Value {
+    match &promise.borrow().kind {
+        ObjectKind::Promise(PromiseData { state: PromiseState::Fulfilled(value) | PromiseState::Rejected(value), .. }) => value.clone(),
+        _ => Value::Undefined,
+    }
+}
+
+// CreateResolvingFunctions, only the first call of either function does anything
+fn create_resolving_functions(runtime: &Runtime, promise: &ObjectRef) -> (Value, Value) {
+    let already_resolved = Rc::new(Cell::new(false));
+
+    let resolve = {
+        let (promise, already_resolved) = (promise.clone(), already_resolved.clone());
+
+        native_function(&runtime.intrinsics, "", &["resolution"], move |runtime, _, x| {
+            if !already_resolved.replace(true) {
+                resolve_promise(runtime, &promise, argument(x, 0));
+            }
+
+            Ok(Some(Value::Undefined))
+        })
+    };
+
+    let reject = {
+        let promise = promise.clone();
+
+        native_function(&runtime.intrinsics, "", &["reason"], move |runtime, _, x| {
+            if !already_resolved.replace(true) {
+                reject_promise(runtime, &promise, argument(x, 0));
+            }
+
+            Ok(Some(Value::Undefined))
+        })
+    };
+
+    (resolve, reject)
+}
+
+// What the resolve function does. A thenable is followed in a job of its own, anything else fulfills the promise.
+pub fn resolve_promise(runtime: &mut Runtime, promise: &ObjectRef, resolution: Value) {
+    if matches!(&resolution, Value::Object(object) if Rc::ptr_eq(object, promise)) {
+        let error = create_error(runtime, ErrorKind::TypeError, "Chaining cycle detected for promise #<Promise>");
+        return reject_promise(runtime, promise, error);
+    }
+
+    if !matches!(resolution, Value::Object(_)) {
+        return fulfill_promise(runtime, promise, resolution);
+    }
+
+    let then = match get_property(runtime, &resolution, &PropertyKey::from("then")) {
+        Ok(then) => then,
+        Err(error) => return reject_promise(runtime, promise, error),
+    };
+
+    if !is_callable(&then) {
+        return fulfill_promise(runtime, promise, resolution);
+    }
+
+    // NewPromiseResolveThenableJob
+    let promise = promise.clone();
+
+    runtime.event_loop.enqueue(move |runtime| {
+        let (resolve, reject) = create_resolving_functions(runtime, &promise);
+
+        if let Err(error) = call(runtime, &then, resolution, vec![resolve, reject.clone()]) {
+            call(runtime, &reject, Value::Undefined, vec![error])?;
+        }
+
+        Ok(())
+    });
+}
+
+fn fulfill_promise(runtime: &mut Runtime, promise: &ObjectRef, value: Value) {
+    let reactions = settle_promise(promise, PromiseState::Fulfilled(value.clone()), ReactionKind::Fulfill);
+    trigger_promise_reactions(runtime, reactions, value);
+}
+
+pub fn reject_promise(runtime: &mut Runtime, promise: &ObjectRef, reason: Value) {
+    let reactions = settle_promise(promise, PromiseState::Rejected(reason.clone()), ReactionKind::Reject);
+
+    if let ObjectKind::Promise(PromiseData { is_handled: false, .. }) = promise.borrow().kind {
+        runtime.event_loop.track_rejection(promise, false);
+    }
+
+    trigger_promise_reactions(runtime, reactions, reason);
+}
+
+// Settles a pending promise and hands back the reactions of the kind that now run, the others are dropped
+fn settle_promise(promise: &ObjectRef, state: PromiseState, kind: ReactionKind) -> Vec<PromiseReaction> {
+    match &mut promise.borrow_mut().kind {
+        ObjectKind::Promise(data) => {
+            data.state = state;
+
+            let fulfill_reactions = std::mem::take(&mut data.fulfill_reactions);
+            let reject_reactions = std::mem::take(&mut data.reject_reactions);
+
+            match kind {
+                ReactionKind::Fulfill => fulfill_reactions,
+                ReactionKind::Reject => reject_reactions,
+            }
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn trigger_promise_reactions(runtime: &mut Runtime, reactions: Vec<PromiseReaction>, argument: Value) {
+    for reaction in reactions {
+        enqueue_reaction_job(runtime, reaction, argument.clone());
+    }
+}
+
+// NewPromiseReactionJob, the handler's result settles the promise `then` returned
+fn enqueue_reaction_job(runtime: &mut Runtime, reaction: PromiseReaction, argument: Value) {
+    runtime.event_loop.enqueue(move |runtime| {
+        let result = match (&reaction.handler, reaction.kind) {
+            (Some(handler), _) => call(runtime, handler, Value::Undefined, vec![argument]),
+            (None, ReactionKind::Fulfill) => Ok(argument),
+            (None, ReactionKind::Reject) => Err(argument),
+        };
+
+        match (reaction.capability, result) {
+            (Some(capability), Ok(value)) => call(runtime, &capability.resolve, Value::Undefined, vec![value]).map(|_| ()),
+            (Some(capability), Err(error)) => call(runtime, &capability.reject, Value::Undefined, vec![error]).map(|_| ()),
+            (None, result) => result.map(|_| ()),
+        }
+    });
+}
+
+// PerformPromiseThen, handlers that are not callable pass the value or reason on
+fn perform_promise_then(runtime: &mut Runtime, promise: &ObjectRef, on_fulfilled: Value, on_rejected: Value, capability: Option<PromiseCapability>) {
+    let reaction = |kind, handler: Value| PromiseReaction {
+        capability: capability.clone(),
+        kind,
+        handler: Some(handler).filter(is_callable),
+    };
+
+    let fulfill_reaction = reaction(ReactionKind::Fulfill, on_fulfilled);
+    let reject_reaction = reaction(ReactionKind::Reject, on_rejected);
+
+    let settled = match &mut promise.borrow_mut().kind {
+        ObjectKind::Promise(data) => {
+            let was_handled = std::mem::replace(&mut data.is_handled, true);
+
+            match &data.state {
+                PromiseState::Pending => {
+                    data.fulfill_reactions.push(fulfill_reaction);
+                    data.reject_reactions.push(reject_reaction);
+                    None
+                },
+                PromiseState::Fulfilled(value) => Some((fulfill_reaction, value.clone(), true)),
+                PromiseState::Rejected(reason) => Some((reject_reaction, reason.clone(), was_handled)),
+            }
+        },
+        _ => unreachable!(),
+    };
+
+    if let Some((reaction, argument, was_handled)) = settled {
+        if !was_handled {
+            runtime.event_loop.track_rejection(promise, true);
+        }
+
+        enqueue_reaction_job(runtime, reaction, argument);
+    }
+}
+
+// Runs `continuation` with the value or reason once the promise settles, which is how an `await` is resumed
+pub fn on_settled(runtime: &mut Runtime, promise: &Value, continuation: impl Fn(&mut Runtime, RuntimeResult<Value>) + 'static) {
+    let promise = match promise {
+        Value::Object(promise) => promise.clone(),
+        _ => unreachable!(),
+    };
+
+    let continuation = Rc::new(continuation);
+
+    let on_fulfilled = {
+        let continuation = continuation.clone();
+
+        native_function(&runtime.intrinsics, "", &["value"], move |runtime, _, x| {
+            continuation(runtime, Ok(argument(x, 0)));
+            Ok(None)
+        })
+    };
+
+    let on_rejected = native_function(&runtime.intrinsics, "", &["reason"], move |runtime, _, x| {
+        continuation(runtime, Err(argument(x, 0)));
+        Ok(None)
+    });
+
+    perform_promise_then(runtime, &promise, on_fulfilled, on_rejected, None);
+}
+
+// NewPromiseCapability, None stands for %Promise% itself.
+// Other constructors are called with an executor that collects the resolving functions.
+fn new_promise_capability(runtime: &mut Runtime, constructor: Option<&Value>) -> RuntimeResult<PromiseCapability> {
+    let constructor = match constructor {
+        Some(constructor) if is_constructor(constructor) => constructor,
+        Some(constructor) => return runtime.type_error(&format!("{} is not a constructor", constructor.to_display_string())),
+        None => {
+            let promise = new_promise(runtime);
+            let (resolve, reject) = create_resolving_functions(runtime, &promise);
+
+            return Ok(PromiseCapability { promise: Value::Object(promise), resolve, reject });
+        },
+    };
+
+    let functions = Rc::new(RefCell::new((Value::Undefined, Value::Undefined)));
+
+    let executor = {
+        let functions = functions.clone();
+
+        native_function(&runtime.intrinsics, "", &["resolve", "reject"], move |runtime, _, x| {
+            let mut functions = functions.borrow_mut();
+
+            if !matches!(*functions, (Value::Undefined, Value::Undefined)) {
+                return runtime.type_error("Promise executor has already been invoked with non-undefined arguments");
+            }
+
+            *functions = (argument(x, 0), argument(x, 1));
+
+            Ok(None)
+        })
+    };
+
+    let promise = construct(runtime, constructor, vec![executor], constructor)?;
+    let (resolve, reject) = functions.borrow().clone();
+
+    if !is_callable(&resolve) || !is_callable(&reject) {
+        return runtime.type_error("Promise resolve or reject function is not callable");
+    }
+
+    Ok(PromiseCapability { promise, resolve, reject })
+}
+
+// PromiseResolve, a promise made by the same constructor is used as it is
+pub fn promise_resolve(runtime: &mut Runtime, constructor: Option<&Value>, value: Value) -> RuntimeResult<Value> {
+    if is_promise(&value) {
+        let value_constructor = get_property(runtime, &value, &PropertyKey::from("constructor"))?;
+
+        let same_constructor = match constructor {
+            Some(constructor) => same_value(&value_constructor, constructor),
+            None => is_intrinsic_promise(runtime, &value_constructor),
+        };
+
+        if same_constructor {
+            return Ok(value);
+        }
+    }
+
+    let capability = new_promise_capability(runtime, constructor)?;
+    call(runtime, &capability.resolve, Value::Undefined, vec![value])?;
+
+    Ok(capability.promise)
+}
+
+pub fn is_promise(value: &Value) -> bool {
+    matches!(value, Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Promise(_)))
+}
+
+// %Promise% is the only native function with Promise.prototype as its `prototype`, which can not be changed
+fn is_intrinsic_promise(runtime: &Runtime, value: &Value) -> bool {
+    let object = match value {
+        Value::Object(object) => object.borrow(),
+        _ => return false,
+    };
+
+    let native = matches!(object.function().map(|function| matches!(function.function_type, FunctionCallType::SystemCall(_))), Some(true));
+
+    native && matches!(
+        object.get_own_property(&PropertyKey::from("prototype")),
+        Some(Property::Data { value: Value::Object(prototype), .. }) if Rc::ptr_eq(&prototype, &runtime.intrinsics.promise_prototype)
+    )
+}
+
+// SpeciesConstructor without Symbol.species, the `constructor` of the promise or %Promise% when it has none
+fn species_constructor(runtime: &mut Runtime, promise: &Value) -> RuntimeResult<Option<Value>> {
+    match get_property(runtime, promise, &PropertyKey::from("constructor"))? {
+        Value::Undefined => Ok(None),
+        constructor @ Value::Object(_) => Ok(Some(constructor)),
+        _ => runtime.type_error("The .constructor property is not an object"),
+    }
+}
+
+// Invoke(promise, "then"), so `catch` and `finally` go through a `then` that was overridden
+fn invoke_then(runtime: &mut Runtime, promise: &Value, on_fulfilled: Value, on_rejected: Value) -> RuntimeResult<Value> {
+    let then = get_property(runtime, promise, &PropertyKey::from("then"))?;
+    call(runtime, &then, promise.clone(), vec![on_fulfilled, on_rejected])
+}
+
+fn this_promise(runtime: &Runtime, this: &Value, method: &str) -> RuntimeResult<ObjectRef> {
+    match this {
+        Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Promise(_)) => Ok(object.clone()),
+        _ => runtime.type_error(&format!("Method Promise.prototype.{} called on incompatible receiver {}", method, this.to_display_string())),
+    }
+}
+
+fn this_constructor(runtime: &Runtime, this: &Value) -> RuntimeResult<()> {
+    match this {
+        Value::Object(_) => Ok(()),
+        _ => runtime.type_error(&format!("PromiseResolve called on non-object {}", this.to_display_string())),
+    }
+}
+
+// Promise.all, allSettled, any and race. Errors while iterating reject the returned promise instead of throwing.
+fn promise_combinator(runtime: &mut Runtime, constructor: &Value, iterable: &Value, combinator: Combinator) -> RuntimeResult<Value> {
+    let capability = new_promise_capability(runtime, Some(constructor))?;
+
+    let state = Rc::new(CombinatorState {
+        combinator,
+        capability: capability.clone(),
+        values: RefCell::new(Vec::new()),
+        remaining: Cell::new(1),
+    });
+
+    if let Err(error) = perform_combinator(runtime, constructor, iterable, &state) {
+        call(runtime, &capability.reject, Value::Undefined, vec![error])?;
+    }
+
+    Ok(capability.promise)
+}
+
+fn perform_combinator(runtime: &mut Runtime, constructor: &Value, iterable: &Value, state: &Rc<CombinatorState>) -> RuntimeResult<()> {
+    let promise_resolve = get_property(runtime, constructor, &PropertyKey::from("resolve"))?;
+
+    if !is_callable(&promise_resolve) {
+        return runtime.type_error("Promise resolve or reject function is not callable");
+    }
+
+    let record = get_iterator(runtime, iterable)?;
+    let mut index = 0;
+
+    while let Some(value) = iterator_step_value(runtime, &record)? {
+        if let Err(error) = combine_element(runtime, constructor, &promise_resolve, value, state, index) {
+            return iterator_close(runtime, &record, Err(error));
+        }
+
+        index += 1;
+    }
+
+    finish_element(runtime, state)
+}
+
+// Resolves one value of the iterable and adds the functions that record its outcome
+fn combine_element(runtime: &mut Runtime, constructor: &Value, promise_resolve: &Value, value: Value, state: &Rc<CombinatorState>, index: usize) -> RuntimeResult<()> {
+    let next_promise = call(runtime, promise_resolve, constructor.clone(), vec![value])?;
+    let capability = &state.capability;
+
+    if !matches!(state.combinator, Combinator::Race) {
+        state.values.borrow_mut().push(Value::Undefined);
+        state.remaining.set(state.remaining.get() + 1);
+    }
+
+    // allSettled records both outcomes, but only the first of the two functions to be called counts
+    let already_called = Rc::new(Cell::new(false));
+
+    let (on_fulfilled, on_rejected) = match state.combinator {
+        Combinator::All => (element_function(runtime, state, index, ReactionKind::Fulfill, &already_called), capability.reject.clone()),
+        Combinator::AllSettled => (
+            element_function(runtime, state, index, ReactionKind::Fulfill, &already_called),
+            element_function(runtime, state, index, ReactionKind::Reject, &already_called),
+        ),
+        Combinator::Any => (capability.resolve.clone(), element_function(runtime, state, index, ReactionKind::Reject, &already_called)),
+        Combinator::Race => (capability.resolve.clone(), capability.reject.clone()),
+    };
+
+    invoke_then(runtime, &next_promise, on_fulfilled, on_rejected)?;
+
+    Ok(())
+}
+
+// The function that stores the outcome of the promise at `index`, the last one to be called settles the result
+fn element_function(runtime: &Runtime, state: &Rc<CombinatorState>, index: usize, kind: ReactionKind, already_called: &Rc<Cell<bool>>) -> Value {
+    let (state, already_called) = (state.clone(), already_called.clone());
+
+    native_function(&runtime.intrinsics, "", &["x"], move |runtime, _, x| {
+        if already_called.replace(true) {
+            return Ok(Some(Value::Undefined));
+        }
+
+        let value = match (state.combinator, kind) {
+            (Combinator::AllSettled, kind) => {
+                let result = Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()));
+
+                let (status, key) = match kind {
+                    ReactionKind::Fulfill => ("fulfilled", "value"),
+                    ReactionKind::Reject => ("rejected", "reason"),
+                };
+
+                result.borrow_mut().create_data_property(PropertyKey::from("status"), Value::string(status));
+                result.borrow_mut().create_data_property(PropertyKey::from(key), argument(x, 0));
+
+                Value::Object(result)
+            },
+            _ => argument(x, 0),
+        };
+
+        state.values.borrow_mut()[index] = value;
+        finish_element(runtime, &state)?;
+
+        Ok(Some(Value::Undefined))
+    })
+}
+
+fn finish_element(runtime: &mut Runtime, state: &CombinatorState) -> RuntimeResult<()> {
+    state.remaining.set(state.remaining.get() - 1);
+
+    if state.remaining.get() > 0 {
+        return Ok(());
+    }
+
+    let values = state.values.borrow().clone();
+
+    match state.combinator {
+        Combinator::All | Combinator::AllSettled => {
+            let values = create_array_from_list(runtime, values);
+            call(runtime, &state.capability.resolve, Value::Undefined, vec![values])?;
+        },
+        Combinator::Any => {
+            let error = create_error(runtime, ErrorKind::AggregateError, "All promises were rejected");
+
+            if let Value::Object(object) = &error {
+                let errors = create_array_from_list(runtime, values);
+                object.borrow_mut().define_builtin("errors", errors);
+            }
+
+            call(runtime, &state.capability.reject, Value::Undefined, vec![error])?;
+        },
+        Combinator::Race => {},
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::runtime::{intrinsics::Intrinsics, object::ObjectKind, value::Value};
+
+use super::native_function;
+
+// Regular expression literals evaluate to objects, but there is no matching engine yet
+pub fn register(intrinsics: &Intrinsics) {
+    let to_string = native_function(intrinsics, "toString", &[], |runtime, this, _| {
+        let source = match this {
+            Value::Object(object) => match &object.borrow().kind {
+                ObjectKind::RegExp { pattern, flags } => Some(format!("/{}/{}", pattern, flags)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match source {
+            Some(source) => Ok(Some(Value::string(&source))),
+            None => runtime.type_error("RegExp.prototype.toString requires that 'this' be a RegExp"),
+        }
+    });
+
+    intrinsics.regexp_prototype.borrow_mut().define_builtin("toString", to_string);
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::RuntimeResult, object::{Object, ObjectKind, ObjectRef, PropertyDescriptor, PropertyKey}, operations::{call, get_iterator, get_property, is_callable, iterator_close, iterator_step_value}, value::Value, Runtime};
+
+use super::{argument, iterator::{create_set_iterator, IterationKind}, map::MapData, native_constructor, native_function};
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    // new Set(iterable), the values are added through `add`
+    let set = native_constructor(intrinsics, "Set", &[], &intrinsics.set_prototype, |runtime, this, x| {
+        if !matches!(this, Value::Object(_)) {
+            return runtime.type_error("Constructor Set requires 'new'");
+        }
+
+        let set = Value::Object(Object::new(ObjectKind::Set(MapData::default()), Some(runtime.intrinsics.set_prototype.clone())));
+
+        let iterable = argument(x, 0);
+
+        if matches!(iterable, Value::Undefined | Value::Null) {
+            return Ok(Some(set));
+        }
+
+        let adder = get_property(runtime, &set, &PropertyKey::from("add"))?;
+
+        if !is_callable(&adder) {
+            return runtime.type_error(&format!("'{}' returned for property 'add' of object '#<Set>' is not a function", adder.to_display_string()));
+        }
+
+        let record = get_iterator(runtime, &iterable)?;
+
+        while let Some(value) = iterator_step_value(runtime, &record)? {
+            if let Err(error) = call(runtime, &adder, set.clone(), vec![value]) {
+                return iterator_close(runtime, &record, Err(error));
+            }
+        }
+
+        Ok(Some(set))
+    });
+
+    let add = native_function(intrinsics, "add", &["value"], |runtime, this, x| {
+        let set = this_set(runtime, this, "add")?;
+        with_set(&set, |data| data.set(argument(x, 0), argument(x, 0)));
+
+        Ok(Some(this.clone()))
+    });
+
+    let has = native_function(intrinsics, "has", &["value"], |runtime, this, x| {
+        let set = this_set(runtime, this, "has")?;
+
+        Ok(Some(Value::Boolean(with_set(&set, |data| data.has(&argument(x, 0))))))
+    });
+
+    let delete = native_function(intrinsics, "delete", &["value"], |runtime, this, x| {
+        let set = this_set(runtime, this, "delete")?;
+
+        Ok(Some(Value::Boolean(with_set(&set, |data| data.delete(&argument(x, 0))))))
+    });
+
+    let clear = native_function(intrinsics, "clear", &[], |runtime, this, _| {
+        let set = this_set(runtime, this, "clear")?;
+        with_set(&set, |data| data.clear());
+
+        Ok(Some(Value::Undefined))
+    });
+
+    // The callback gets the value twice, to match the signature of Map.prototype.forEach
+    let for_each = native_function(intrinsics, "forEach", &["callbackfn"], |runtime, this, x| {
+        let set = this_set(runtime, this, "forEach")?;
+        let callback = argument(x, 0);
+
+        if !is_callable(&callback) {
+            return runtime.type_error(&format!("{} is not a function", callback.to_display_string()));
+        }
+
+        let mut index = 0;
+
+        while let Some((position, value, _)) = with_set(&set, |data| data.entry(index)) {
+            call(runtime, &callback, argument(x, 1), vec![value.clone(), value, this.clone()])?;
+            index = position + 1;
+        }
+
+        Ok(Some(Value::Undefined))
+    });
+
+    let values = native_function(intrinsics, "values", &[], |runtime, this, _| {
+        let set = this_set(runtime, this, "values")?;
+        Ok(Some(create_set_iterator(runtime, set, IterationKind::Values)))
+    });
+
+    let entries = native_function(intrinsics, "entries", &[], |runtime, this, _| {
+        let set = this_set(runtime, this, "entries")?;
+        Ok(Some(create_set_iterator(runtime, set, IterationKind::Entries)))
+    });
+
+    let size = native_function(intrinsics, "get size", &[], |runtime, this, _| {
+        let set = this_set(runtime, this, "size")?;
+        Ok(Some(Value::Number(with_set(&set, |data| data.size()) as f64)))
+    });
+
+    let mut prototype = intrinsics.set_prototype.borrow_mut();
+
+    prototype.define_builtin("add", add);
+    prototype.define_builtin("has", has);
+    prototype.define_builtin("delete", delete);
+    prototype.define_builtin("clear", clear);
+    prototype.define_builtin("forEach", for_each);
+    prototype.define_builtin("entries", entries);
+    // `keys` is the very same function as `values`
+    prototype.define_builtin("values", values.clone());
+    prototype.define_builtin("keys", values.clone());
+    prototype.define_builtin(&intrinsics.iterator_symbol, values);
+    prototype.define_own_property(PropertyKey::from("size"), PropertyDescriptor {
+        get: Some(size),
+        enumerable: Some(false),
+        configurable: Some(true),
+        ..Default::default()
+    });
+
+    variables.insert("Set".to_string(), set);
+}
+
+fn this_set(runtime: &Runtime, this: &Value, method: &str) -> RuntimeResult<ObjectRef> {
+    match this {
+        Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Set(_)) => Ok(object.clone()),
+        _ => runtime.type_error(&format!("Method Set.prototype.{} called on incompatible receiver {}", method, this.to_display_string())),
+    }
+}
+
+fn with_set<T>(set: &ObjectRef, f: impl FnOnce(&mut MapData) -> T) -> T {
+    match &mut set.borrow_mut().kind {
+        ObjectKind::Set(data) => f(data),
+        _ => unreachable!(),
+    }
+}
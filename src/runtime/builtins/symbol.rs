@@ -0,0 +1,38 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::runtime::{intrinsics::Intrinsics, object::{PropertyDescriptor, PropertyKey}, operations::to_string, value::{Symbol, Value}};
+
+use super::{argument, native_function};
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    let symbol = native_function(intrinsics, "Symbol", &["description"], |runtime, _, x| {
+        let description = match &argument(x, 0) {
+            Value::Undefined => None,
+            value => Some(to_string(runtime, value)?),
+        };
+
+        Ok(Some(Value::Symbol(Rc::new(Symbol { description }))))
+    });
+
+    // The well-known symbols are constants on the Symbol function
+    if let Value::Object(object) = &symbol {
+        let well_known = [
+            ("hasInstance", &intrinsics.has_instance_symbol),
+            ("iterator", &intrinsics.iterator_symbol),
+            ("asyncIterator", &intrinsics.async_iterator_symbol),
+            ("toPrimitive", &intrinsics.to_primitive_symbol),
+        ];
+
+        for (name, value) in well_known {
+            object.borrow_mut().define_own_property(PropertyKey::from(name), PropertyDescriptor {
+                value: Some(Value::Symbol(value.clone())),
+                writable: Some(false),
+                enumerable: Some(false),
+                configurable: Some(false),
+                ..Default::default()
+            });
+        }
+    }
+
+    variables.insert("Symbol".to_string(), symbol);
+}
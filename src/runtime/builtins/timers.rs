@@ -0,0 +1,75 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::runtime::{intrinsics::Intrinsics, nodes::RuntimeResult, operations::{call, is_callable, to_number}, value::Value, Runtime};
+
+use super::{argument, native_function};
+
+pub fn register(variables: &mut HashMap<String, Value>, intrinsics: &Intrinsics) {
+    let set_timeout = native_function(intrinsics, "setTimeout", &["callback", "delay"], |runtime, _, x| {
+        Ok(Some(add_timer(runtime, x, false)?))
+    });
+
+    let set_interval = native_function(intrinsics, "setInterval", &["callback", "delay"], |runtime, _, x| {
+        Ok(Some(add_timer(runtime, x, true)?))
+    });
+
+    let clear_timeout = native_function(intrinsics, "clearTimeout", &["id"], |runtime, _, x| {
+        clear_timer(runtime, &argument(x, 0))?;
+        Ok(None)
+    });
+
+    let clear_interval = native_function(intrinsics, "clearInterval", &["id"], |runtime, _, x| {
+        clear_timer(runtime, &argument(x, 0))?;
+        Ok(None)
+    });
+
+    // The callback runs as a job, after the current script or task like the reactions of a promise
+    let queue_microtask = native_function(intrinsics, "queueMicrotask", &["callback"], |runtime, _, x| {
+        let callback = callback_argument(runtime, x)?;
+
+        runtime.event_loop.enqueue(move |runtime| {
+            call(runtime, &callback, Value::Undefined, Vec::new()).map(|_| ())
+        });
+
+        Ok(None)
+    });
+
+    variables.insert("setTimeout".to_string(), set_timeout);
+    variables.insert("setInterval".to_string(), set_interval);
+    variables.insert("clearTimeout".to_string(), clear_timeout);
+    variables.insert("clearInterval".to_string(), clear_interval);
+    variables.insert("queueMicrotask".to_string(), queue_microtask);
+}
+
+// The arguments after the delay are passed on to the callback.
+// A delay that is not a number, is negative or does not fit in 32 bits runs the callback as soon as possible.
+fn add_timer(runtime: &mut Runtime, arguments: &[Value], repeat: bool) -> RuntimeResult<Value> {
+    let callback = callback_argument(runtime, arguments)?;
+    let delay = to_number(runtime, &argument(arguments, 1))?;
+
+    let delay = match delay {
+        delay if (0.0..=i32::MAX as f64).contains(&delay) => Duration::from_millis(delay as u64),
+        _ => Duration::ZERO,
+    };
+
+    let id = runtime.event_loop.add_timer(callback, arguments.get(2..).unwrap_or_default().to_vec(), delay, repeat);
+
+    Ok(Value::Number(id as f64))
+}
+
+fn clear_timer(runtime: &mut Runtime, id: &Value) -> RuntimeResult<()> {
+    let id = to_number(runtime, id)?;
+
+    if id.fract() == 0.0 && (1.0..=u32::MAX as f64).contains(&id) {
+        runtime.event_loop.clear_timer(id as u32);
+    }
+
+    Ok(())
+}
+
+fn callback_argument(runtime: &Runtime, arguments: &[Value]) -> RuntimeResult<Value> {
+    match argument(arguments, 0) {
+        callback if is_callable(&callback) => Ok(callback),
+        callback => runtime.type_error(&format!("The callback must be a function, received {}", callback.to_display_string())),
+    }
+}
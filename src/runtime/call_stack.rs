@@ -0,0 +1,89 @@
+use std::rc::Rc;
+
+use super::{nodes::RuntimeResult, Runtime};
+
+// The native stack scripts run on, `main` starts the runtime on a thread of this size
+pub const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+// Scripts that recurse deeper than this get a RangeError, as in other engines
+const MAX_CALL_DEPTH: usize = 10_000;
+
+// What a call may still use of the native stack after the check, debug builds take several KiB per script call
+pub const STACK_HEADROOM: usize = 128 * 1024;
+
+// A script function that is running, a script or module body is a frame without a function name
+pub struct StackFrame {
+    pub function_name: Option<String>,
+    // Where in the script the frame currently is, the start of the statement or call it is running
+    pub position: usize,
+    // The script or module the code comes from
+    pub script: Rc<Script>,
+}
+
+// The name of a script or module and where each of its lines starts, so positions can be shown as line:column.
+// The name of a module is the key its loader resolved it to.
+pub struct Script {
+    pub name: String,
+    line_starts: Vec<usize>,
+}
+
+impl Script {
+    pub fn new(name: &str, text: &str) -> Script {
+        let line_starts = std::iter::once(0)
+            .chain(text.chars().enumerate().filter(|(_, character)| *character == '\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Script {
+            name: name.to_string(),
+            line_starts,
+        }
+    }
+
+    // Lines and columns count from 1
+    pub fn line_column(&self, position: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= position);
+
+        (line, position - self.line_starts[line - 1] + 1)
+    }
+}
+
+// The lines below the header of an error's `stack`, innermost frame first
+pub fn stack_trace(runtime: &Runtime) -> String {
+    runtime.call_stack.iter()
+        .rev()
+        .map(|frame| {
+            let (line, column) = frame.script.line_column(frame.position);
+            let location = format!("{}:{}:{}", frame.script.name, line, column);
+
+            match &frame.function_name {
+                Some(name) if !name.is_empty() => format!("\n    at {} ({})", name, location),
+                _ => format!("\n    at {}", location),
+            }
+        })
+        .collect()
+}
+
+// The address of a local is close enough to the stack pointer to tell how much native stack is left
+#[inline(never)]
+pub fn stack_pointer() -> usize {
+    let marker = 0u8;
+    std::ptr::addr_of!(marker) as usize
+}
+
+// Every call checks this before it goes deeper, the native stack can run out first when the frames are large
+pub fn check_call_depth(runtime: &Runtime) -> RuntimeResult<()> {
+    if runtime.call_stack.len() >= MAX_CALL_DEPTH {
+        return runtime.range_error("Maximum call stack size exceeded");
+    }
+
+    check_stack(runtime)
+}
+
+// Nested expressions go deeper on the native stack without a call
+pub fn check_stack(runtime: &Runtime) -> RuntimeResult<()> {
+    if stack_pointer() < runtime.stack_limit {
+        return runtime.range_error("Maximum call stack size exceeded");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use std::{cell::Cell, rc::Rc};
+
+use corosensei::{stack::{DefaultStack, Stack}, CoroutineResult, Yielder};
+
+use super::{call_stack::{STACK_HEADROOM, STACK_SIZE}, nodes::RuntimeResult, Runtime, Value};
+
+// How many stacks of finished coroutines are kept for the next ones
+const POOLED_STACKS: usize = 16;
+
+// What Linux allows when `vm.max_map_count` cannot be read
+const DEFAULT_MAX_MAP_COUNT: usize = 65530;
+
+// What a suspended function is resumed with, like the argument of `next`, `throw` or `return` on a generator
+#[derive(Clone)]
+pub enum ResumeAction {
+    Next(Value),
+    Throw(Value),
+    Return(Value),
+}
+
+// Why a function stopped, async generators do both
+pub enum Suspension {
+    // The value a generator hands out
+    Yield(Value),
+    // The promise an async function waits for
+    Await(Value),
+}
+
+pub enum CoroutineState {
+    Suspended(Suspension),
+    Completed(RuntimeResult<Value>),
+}
+
+// The runtime is lent to the coroutine for as long as it runs, and handed back when it suspends
+struct Resume {
+    runtime: *mut Runtime,
+    action: ResumeAction,
+}
+
+struct Suspend {
+    runtime: *mut Runtime,
+    suspension: Suspension,
+}
+
+type Body = Box<dyn FnOnce(&mut Runtime) -> RuntimeResult<Value>>;
+
+// A script function body running on a stack of its own, so a `yield` deep inside the evaluator
+// can stop it and a later resume continues from the same place
+pub struct Coroutine {
+    state: State,
+}
+
+// A coroutine only holds a stack from its first resume until its body is done
+enum State {
+    NotStarted(Body),
+    Started {
+        coroutine: corosensei::Coroutine<Resume, Suspend, RuntimeResult<Value>, DefaultStack>,
+        // The `stack_limit` of the runtime while the body runs on this stack
+        stack_limit: usize,
+        // The count of the pool the stack came from, which goes down when a suspended coroutine is dropped
+        live_stacks: Rc<Cell<usize>>,
+    },
+    Finished,
+}
+
+// How a suspending function finds the coroutine it runs in, the innermost is last in `Runtime::coroutines`
+pub struct CoroutineYielder(*const Yielder<Resume, Suspend>);
+
+// The stacks of finished coroutines, reused so that each call of an async function does not map a new one.
+// Every suspended generator and async function holds a stack, the pool maps no more than `limit` at a time.
+pub struct StackPool {
+    stacks: Vec<DefaultStack>,
+    // The stacks that are mapped, pooled or held by a coroutine
+    live_stacks: Rc<Cell<usize>>,
+    limit: usize,
+}
+
+impl Coroutine {
+    // The body runs on the first resume, whatever that resume passes is ignored
+    pub fn new(body: impl FnOnce(&mut Runtime) -> RuntimeResult<Value> + 'static) -> Coroutine {
+        Coroutine { state: State::NotStarted(Box::new(body)) }
+    }
+
+    // A coroutine that could not get a stack completes with a RangeError instead of running its body
+    pub fn resume(&mut self, runtime: &mut Runtime, action: ResumeAction) -> CoroutineState {
+        if let State::NotStarted(_) = self.state {
+            let body = match std::mem::replace(&mut self.state, State::Finished) {
+                State::NotStarted(body) => body,
+                _ => unreachable!(),
+            };
+
+            match runtime.coroutine_stacks.take() {
+                Some(stack) => self.state = start(stack, body, runtime.coroutine_stacks.live_stacks.clone()),
+                None => return CoroutineState::Completed(runtime.range_error("Out of memory for the stack of a generator or async function")),
+            }
+        }
+
+        let (coroutine, stack_limit) = match &mut self.state {
+            State::Started { coroutine, stack_limit, .. } => (coroutine, *stack_limit),
+            _ => panic!("A finished coroutine cannot be resumed"),
+        };
+
+        let caller_stack_limit = std::mem::replace(&mut runtime.stack_limit, stack_limit);
+        let result = coroutine.resume(Resume { runtime, action });
+        runtime.stack_limit = caller_stack_limit;
+
+        match result {
+            CoroutineResult::Yield(suspend) => {
+                debug_assert!(std::ptr::eq(suspend.runtime, runtime));
+                CoroutineState::Suspended(suspend.suspension)
+            },
+            CoroutineResult::Return(result) => {
+                if let State::Started { coroutine, .. } = std::mem::replace(&mut self.state, State::Finished) {
+                    runtime.coroutine_stacks.give_back(coroutine.into_stack());
+                }
+
+                CoroutineState::Completed(result)
+            },
+        }
+    }
+}
+
+fn start(stack: DefaultStack, body: Body, live_stacks: Rc<Cell<usize>>) -> State {
+    let stack_limit = stack.limit().get() + STACK_HEADROOM;
+
+    let coroutine = corosensei::Coroutine::with_stack(stack, move |yielder: &Yielder<Resume, Suspend>, resume: Resume| {
+        // SAFETY: `resume` passes the runtime it was given and blocks until the coroutine suspends or returns,
+        // so the runtime is only used from here while nothing else uses it
+        let runtime = unsafe { &mut *resume.runtime };
+
+        runtime.coroutines.push(CoroutineYielder(yielder));
+        let result = body(runtime);
+        runtime.coroutines.pop();
+
+        result
+    });
+
+    State::Started { coroutine, stack_limit, live_stacks }
+}
+
+impl StackPool {
+    // Past the limit a new generator or async function throws a RangeError, instead of the process running out of mappings
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    fn take(&mut self) -> Option<DefaultStack> {
+        if let Some(stack) = self.stacks.pop() {
+            return Some(stack);
+        }
+
+        if self.live_stacks.get() >= self.limit {
+            return None;
+        }
+
+        // As large as the stack scripts start on so bodies recurse as deep, pages are only committed once touched
+        let stack = DefaultStack::new(STACK_SIZE).ok()?;
+        self.live_stacks.set(self.live_stacks.get() + 1);
+
+        Some(stack)
+    }
+
+    fn give_back(&mut self, stack: DefaultStack) {
+        match self.stacks.len() < POOLED_STACKS {
+            true => self.stacks.push(stack),
+            false => self.live_stacks.set(self.live_stacks.get() - 1),
+        }
+    }
+}
+
+// Linux allows `vm.max_map_count` memory mappings per process and a stack with its guard page takes two.
+// Three eighths of them go to stacks, the rest are left to `malloc` and everything else the process maps.
+impl Default for StackPool {
+    fn default() -> StackPool {
+        let max_map_count = std::fs::read_to_string("/proc/sys/vm/max_map_count").ok()
+            .and_then(|count| count.trim().parse().ok())
+            .unwrap_or(DEFAULT_MAX_MAP_COUNT);
+
+        StackPool {
+            stacks: Vec::new(),
+            live_stacks: Rc::new(Cell::new(0)),
+            limit: max_map_count * 3 / 8,
+        }
+    }
+}
+
+// A generator that is dropped while suspended unmaps its stack along with it
+impl Drop for Coroutine {
+    fn drop(&mut self) {
+        if let State::Started { live_stacks, .. } = &self.state {
+            live_stacks.set(live_stacks.get() - 1);
+        }
+    }
+}
+
+// Stops the innermost coroutine until it is resumed, handing `suspension` to whoever resumed it.
+// The frame and environment of the suspended function are put back when it continues.
+pub fn suspend(runtime: &mut Runtime, suspension: Suspension) -> ResumeAction {
+    let yielder = runtime.coroutines.pop().expect("Only a running coroutine can suspend");
+    let frame = runtime.call_stack.pop();
+    let environment = runtime.environment.clone();
+
+    // SAFETY: the yielder lives on the stack of the coroutine that is running this code
+    let resume = unsafe { &*yielder.0 }.suspend(Suspend { runtime, suspension });
+    debug_assert!(std::ptr::eq(resume.runtime, runtime));
+
+    runtime.enter_environment(environment);
+    runtime.call_stack.extend(frame);
+    runtime.coroutines.push(yielder);
+
+    resume.action
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::{run_test_script, run_test_script_with};
+
+    use super::*;
+
+    fn global(runtime: &Runtime, name: &str) -> String {
+        runtime.variable(name).ok().flatten().unwrap_or_else(|| panic!("{} is not defined", name)).to_display_string()
+    }
+
+    #[test]
+    fn generators_and_async_functions_recurse_as_deep_as_scripts() {
+        let script = "
+            function deep(n) { if (n === 0) { return 0; } return deep(n - 1) + 1; }
+            function tooDeep(f) { try { f(); } catch (error) { return error.name; } }
+
+            var sync = deep(5000);
+            var syncLimit = tooDeep(function () { deep(20000); });
+
+            function* generator() { yield deep(5000); yield tooDeep(function () { deep(20000); }); }
+            var iterator = generator();
+            var fromGenerator = iterator.next().value;
+            var generatorLimit = iterator.next().value;
+
+            var fromAsync;
+            var asyncLimit;
+            async function awaiting() { await null; fromAsync = deep(5000); asyncLimit = tooDeep(function () { deep(20000); }); }
+            awaiting();
+        ";
+
+        run_test_script(script, |runtime| {
+            for name in ["sync", "fromGenerator", "fromAsync"] {
+                assert_eq!(global(runtime, name), "5000", "{}", name);
+            }
+
+            for name in ["syncLimit", "generatorLimit", "asyncLimit"] {
+                assert_eq!(global(runtime, name), "RangeError", "{}", name);
+            }
+        });
+    }
+
+    #[test]
+    fn suspended_functions_past_the_stack_limit_throw_until_stacks_are_freed() {
+        let script = "
+            var results = [];
+            async function waiting(value) { await null; return value; }
+            function all(count) {
+                var promises = [];
+                while (promises.length < count) { promises.push(waiting(promises.length)); }
+                return Promise.all(promises);
+            }
+
+            all(100)
+                .then(function (values) { results.push(values.length); return all(101); })
+                .catch(function (error) { results.push(error.name + ': ' + error.message); })
+                .then(function () { return all(100); })
+                .then(function (values) { results.push(values.length); });
+        ";
+
+        run_test_script_with(script, |runtime| runtime.coroutine_stacks.set_limit(100), |runtime, outcome| {
+            assert!(outcome.is_ok());
+            assert_eq!(global(runtime, "results"), "[ 100, 'RangeError: Out of memory for the stack of a generator or async function', 100 ]");
+            assert_eq!(runtime.coroutine_stacks.live_stacks.get(), POOLED_STACKS);
+        });
+    }
+}
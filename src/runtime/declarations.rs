@@ -0,0 +1,240 @@
+use crate::ast::nodes::{AstNode, BlockStatement, ExportDefaultDeclaration, ExportDefaultKind, ExportNamedDeclaration, Expression, FunctionDeclaration, TryStatement, VariableKind};
+
+use super::{Runtime, Value, environment::{is_strict, variable_environment}, parsers::{parse_function_declaration::{create_function, FunctionKind}, pattern::bound_names}};
+
+// Declaration instantiation for a script or function body, run before its first statement.
+// Functions are created with their bodies, `var` bindings start out as undefined and `let` and `const` uninitialized.
+pub fn instantiate_function_declarations(runtime: &mut Runtime, body: &[AstNode], parameter_names: &[String]) {
+    let environment = variable_environment(&runtime.environment);
+
+    instantiate_lexical_declarations(runtime, body);
+
+    let mut var_names = Vec::new();
+    collect_var_names(body, &mut var_names);
+
+    for name in var_names {
+        if !environment.borrow().has_own_binding(&name) {
+            environment.borrow_mut().declare(&name, Value::undefined());
+        }
+    }
+
+    // In sloppy code block level functions also get a `var` binding, unless a `var` of that name could not be declared where
+    // the function is, because a parameter or a `let`, `const` or `class` around it already has the name (Annex B.3.3)
+    let mut block_functions = Vec::new();
+
+    if !is_strict(&environment) {
+        let mut lexical_names = parameter_names.to_vec();
+        lexical_declaration_names(body, &mut lexical_names);
+
+        collect_block_functions(body, false, &mut lexical_names, &mut block_functions);
+    }
+
+    for declaration in block_functions {
+        let mut environment = environment.borrow_mut();
+
+        if !environment.has_own_binding(&declaration.id.name) {
+            environment.declare(&declaration.id.name, Value::undefined());
+        }
+
+        environment.block_functions.insert(declaration.range.0);
+    }
+
+    // Later declarations of the same function win
+    for statement in body {
+        if let AstNode::FunctionDeclaration(declaration) = exported_declaration(statement) {
+            let function = create_function(runtime, &declaration.id.name, &declaration.params, &declaration.body, FunctionKind::Normal, declaration.generator, declaration.is_async);
+            environment.borrow_mut().declare(&declaration.id.name, function);
+        }
+    }
+}
+
+// A directive prologue is the run of string literal statements at the start of a body.
+// The raw text is compared, so an escaped "use strict" does not count.
+pub fn has_use_strict_directive(body: &[AstNode]) -> bool {
+    body.iter()
+        .map_while(|statement| match statement {
+            AstNode::ExpressionStatement(statement) => match &statement.expression {
+                Expression::StringLiteral(literal) => Some(literal),
+                _ => None,
+            },
+            _ => None,
+        })
+        .any(|literal| literal.raw == "\"use strict\"" || literal.raw == "'use strict'")
+}
+
+// Functions declared directly in a block are created when the block is entered
+pub fn instantiate_block_declarations(runtime: &mut Runtime, body: &[AstNode]) {
+    instantiate_lexical_declarations(runtime, body);
+
+    for statement in body {
+        if let AstNode::FunctionDeclaration(declaration) = statement {
+            let function = create_function(runtime, &declaration.id.name, &declaration.params, &declaration.body, FunctionKind::Normal, declaration.generator, declaration.is_async);
+            runtime.declare_variable(&declaration.id.name, function);
+        }
+    }
+}
+
+pub fn has_block_declarations(body: &[AstNode]) -> bool {
+    body.iter().any(|statement| match statement {
+        AstNode::FunctionDeclaration(_) | AstNode::ClassDeclaration(_) => true,
+        AstNode::VariableDeclaration(declaration) => declaration.kind != VariableKind::Var,
+        _ => false,
+    })
+}
+
+// `let` and `const` bindings exist from the start of their scope, using them before the declaration runs is an error
+fn instantiate_lexical_declarations(runtime: &mut Runtime, body: &[AstNode]) {
+    for statement in body {
+        // `export default` with an expression binds it to `default`
+        if let AstNode::ExportDefaultDeclaration(ExportDefaultDeclaration { declaration: ExportDefaultKind::Expression(_), .. }) = statement {
+            runtime.environment.borrow_mut().declare_uninitialized("default", true);
+        }
+
+        match exported_declaration(statement) {
+            AstNode::VariableDeclaration(declaration) if declaration.kind != VariableKind::Var => {
+                let mut names = Vec::new();
+
+                for declarator in declaration.declarations.iter() {
+                    bound_names(&declarator.id, &mut names);
+                }
+
+                for name in names {
+                    runtime.environment.borrow_mut().declare_uninitialized(&name, declaration.kind == VariableKind::Const);
+                }
+            },
+            // A class can be referred to before its declaration runs, but not used
+            AstNode::ClassDeclaration(declaration) => {
+                runtime.environment.borrow_mut().declare_uninitialized(&declaration.id.name, false);
+            },
+            _ => {}
+        }
+    }
+}
+
+// All `var` names in a body, without descending into nested functions
+fn collect_var_names(body: &[AstNode], names: &mut Vec<String>) {
+    for statement in body {
+        match exported_declaration(statement) {
+            AstNode::VariableDeclaration(declaration) if declaration.kind == VariableKind::Var => {
+                for declarator in declaration.declarations.iter() {
+                    bound_names(&declarator.id, names);
+                }
+            },
+            AstNode::BlockStatement(block) => collect_var_names(&block.body, names),
+            AstNode::IfStatement(statement) => {
+                collect_var_names(std::slice::from_ref(&statement.consequent), names);
+
+                if let Some(alternate) = &statement.alternate {
+                    collect_var_names(std::slice::from_ref(alternate), names);
+                }
+            },
+            AstNode::WhileStatement(statement) => collect_var_names(std::slice::from_ref(&statement.body), names),
+            AstNode::ForOfStatement(statement) => {
+                if statement.kind == Some(VariableKind::Var) {
+                    bound_names(&statement.left, names);
+                }
+
+                collect_var_names(std::slice::from_ref(&statement.body), names);
+            },
+            AstNode::TryStatement(statement) => {
+                for block in try_blocks(statement) {
+                    collect_var_names(&block.body, names);
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+// The declaration an `export` wraps, which is instantiated like any other declaration in the module body
+fn exported_declaration(statement: &AstNode) -> &AstNode {
+    match statement {
+        AstNode::ExportNamedDeclaration(ExportNamedDeclaration { declaration: Some(declaration), .. }) => declaration,
+        AstNode::ExportDefaultDeclaration(ExportDefaultDeclaration { declaration: ExportDefaultKind::Declaration(declaration), .. }) => declaration,
+        statement => statement,
+    }
+}
+
+// The functions declared inside blocks of a body that get a `var` binding, without descending into nested functions.
+// `lexical_names` holds the names such a binding would clash with, from the parameters and the scopes around the block.
+fn collect_block_functions<'a>(body: &'a [AstNode], in_block: bool, lexical_names: &mut Vec<String>, functions: &mut Vec<&'a FunctionDeclaration>) {
+    let outer_names = lexical_names.len();
+
+    if in_block {
+        let mut block_names = Vec::new();
+        lexical_declaration_names(body, &mut block_names);
+
+        for statement in body {
+            if let AstNode::FunctionDeclaration(declaration) = statement {
+                let name = &declaration.id.name;
+
+                if !lexical_names.contains(name) && !block_names.contains(name) {
+                    functions.push(declaration);
+                }
+            }
+        }
+
+        // Nested blocks also clash with the functions of this one
+        lexical_names.extend(block_names);
+        lexical_names.extend(body.iter().filter_map(|statement| match statement {
+            AstNode::FunctionDeclaration(declaration) => Some(declaration.id.name.clone()),
+            _ => None,
+        }));
+    }
+
+    for statement in body {
+        match statement {
+            AstNode::BlockStatement(block) => collect_block_functions(&block.body, true, lexical_names, functions),
+            AstNode::IfStatement(statement) => {
+                collect_block_functions(std::slice::from_ref(&statement.consequent), in_block, lexical_names, functions);
+
+                if let Some(alternate) = &statement.alternate {
+                    collect_block_functions(std::slice::from_ref(alternate), in_block, lexical_names, functions);
+                }
+            },
+            AstNode::WhileStatement(statement) => {
+                collect_block_functions(std::slice::from_ref(&statement.body), in_block, lexical_names, functions)
+            },
+            AstNode::ForOfStatement(statement) => {
+                let loop_names = lexical_names.len();
+
+                if matches!(statement.kind, Some(VariableKind::Let | VariableKind::Const)) {
+                    bound_names(&statement.left, lexical_names);
+                }
+
+                collect_block_functions(std::slice::from_ref(&statement.body), in_block, lexical_names, functions);
+                lexical_names.truncate(loop_names);
+            },
+            AstNode::TryStatement(statement) => {
+                for block in try_blocks(statement) {
+                    collect_block_functions(&block.body, true, lexical_names, functions);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    lexical_names.truncate(outer_names);
+}
+
+// The `let`, `const` and `class` names declared directly in a body
+fn lexical_declaration_names(body: &[AstNode], names: &mut Vec<String>) {
+    for statement in body {
+        match exported_declaration(statement) {
+            AstNode::VariableDeclaration(declaration) if declaration.kind != VariableKind::Var => {
+                for declarator in declaration.declarations.iter() {
+                    bound_names(&declarator.id, names);
+                }
+            },
+            AstNode::ClassDeclaration(declaration) => names.push(declaration.id.name.clone()),
+            _ => {}
+        }
+    }
+}
+
+// The try block, the catch block and the finally block, whichever are present
+fn try_blocks(statement: &TryStatement) -> impl Iterator<Item = &BlockStatement> {
+    std::iter::once(&statement.block)
+        .chain(statement.handler.as_ref().map(|handler| &handler.body))
+        .chain(statement.finalizer.as_ref())
+}
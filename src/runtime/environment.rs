@@ -0,0 +1,394 @@
+use std::{cell::RefCell, collections::{HashMap, HashSet}, rc::Rc};
+
+use super::{builtins::register_builtins, intrinsics::Intrinsics, module::ModuleRef, nodes::{RuntimeCall, RuntimeResult}, object::{find_property, ObjectRef, Property, PropertyDescriptor, PropertyKey}, value::Value, Runtime};
+
+pub type EnvironmentRef = Rc<RefCell<Environment>>;
+
+// An environment record, functions keep a reference to the one they were declared in
+pub struct Environment {
+    pub variables: HashMap<String, Value>,
+    pub parent: Option<EnvironmentRef>,
+    // Function and script environments hold the `var` bindings, blocks only hold their own declarations
+    pub function_scope: bool,
+    // Where the block level functions that also got a `var` binding in this function scope are declared (Annex B.3.3)
+    pub block_functions: HashSet<usize>,
+    // `let`, `const` and class bindings before their declaration runs, reading them is an error
+    pub uninitialized: HashSet<String>,
+    // `const` bindings, which can never be assigned
+    pub constants: HashSet<String>,
+    // The `this` of a function call, blocks and arrow functions look it up in their parents
+    pub this_binding: ThisBinding,
+    // Whether the code running in this function or script is strict mode code
+    pub strict: bool,
+    // The script environment keeps its `var` and function bindings as properties of the global object
+    pub binding_object: Option<ObjectRef>,
+    // Where `super.name` starts looking, the object the running method was defined on
+    pub home_object: Option<ObjectRef>,
+    // The constructor `new` was applied to and the function it ended up running, `super()` needs both
+    pub new_target: Value,
+    pub function_object: Option<ObjectRef>,
+    // The imports of a module, each a live view of a binding in the module that exports it
+    pub imports: HashMap<String, (ModuleRef, String)>,
+}
+
+// What looking up a name found, bindings in their temporal dead zone have no value yet
+pub enum Binding {
+    Initialized(Value),
+    Uninitialized,
+}
+
+// How assigning to a name went, so the caller can report the failure the way the code's mode asks for
+pub enum Assignment {
+    Assigned,
+    ReadOnly,
+    Constant,
+    Uninitialized,
+    Unresolved,
+}
+
+pub enum ThisBinding {
+    // Blocks, class scopes and the functions that do not bind `this`
+    Lexical,
+    // A derived class constructor before `super()` returns
+    Uninitialized,
+    Initialized(Value),
+}
+
+impl Environment {
+    pub fn new(parent: &EnvironmentRef) -> EnvironmentRef {
+        Rc::new(RefCell::new(Environment {
+            variables: HashMap::new(),
+            parent: Some(parent.clone()),
+            function_scope: false,
+            block_functions: HashSet::new(),
+            uninitialized: HashSet::new(),
+            constants: HashSet::new(),
+            this_binding: ThisBinding::Lexical,
+            strict: false,
+            binding_object: None,
+            home_object: None,
+            new_target: Value::Undefined,
+            function_object: None,
+            imports: HashMap::new(),
+        }))
+    }
+
+    pub fn new_function(parent: &EnvironmentRef, function: &RuntimeCall, this_binding: ThisBinding) -> EnvironmentRef {
+        Rc::new(RefCell::new(Environment {
+            variables: HashMap::new(),
+            parent: Some(parent.clone()),
+            function_scope: true,
+            block_functions: HashSet::new(),
+            uninitialized: HashSet::new(),
+            constants: HashSet::new(),
+            this_binding,
+            strict: function.strict,
+            binding_object: None,
+            home_object: function.home_object.clone(),
+            new_target: Value::Undefined,
+            function_object: None,
+            imports: HashMap::new(),
+        }))
+    }
+
+    // The scope of a class body, it holds the class binding and the private names and is always strict
+    pub fn new_class(parent: &EnvironmentRef) -> EnvironmentRef {
+        let environment = Environment::new(parent);
+
+        {
+            let mut class_environment = environment.borrow_mut();
+            class_environment.function_scope = true;
+            class_environment.strict = true;
+        }
+
+        environment
+    }
+
+    // The scope of a module body, always strict and with an undefined `this`
+    pub fn new_module(parent: &EnvironmentRef) -> EnvironmentRef {
+        let environment = Environment::new(parent);
+
+        {
+            let mut module_environment = environment.borrow_mut();
+            module_environment.function_scope = true;
+            module_environment.strict = true;
+            module_environment.this_binding = ThisBinding::Initialized(Value::Undefined);
+        }
+
+        environment
+    }
+
+    // The built-ins are writable, non-enumerable properties of the global object, except for the constant values
+    pub fn new_root(intrinsics: &Intrinsics, global_object: &ObjectRef) -> EnvironmentRef {
+        let mut variables = HashMap::new();
+
+        register_builtins(&mut variables, intrinsics);
+        variables.insert("globalThis".to_string(), Value::Object(global_object.clone()));
+
+        let mut names: Vec<&String> = variables.keys().collect();
+        names.sort();
+
+        for name in names {
+            let constant = ["NaN", "Infinity", "undefined"].contains(&name.as_str());
+
+            global_object.borrow_mut().define_own_property(PropertyKey::from(name.as_str()), PropertyDescriptor {
+                value: Some(variables[name].clone()),
+                writable: Some(!constant),
+                enumerable: Some(false),
+                configurable: Some(!constant),
+                ..Default::default()
+            });
+        }
+
+        Rc::new(RefCell::new(Environment {
+            variables: HashMap::new(),
+            parent: None,
+            function_scope: true,
+            block_functions: HashSet::new(),
+            uninitialized: HashSet::new(),
+            constants: HashSet::new(),
+            this_binding: ThisBinding::Initialized(Value::Object(global_object.clone())),
+            strict: false,
+            binding_object: Some(global_object.clone()),
+            home_object: None,
+            new_target: Value::Undefined,
+            function_object: None,
+            imports: HashMap::new(),
+        }))
+    }
+
+    // Declarations in the script environment become enumerable, non-configurable properties of the global object
+    pub fn declare(&mut self, name: &str, value: Value) {
+        match &self.binding_object {
+            Some(object) => {
+                object.borrow_mut().define_own_property(PropertyKey::from(name), PropertyDescriptor {
+                    value: Some(value),
+                    writable: Some(true),
+                    enumerable: Some(true),
+                    configurable: Some(false),
+                    ..Default::default()
+                });
+            },
+            None => {
+                self.variables.insert(name.to_string(), value);
+            },
+        }
+    }
+
+    // Classes are lexical declarations, even in the script environment they do not become global properties
+    pub fn declare_lexical(&mut self, name: &str, value: Value) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    // Creates a `let` or `const` binding that cannot be used until its declaration initializes it
+    pub fn declare_uninitialized(&mut self, name: &str, constant: bool) {
+        self.variables.insert(name.to_string(), Value::Undefined);
+        self.uninitialized.insert(name.to_string());
+
+        if constant {
+            self.constants.insert(name.to_string());
+        }
+    }
+
+    // Gives a lexical binding its first value, names without a lexical binding are declared
+    pub fn initialize(&mut self, name: &str, value: Value) {
+        match self.uninitialized.remove(name) {
+            true => self.declare_lexical(name, value),
+            false => self.declare(name, value),
+        }
+    }
+
+    pub fn has_own_binding(&self, name: &str) -> bool {
+        if self.variables.contains_key(name) || self.imports.contains_key(name) {
+            return true;
+        }
+
+        match &self.binding_object {
+            Some(object) => object.borrow().get_own_property(&PropertyKey::from(name)).is_some(),
+            None => false,
+        }
+    }
+
+    // Bindings on the global object are also found through its prototype chain, accessors read as undefined
+    pub fn get_binding(&self, name: &str) -> Option<Binding> {
+        if self.uninitialized.contains(name) {
+            return Some(Binding::Uninitialized);
+        }
+
+        // A binding the exporting module has not declared yet, or a module that has not been set up, is not initialized
+        if let Some((module, binding_name)) = self.imports.get(name) {
+            return match &module.borrow().environment {
+                Some(environment) => environment.borrow().get_binding(binding_name).or(Some(Binding::Uninitialized)),
+                None => Some(Binding::Uninitialized),
+            };
+        }
+
+        if let Some(value) = self.variables.get(name) {
+            return Some(Binding::Initialized(value.clone()));
+        }
+
+        match &self.binding_object {
+            Some(object) => match find_property(object, &PropertyKey::from(name))? {
+                Property::Data { value, .. } => Some(Binding::Initialized(value)),
+                Property::Accessor { .. } => Some(Binding::Initialized(Value::Undefined)),
+            },
+            None => None,
+        }
+    }
+
+    // Returns None when there is no binding here
+    fn set_binding(&mut self, name: &str, value: Value) -> Option<Assignment> {
+        if self.uninitialized.contains(name) {
+            return Some(Assignment::Uninitialized);
+        }
+
+        if self.constants.contains(name) || self.imports.contains_key(name) {
+            return Some(Assignment::Constant);
+        }
+
+        if let Some(variable) = self.variables.get_mut(name) {
+            *variable = value;
+            return Some(Assignment::Assigned);
+        }
+
+        match &self.binding_object {
+            Some(object) => {
+                let key = PropertyKey::from(name);
+
+                match find_property(object, &key)? {
+                    Property::Data { writable: false, .. } | Property::Accessor { .. } => Some(Assignment::ReadOnly),
+                    Property::Data { .. } => {
+                        let mut object = object.borrow_mut();
+
+                        let assigned = match object.get_own_property(&key) {
+                            Some(_) => object.define_own_property(key, PropertyDescriptor {
+                                value: Some(value),
+                                ..Default::default()
+                            }),
+                            None => object.create_data_property(key, value),
+                        };
+
+                        Some(if assigned { Assignment::Assigned } else { Assignment::ReadOnly })
+                    },
+                }
+            },
+            None => None,
+        }
+    }
+}
+
+// `delete name`, only bindings that are properties of the global object can be deleted, declared ones cannot
+pub fn delete_variable(environment: &EnvironmentRef, name: &str) -> bool {
+    let key = PropertyKey::from(name);
+    let mut current = environment.clone();
+
+    loop {
+        let parent = {
+            let environment = current.borrow();
+
+            if environment.uninitialized.contains(name) || environment.variables.contains_key(name) || environment.imports.contains_key(name) {
+                return false;
+            }
+
+            if let Some(object) = &environment.binding_object {
+                if find_property(object, &key).is_some() {
+                    return object.borrow_mut().delete(&key);
+                }
+            }
+
+            environment.parent.clone()
+        };
+
+        match parent {
+            Some(parent) => current = parent,
+            // Deleting a name that does not exist succeeds
+            None => return true,
+        }
+    }
+}
+
+pub fn lookup_variable(environment: &EnvironmentRef, name: &str) -> Option<Binding> {
+    let mut current = environment.clone();
+
+    loop {
+        if let Some(value) = current.borrow().get_binding(name) {
+            return Some(value);
+        }
+
+        let parent = current.borrow().parent.clone();
+
+        match parent {
+            Some(parent) => current = parent,
+            None => return None,
+        }
+    }
+}
+
+// The `this` of the closest environment that binds one, arrow functions do not
+pub fn this_value(runtime: &Runtime, environment: &EnvironmentRef) -> RuntimeResult<Value> {
+    match &this_environment(environment).borrow().this_binding {
+        ThisBinding::Initialized(this_value) => Ok(this_value.clone()),
+        ThisBinding::Uninitialized => {
+            runtime.reference_error("Must call super constructor in derived class before accessing 'this' or returning from derived constructor")
+        },
+        ThisBinding::Lexical => Ok(Value::Undefined),
+    }
+}
+
+// GetThisEnvironment, the script environment binds `this` so the search always ends
+pub fn this_environment(environment: &EnvironmentRef) -> EnvironmentRef {
+    let mut current = environment.clone();
+
+    loop {
+        if !matches!(current.borrow().this_binding, ThisBinding::Lexical) {
+            return current;
+        }
+
+        let parent = current.borrow().parent.clone();
+
+        match parent {
+            Some(parent) => current = parent,
+            None => return current,
+        }
+    }
+}
+
+pub fn is_strict(environment: &EnvironmentRef) -> bool {
+    variable_environment(environment).borrow().strict
+}
+
+// The closest function or script environment, where `var` bindings live
+pub fn variable_environment(environment: &EnvironmentRef) -> EnvironmentRef {
+    let mut current = environment.clone();
+
+    loop {
+        if current.borrow().function_scope {
+            return current;
+        }
+
+        let parent = current.borrow().parent.clone();
+
+        match parent {
+            Some(parent) => current = parent,
+            None => return current,
+        }
+    }
+}
+
+// Assigns to the closest environment that declares the variable
+pub fn assign_variable(environment: &EnvironmentRef, name: &str, value: Value) -> Assignment {
+    let mut current = environment.clone();
+
+    loop {
+        if let Some(assignment) = current.borrow_mut().set_binding(name, value.clone()) {
+            return assignment;
+        }
+
+        let parent = current.borrow().parent.clone();
+
+        match parent {
+            Some(parent) => current = parent,
+            None => return Assignment::Unresolved,
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::{collections::VecDeque, rc::Rc, time::{Duration, Instant}};
+
+use super::{nodes::RuntimeResult, object::ObjectRef, value::Value, Runtime};
+
+// Work that runs after the script and the jobs queued before it, like the reactions of a settled promise
+pub type Job = Box<dyn FnOnce(&mut Runtime) -> RuntimeResult<()>>;
+
+// Where the event loop gets the time from. Time is measured from the start of the script.
+pub trait Clock {
+    fn now(&self) -> Duration;
+
+    // Blocks until `time`, the loop calls this when nothing is left to do before the next timer is due
+    fn wait_until(&mut self, time: Duration);
+}
+
+// The time of the machine, waiting for a timer sleeps the thread
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> SystemClock {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn wait_until(&mut self, time: Duration) {
+        if let Some(remaining) = time.checked_sub(self.now()) {
+            std::thread::sleep(remaining);
+        }
+    }
+}
+
+// Time that only moves when the loop waits for a timer, it jumps straight to it. Runs are deterministic and never sleep.
+#[derive(Default)]
+pub struct VirtualClock {
+    now: Duration,
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn wait_until(&mut self, time: Duration) {
+        self.now = self.now.max(time);
+    }
+}
+
+pub struct Timer {
+    pub callback: Value,
+    pub arguments: Vec<Value>,
+    // Set for `setInterval`, the timer is scheduled again before its callback runs
+    pub interval: Option<Duration>,
+    id: u32,
+    time: Duration,
+    // Timers that are due at the same time run in the order they were scheduled
+    sequence: u64,
+}
+
+// The jobs, timers and clock of a runtime. After the script and after every timer the jobs are drained,
+// then the loop waits for the next timer until there are none left.
+pub struct EventLoop {
+    jobs: VecDeque<Job>,
+    timers: Vec<Timer>,
+    clock: Box<dyn Clock>,
+    next_timer_id: u32,
+    next_sequence: u64,
+    // Rejected promises that no handler has been added to yet, the host hears about the ones still here once the queue is empty
+    unhandled_rejections: Vec<ObjectRef>,
+}
+
+impl EventLoop {
+    pub fn new(clock: Box<dyn Clock>) -> EventLoop {
+        EventLoop {
+            jobs: VecDeque::new(),
+            timers: Vec::new(),
+            clock,
+            next_timer_id: 1,
+            next_sequence: 0,
+            unhandled_rejections: Vec::new(),
+        }
+    }
+
+    // HostEnqueuePromiseJob, jobs run in the order they are queued
+    pub fn enqueue(&mut self, job: impl FnOnce(&mut Runtime) -> RuntimeResult<()> + 'static) {
+        self.jobs.push_back(Box::new(job));
+    }
+
+    pub fn next(&mut self) -> Option<Job> {
+        self.jobs.pop_front()
+    }
+
+    // Schedules a callback `delay` from now, the id it returns cancels it
+    pub fn add_timer(&mut self, callback: Value, arguments: Vec<Value>, delay: Duration, repeat: bool) -> u32 {
+        let id = self.next_timer_id;
+        self.next_timer_id += 1;
+
+        let interval = match repeat {
+            true => Some(delay),
+            false => None,
+        };
+
+        self.schedule(Timer { callback, arguments, interval, id, time: Duration::ZERO, sequence: 0 }, delay);
+
+        id
+    }
+
+    // `clearTimeout` and `clearInterval` share the ids, unknown ones are ignored
+    pub fn clear_timer(&mut self, id: u32) {
+        self.timers.retain(|timer| timer.id != id);
+    }
+
+    // Waits until the earliest timer is due and takes it out, an interval is put back for its next run
+    pub fn next_timer(&mut self) -> Option<Timer> {
+        let index = (0..self.timers.len()).min_by_key(|&index| (self.timers[index].time, self.timers[index].sequence))?;
+        let timer = self.timers.remove(index);
+
+        self.clock.wait_until(timer.time);
+
+        if let Some(interval) = timer.interval {
+            self.schedule(Timer { callback: timer.callback.clone(), arguments: timer.arguments.clone(), ..timer }, interval);
+        }
+
+        Some(timer)
+    }
+
+    fn schedule(&mut self, mut timer: Timer, delay: Duration) {
+        timer.time = self.clock.now() + delay;
+        timer.sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        self.timers.push(timer);
+    }
+
+    // HostPromiseRejectionTracker, with `handled` for a handler added to a promise that was already rejected
+    pub fn track_rejection(&mut self, promise: &ObjectRef, handled: bool) {
+        match handled {
+            true => self.unhandled_rejections.retain(|rejected| !Rc::ptr_eq(rejected, promise)),
+            false => self.unhandled_rejections.push(promise.clone()),
+        }
+    }
+
+    pub fn take_unhandled_rejections(&mut self) -> Vec<ObjectRef> {
+        std::mem::take(&mut self.unhandled_rejections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::{run_test_script, run_test_script_with, Uncaught};
+
+    use super::*;
+
+    fn virtual_event_loop() -> EventLoop {
+        EventLoop::new(Box::new(VirtualClock::default()))
+    }
+
+    fn add(event_loop: &mut EventLoop, milliseconds: u64, repeat: bool) -> u32 {
+        event_loop.add_timer(Value::Undefined, vec![], Duration::from_millis(milliseconds), repeat)
+    }
+
+    #[test]
+    fn timers_run_by_due_time_then_in_scheduling_order() {
+        let mut event_loop = virtual_event_loop();
+
+        let late = add(&mut event_loop, 30, false);
+        let first = add(&mut event_loop, 10, false);
+        let second = add(&mut event_loop, 10, false);
+        let immediate = add(&mut event_loop, 0, false);
+
+        let order: Vec<u32> = std::iter::from_fn(|| event_loop.next_timer()).map(|timer| timer.id).collect();
+
+        assert_eq!(order, vec![immediate, first, second, late]);
+        assert_eq!(event_loop.clock.now(), Duration::from_millis(30));
+    }
+
+    #[test]
+    fn virtual_clock_jumps_to_each_timer_and_intervals_are_scheduled_again() {
+        let mut event_loop = virtual_event_loop();
+        let interval = add(&mut event_loop, 10, true);
+        let timeout = add(&mut event_loop, 25, false);
+
+        let mut runs = Vec::new();
+
+        for _ in 0..4 {
+            let timer = event_loop.next_timer().unwrap();
+            runs.push((timer.id, event_loop.clock.now().as_millis()));
+        }
+
+        assert_eq!(runs, vec![(interval, 10), (interval, 20), (timeout, 25), (interval, 30)]);
+
+        event_loop.clear_timer(interval);
+        assert!(event_loop.next_timer().is_none());
+    }
+
+    #[test]
+    fn jobs_run_before_timers_and_cleared_timers_never_run() {
+        run_test_script("
+            var log = [];
+            setTimeout(function () { log.push('timeout 10'); }, 10);
+            setTimeout(function () {
+                log.push('timeout 0');
+                Promise.resolve().then(function () { log.push('job from timeout 0'); });
+                setTimeout(function () { log.push('nested timeout 10'); }, 10);
+            }, 0);
+            var cancelled = setTimeout(function () { log.push('cancelled'); }, 5);
+            clearTimeout(cancelled);
+            var count = 0;
+            var interval = setInterval(function () {
+                count = count + 1;
+                log.push('interval ' + count);
+                if (count == 2) {
+                    clearInterval(interval);
+                }
+            }, 4);
+            queueMicrotask(function () { log.push('microtask'); });
+            Promise.resolve().then(function () { log.push('promise job'); });
+            log.push('script');
+        ", |runtime| {
+            let log = runtime.variable("log").ok().flatten().expect("log is defined");
+
+            assert_eq!(log.to_display_string(), "[ 'script', 'microtask', 'promise job', 'timeout 0', 'job from timeout 0', \
+                'interval 1', 'interval 2', 'timeout 10', 'nested timeout 10' ]");
+        });
+    }
+
+    #[test]
+    fn an_unhandled_rejection_ends_the_run_after_the_jobs_of_its_task() {
+        run_test_script_with("
+            var log = [];
+            Promise.reject(new Error('handled')).catch(function () { log.push('caught'); });
+            setTimeout(function () {
+                Promise.reject(new TypeError('unhandled'));
+                log.push('timeout 0');
+            }, 0);
+            setTimeout(function () { log.push('timeout 10'); }, 10);
+        ", |_| {}, |runtime, outcome| {
+            let reasons = match outcome {
+                Err(Uncaught::Rejections(reasons)) => reasons,
+                _ => panic!("Expected the run to end with an unhandled rejection"),
+            };
+
+            assert_eq!(reasons.len(), 1);
+            assert!(Uncaught::Rejections(reasons).to_string().starts_with("Uncaught (in promise) TypeError: unhandled"));
+
+            let log = runtime.variable("log").ok().flatten().expect("log is defined");
+            assert_eq!(log.to_display_string(), "[ 'caught', 'timeout 0' ]");
+        });
+    }
+}
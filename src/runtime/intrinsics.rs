@@ -0,0 +1,128 @@
+use std::rc::Rc;
+
+use super::{builtins::ErrorKind, object::{Object, ObjectRef}, value::{Symbol, Value}};
+
+// The built-in prototypes, every object the runtime creates links to one of these
+pub struct Intrinsics {
+    pub object_prototype: ObjectRef,
+    pub function_prototype: ObjectRef,
+    pub array_prototype: ObjectRef,
+    pub string_prototype: ObjectRef,
+    pub number_prototype: ObjectRef,
+    pub boolean_prototype: ObjectRef,
+    pub symbol_prototype: ObjectRef,
+    pub bigint_prototype: ObjectRef,
+    pub regexp_prototype: ObjectRef,
+    pub error_prototype: ObjectRef,
+    pub type_error_prototype: ObjectRef,
+    pub reference_error_prototype: ObjectRef,
+    pub range_error_prototype: ObjectRef,
+    pub syntax_error_prototype: ObjectRef,
+    pub eval_error_prototype: ObjectRef,
+    pub uri_error_prototype: ObjectRef,
+    pub aggregate_error_prototype: ObjectRef,
+    pub map_prototype: ObjectRef,
+    pub set_prototype: ObjectRef,
+    pub promise_prototype: ObjectRef,
+    // %IteratorPrototype%, the built-in iterators and generators inherit `[Symbol.iterator]() { return this }` from it
+    pub iterator_prototype: ObjectRef,
+    pub array_iterator_prototype: ObjectRef,
+    pub string_iterator_prototype: ObjectRef,
+    pub map_iterator_prototype: ObjectRef,
+    pub set_iterator_prototype: ObjectRef,
+    // What generator functions inherit from instead of Function.prototype, its `prototype` is %GeneratorPrototype%
+    pub generator_function_prototype: ObjectRef,
+    pub generator_prototype: ObjectRef,
+    // The same for async functions and async generators, async generators inherit `[Symbol.asyncIterator]` instead
+    pub async_function_prototype: ObjectRef,
+    pub async_generator_function_prototype: ObjectRef,
+    pub async_iterator_prototype: ObjectRef,
+    pub async_generator_prototype: ObjectRef,
+    // The well-known symbols, the keys the language itself looks methods up by
+    pub has_instance_symbol: Rc<Symbol>,
+    pub iterator_symbol: Rc<Symbol>,
+    pub async_iterator_symbol: Rc<Symbol>,
+    pub to_primitive_symbol: Rc<Symbol>,
+}
+
+impl Intrinsics {
+    // The prototypes start out empty, the built-ins fill in their methods when they are registered
+    pub fn new() -> Intrinsics {
+        let object_prototype = Object::new_ordinary(None);
+        let prototype = || Object::new_ordinary(Some(object_prototype.clone()));
+        let error_prototype = prototype();
+        let error_subprototype = || Object::new_ordinary(Some(error_prototype.clone()));
+        let function_prototype = prototype();
+        let iterator_prototype = prototype();
+        let iterator_subprototype = || Object::new_ordinary(Some(iterator_prototype.clone()));
+        let async_iterator_prototype = prototype();
+
+        Intrinsics {
+            generator_function_prototype: Object::new_ordinary(Some(function_prototype.clone())),
+            async_function_prototype: Object::new_ordinary(Some(function_prototype.clone())),
+            async_generator_function_prototype: Object::new_ordinary(Some(function_prototype.clone())),
+            async_generator_prototype: Object::new_ordinary(Some(async_iterator_prototype.clone())),
+            async_iterator_prototype,
+            function_prototype,
+            // Array.prototype is an array itself
+            array_prototype: Object::new_array(&object_prototype),
+            string_prototype: prototype(),
+            number_prototype: prototype(),
+            boolean_prototype: prototype(),
+            symbol_prototype: prototype(),
+            bigint_prototype: prototype(),
+            regexp_prototype: prototype(),
+            type_error_prototype: error_subprototype(),
+            reference_error_prototype: error_subprototype(),
+            range_error_prototype: error_subprototype(),
+            syntax_error_prototype: error_subprototype(),
+            eval_error_prototype: error_subprototype(),
+            uri_error_prototype: error_subprototype(),
+            aggregate_error_prototype: error_subprototype(),
+            map_prototype: prototype(),
+            set_prototype: prototype(),
+            promise_prototype: prototype(),
+            array_iterator_prototype: iterator_subprototype(),
+            string_iterator_prototype: iterator_subprototype(),
+            map_iterator_prototype: iterator_subprototype(),
+            set_iterator_prototype: iterator_subprototype(),
+            generator_prototype: iterator_subprototype(),
+            iterator_prototype,
+            error_prototype,
+            object_prototype,
+            has_instance_symbol: well_known_symbol("hasInstance"),
+            iterator_symbol: well_known_symbol("iterator"),
+            async_iterator_symbol: well_known_symbol("asyncIterator"),
+            to_primitive_symbol: well_known_symbol("toPrimitive"),
+        }
+    }
+
+    // The prototype a primitive reads its properties from, objects and nullish values have none here
+    pub fn primitive_prototype(&self, value: &Value) -> Option<ObjectRef> {
+        match value {
+            Value::Undefined | Value::Null | Value::Object(_) => None,
+            Value::Boolean(_) => Some(self.boolean_prototype.clone()),
+            Value::Number(_) => Some(self.number_prototype.clone()),
+            Value::BigInt(_) => Some(self.bigint_prototype.clone()),
+            Value::String(_) => Some(self.string_prototype.clone()),
+            Value::Symbol(_) => Some(self.symbol_prototype.clone()),
+        }
+    }
+
+    pub fn error_prototype_for(&self, kind: ErrorKind) -> ObjectRef {
+        match kind {
+            ErrorKind::Error => self.error_prototype.clone(),
+            ErrorKind::TypeError => self.type_error_prototype.clone(),
+            ErrorKind::ReferenceError => self.reference_error_prototype.clone(),
+            ErrorKind::RangeError => self.range_error_prototype.clone(),
+            ErrorKind::SyntaxError => self.syntax_error_prototype.clone(),
+            ErrorKind::EvalError => self.eval_error_prototype.clone(),
+            ErrorKind::URIError => self.uri_error_prototype.clone(),
+            ErrorKind::AggregateError => self.aggregate_error_prototype.clone(),
+        }
+    }
+}
+
+fn well_known_symbol(name: &str) -> Rc<Symbol> {
+    Rc::new(Symbol { description: Some(format!("Symbol.{}", name)) })
+}
@@ -1,104 +1,352 @@
+use std::{fmt, rc::Rc};
 
-use core::panic;
-use crate::{ast::{AstProgram, nodes::{AstNode, VariableLiteral}}};
+use crate::ast::{AstProgram, nodes::AstNode};
 
-use self::{nodes::{BlockScope, FunctionCall}, parsers::{expression_statement::parse_expression_statement, parse_function_declaration::parse_function_declaration, variable_declaration::parse_variable_declaration}};
+use self::{builtins::{create_error, promise_result, ErrorKind, PromiseState}, call_stack::{stack_pointer, Script, StackFrame, STACK_HEADROOM}, coroutine::{CoroutineYielder, StackPool}, declarations::{has_use_strict_directive, instantiate_function_declarations}, environment::{Assignment, Binding, Environment, EnvironmentRef, assign_variable, is_strict, lookup_variable}, event_loop::EventLoop, intrinsics::Intrinsics, module::{import_module, ModuleMap}, object::{find_property, Object, ObjectKind, ObjectRef, Property, PropertyKey}, operations::call, nodes::{Completion, RuntimeResult}, parsers::{block_statement::parse_block_statement, class_expression::parse_class_declaration, export_declaration::{parse_export_default_declaration, parse_export_named_declaration}, expression_statement::parse_expression_statement, for_of_statement::parse_for_of_statement, if_statement::parse_if_statement, parse_function_declaration::parse_function_declaration, return_statement::parse_return_statement, throw_statement::parse_throw_statement, try_statement::parse_try_statement, variable_declaration::parse_variable_declaration, while_statement::parse_while_statement}};
 
+mod builtins;
+mod call_stack;
+mod coroutine;
+mod declarations;
+mod environment;
+mod event_loop;
+mod intrinsics;
+mod module;
+mod module_loader;
 mod nodes;
+mod object;
+mod operations;
 mod parsers;
+mod reference;
+mod value;
+
+pub use self::{call_stack::STACK_SIZE, event_loop::{Clock, SystemClock, VirtualClock}, module_loader::{FileSystemLoader, ModuleLoader}, value::Value};
 
 pub struct Runtime {
-    pub scopes: Vec<BlockScope>,
+    pub environment: EnvironmentRef,
+    // The scope of scripts, each module gets a scope of its own inside it
+    pub global_environment: EnvironmentRef,
+    pub intrinsics: Intrinsics,
+    // The `this` of scripts and of sloppy functions called without a receiver
+    pub global_object: ObjectRef,
+    // The script functions that are running, the innermost last, errors record it as their `stack`
+    pub call_stack: Vec<StackFrame>,
+    // The generators and async functions that are running, a `yield` or `await` suspends the innermost
+    pub coroutines: Vec<CoroutineYielder>,
+    pub coroutine_stacks: StackPool,
+    // The lowest native stack address a call may start at, see `check_call_depth`
+    pub stack_limit: usize,
+    pub event_loop: EventLoop,
+    pub modules: ModuleMap,
 }
 
 impl Runtime {
-    pub fn new() -> Runtime {
-        let root_scope = BlockScope::new_root();
-        let scopes = vec![root_scope];
+    pub fn new(clock: Box<dyn Clock>, loader: Box<dyn ModuleLoader>) -> Runtime {
+        let intrinsics = Intrinsics::new();
+        let global_object = Object::new_ordinary(Some(intrinsics.object_prototype.clone()));
+        let environment = Environment::new_root(&intrinsics, &global_object);
 
         Runtime {
-            scopes,
+            environment: environment.clone(),
+            global_environment: environment,
+            intrinsics,
+            global_object,
+            call_stack: Vec::new(),
+            coroutines: Vec::new(),
+            coroutine_stacks: StackPool::default(),
+            // The runtime is created near the top of a stack of `STACK_SIZE`
+            stack_limit: stack_pointer().saturating_sub(STACK_SIZE) + STACK_HEADROOM,
+            event_loop: EventLoop::new(clock),
+            modules: ModuleMap::new(loader),
         }
     }
 
-    pub fn run(&mut self, program: &AstProgram) {
-
-        for step in program.body.iter() {
-            match step {
-                AstNode::VariableDeclaration(variable) => {
-                    parse_variable_declaration(self, variable);
-                },
-                AstNode::ExpressionStatement(statement) => {
-                    parse_expression_statement(self, statement);
-                },
-                AstNode::FunctionDeclaration(declaration) => {
-                    parse_function_declaration(self, declaration);
-                },
-                _ => { panic!("Unsupported step: {:?}", step) }
-            }
+    // An exception that no `catch` handled ends the script with it as the error
+    pub fn run(&mut self, program: &AstProgram, script: Rc<Script>) -> RuntimeResult<()> {
+        self.environment.borrow_mut().strict = has_use_strict_directive(&program.body);
+        self.call_stack.push(StackFrame { function_name: None, position: 0, script });
+
+        instantiate_function_declarations(self, &program.body, &[]);
+
+        let result = self.run_body(&program.body);
+        self.call_stack.pop();
+
+        result
+    }
+
+    // Runs the statements of a script or module body, which can not return or break out of it
+    pub fn run_body(&mut self, body: &[AstNode]) -> RuntimeResult<()> {
+        match self.run_statements(body)? {
+            Completion::Normal => Ok(()),
+            Completion::Return(_) => self.syntax_error("Illegal return statement"),
+            Completion::Break => self.syntax_error("Illegal break statement"),
+            Completion::Continue => self.syntax_error("Illegal continue statement"),
         }
     }
 
-    pub fn function(&self, function_name: &String) -> Option<&FunctionCall> {
-        let latest_scope = self.scopes.len();
+    // The script or module of the innermost frame, functions created now belong to it and `import()` resolves against it
+    pub fn current_script(&self) -> Rc<Script> {
+        self.call_stack.last().expect("Code only runs inside a frame").script.clone()
+    }
 
-        for index in (0..latest_scope).rev() {
-            let scope = self.scopes.get(index);
+    // Runs queued jobs until there are none left, including the jobs they queue themselves
+    pub fn run_jobs(&mut self) -> RuntimeResult<()> {
+        while let Some(job) = self.event_loop.next() {
+            job(self)?;
+        }
 
-            if scope.is_none() {
-                break;
-            }
+        Ok(())
+    }
+
+    // Waits for the next timer and runs its callback, or gives None when there are no timers left
+    pub fn run_next_timer(&mut self) -> Option<RuntimeResult<()>> {
+        let timer = self.event_loop.next_timer()?;
 
-            let scope = scope.unwrap();
-            let function = scope.functions.get(function_name);
+        Some(call(self, &timer.callback, Value::Undefined, timer.arguments).map(|_| ()))
+    }
 
-            if function.is_some() {
-                return function;
+    // Runs the statements in order, stopping at the first one that does not complete normally
+    pub fn run_statements(&mut self, statements: &[AstNode]) -> RuntimeResult<Completion> {
+        for statement in statements.iter() {
+            let completion = self.run_statement(statement)?;
+
+            if !matches!(completion, Completion::Normal) {
+                return Ok(completion);
             }
         }
 
-        None
+        Ok(Completion::Normal)
     }
 
-    pub fn variable(&self, variable_name: &String) -> Option<&VariableLiteral> {
-        let latest_scope = self.scopes.len();
+    pub fn run_statement(&mut self, statement: &AstNode) -> RuntimeResult<Completion> {
+        self.set_position(statement.range().0);
 
-        for index in (0..latest_scope).rev() {
-            let scope = self.scopes.get(index);
+        match statement {
+            AstNode::VariableDeclaration(variable) => {
+                parse_variable_declaration(self, variable)?;
+                Ok(Completion::Normal)
+            },
+            AstNode::ExpressionStatement(statement) => {
+                parse_expression_statement(self, statement)?;
+                Ok(Completion::Normal)
+            },
+            AstNode::FunctionDeclaration(declaration) => {
+                parse_function_declaration(self, declaration)?;
+                Ok(Completion::Normal)
+            },
+            AstNode::ClassDeclaration(declaration) => {
+                parse_class_declaration(self, declaration)?;
+                Ok(Completion::Normal)
+            },
+            AstNode::BlockStatement(block) => parse_block_statement(self, block),
+            AstNode::ReturnStatement(statement) => parse_return_statement(self, statement),
+            AstNode::IfStatement(statement) => parse_if_statement(self, statement),
+            AstNode::WhileStatement(statement) => parse_while_statement(self, statement),
+            AstNode::ForOfStatement(statement) => parse_for_of_statement(self, statement),
+            AstNode::ThrowStatement(statement) => parse_throw_statement(self, statement),
+            AstNode::TryStatement(statement) => parse_try_statement(self, statement),
+            AstNode::BreakStatement(_) => Ok(Completion::Break),
+            AstNode::ContinueStatement(_) => Ok(Completion::Continue),
+            AstNode::ExportNamedDeclaration(declaration) => parse_export_named_declaration(self, declaration),
+            AstNode::ExportDefaultDeclaration(declaration) => parse_export_default_declaration(self, declaration),
+            // Imports and `export *` are done by the time the module body runs
+            AstNode::ImportDeclaration(_) | AstNode::ExportAllDeclaration(_) => Ok(Completion::Normal),
+        }
+    }
 
-            if scope.is_none() {
-                break;
-            }
+    pub fn variable(&self, variable_name: &str) -> RuntimeResult<Option<Value>> {
+        match lookup_variable(&self.environment, variable_name) {
+            Some(Binding::Initialized(value)) => Ok(Some(value)),
+            Some(Binding::Uninitialized) => self.reference_error(&format!("Cannot access '{}' before initialization", variable_name)),
+            None => Ok(None),
+        }
+    }
 
-            let scope = scope.unwrap();
-            let variable = scope.variables.get(variable_name);
+    pub fn declare_variable(&mut self, variable_name: &str, value: Value) {
+        self.environment.borrow_mut().declare(variable_name, value);
+    }
 
-            if variable.is_some() {
-                return variable;
-            }
+    // Undeclared variables become properties of the global object, strict code gets an error instead
+    pub fn assign_variable(&mut self, variable_name: &str, value: Value) -> RuntimeResult<()> {
+        match assign_variable(&self.environment, variable_name, value.clone()) {
+            Assignment::Assigned => {},
+            Assignment::ReadOnly if self.is_strict() => return self.type_error(&format!("Cannot assign to read only variable '{}'", variable_name)),
+            Assignment::ReadOnly => {},
+            Assignment::Constant => return self.type_error("Assignment to constant variable."),
+            Assignment::Uninitialized => return self.reference_error(&format!("Cannot access '{}' before initialization", variable_name)),
+            Assignment::Unresolved if self.is_strict() => return self.reference_error(&format!("{} is not defined", variable_name)),
+            Assignment::Unresolved => {
+                self.global_object.borrow_mut().create_data_property(PropertyKey::from(variable_name), value);
+            },
+        }
+
+        Ok(())
+    }
+
+    pub fn is_strict(&self) -> bool {
+        is_strict(&self.environment)
+    }
+
+    // Makes `environment` the current one, returning the environment that was replaced
+    pub fn enter_environment(&mut self, environment: EnvironmentRef) -> EnvironmentRef {
+        std::mem::replace(&mut self.environment, environment)
+    }
+
+    // Moves the innermost frame to `position`, which is where a stack trace will show it
+    pub fn set_position(&mut self, position: usize) {
+        if let Some(frame) = self.call_stack.last_mut() {
+            frame.position = position;
+        }
+    }
+
+    // Errors the runtime runs into are thrown as instances of the built-in errors, so scripts can catch them
+    pub fn throw_error<T>(&self, kind: ErrorKind, message: &str) -> RuntimeResult<T> {
+        Err(create_error(self, kind, message))
+    }
+
+    pub fn type_error<T>(&self, message: &str) -> RuntimeResult<T> {
+        self.throw_error(ErrorKind::TypeError, message)
+    }
+
+    pub fn reference_error<T>(&self, message: &str) -> RuntimeResult<T> {
+        self.throw_error(ErrorKind::ReferenceError, message)
+    }
+
+    pub fn range_error<T>(&self, message: &str) -> RuntimeResult<T> {
+        self.throw_error(ErrorKind::RangeError, message)
+    }
+
+    pub fn syntax_error<T>(&self, message: &str) -> RuntimeResult<T> {
+        self.throw_error(ErrorKind::SyntaxError, message)
+    }
+}
+
+// Why a run ended in failure, `main` reports it on stderr and picks the exit code
+pub enum Uncaught {
+    // An exception no `catch` handled, or a module that failed to load, link or run
+    Exception(Value),
+    // The reasons of promises that were still rejected without a handler once the jobs after a task were done
+    Rejections(Vec<Value>),
+    // The module still waits on a top-level await that nothing is left to settle
+    UnsettledTopLevelAwait,
+}
+
+impl Uncaught {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Uncaught::UnsettledTopLevelAwait => 13,
+            _ => 1,
         }
+    }
+}
 
-        None
+impl fmt::Display for Uncaught {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Uncaught::Exception(error) => write!(f, "Uncaught {}", error_report(error)),
+            Uncaught::Rejections(reasons) => {
+                let reports: Vec<String> = reasons.iter().map(|reason| format!("Uncaught (in promise) {}", error_report(reason))).collect();
+                write!(f, "{}", reports.join("\n"))
+            },
+            Uncaught::UnsettledTopLevelAwait => write!(f, "Warning: Detected unsettled top-level await"),
+        }
     }
+}
+
+// Runs the script and then the event loop until no jobs or timers are left.
+// An uncaught exception ends the run, so do promises that are still rejected without a handler once the jobs after a task are done.
+pub fn run(mut runtime: Runtime, program: &AstProgram, name: &str, text: &str) -> Result<(), Uncaught> {
+    let result = runtime.run(program, Rc::new(Script::new(name, text)));
+
+    run_event_loop(&mut runtime, result, None)
+}
 
-    pub fn new_scope(&mut self) {
-        self.scopes.push(BlockScope::new());
+// Loads the module through the loader and runs it and its imports, then the event loop.
+// A module that fails to load, link or run ends the run like an uncaught exception does.
+pub fn run_module(mut runtime: Runtime, specifier: &str) -> Result<(), Uncaught> {
+    match import_module(&mut runtime, specifier, None) {
+        Ok((_, evaluation)) => run_event_loop(&mut runtime, Ok(()), Some(&evaluation)),
+        Err(error) => run_event_loop(&mut runtime, Err(error), None),
     }
+}
+
+// Once nothing is left to run, a module whose evaluation is still pending waits on a promise that can never settle
+fn run_event_loop(runtime: &mut Runtime, mut result: RuntimeResult<()>, evaluation: Option<&ObjectRef>) -> Result<(), Uncaught> {
+    loop {
+        result.and_then(|_| runtime.run_jobs()).map_err(Uncaught::Exception)?;
 
-    pub fn pop_scope(&mut self) {
-        if self.scopes.len() > 1 {
-            self.scopes.pop();
+        if let Some(PromiseState::Rejected(error)) = evaluation.map(promise_state) {
+            return Err(Uncaught::Exception(error));
         }
+
+        let rejections = runtime.event_loop.take_unhandled_rejections();
+
+        if !rejections.is_empty() {
+            return Err(Uncaught::Rejections(rejections.iter().map(promise_result).collect()));
+        }
+
+        result = match runtime.run_next_timer() {
+            Some(result) => result,
+            None => break,
+        };
     }
 
-    pub fn current_scope(&mut self) -> &mut BlockScope {
-        let index = self.scopes.len() - 1;
-        self.scopes.get_mut(index).unwrap()
+    match evaluation.map(promise_state) {
+        Some(PromiseState::Pending) => Err(Uncaught::UnsettledTopLevelAwait),
+        _ => Ok(()),
+    }
+}
+
+fn promise_state(promise: &ObjectRef) -> PromiseState {
+    match &promise.borrow().kind {
+        ObjectKind::Promise(data) => data.state.clone(),
+        _ => unreachable!(),
     }
+}
+
+// An error's `stack` already starts with its name and message
+fn error_report(error: &Value) -> String {
+    match error {
+        Value::Object(object) if object.borrow().is_error() => match find_property(object, &PropertyKey::from("stack")) {
+            Some(Property::Data { value: Value::String(stack), .. }) => stack.to_string(),
+            _ => error.to_display_string(),
+        },
+        _ => error.to_display_string(),
+    }
+}
 
+// Runs a script on a virtual clock until its jobs and timers are done, then hands the runtime to `check`.
+// Like `main` it runs on a thread with a stack of `STACK_SIZE`, which the runtime measures recursion against.
+#[cfg(test)]
+pub fn run_test_script(text: &str, check: impl FnOnce(&mut Runtime) + Send + 'static) {
+    run_test_script_with(text, |_| {}, move |runtime, outcome| {
+        if let Err(uncaught) = outcome {
+            panic!("{}", uncaught);
+        }
+
+        check(runtime);
+    });
 }
 
-pub fn run(program: &AstProgram) {
-    let mut runtime = Runtime::new();
-    runtime.run(program);
-}
\ No newline at end of file
+// The same with a `setup` that configures the runtime before the script runs, `check` also gets how the run ended
+#[cfg(test)]
+pub fn run_test_script_with(text: &str, setup: impl FnOnce(&mut Runtime) + Send + 'static, check: impl FnOnce(&mut Runtime, Result<(), Uncaught>) + Send + 'static) {
+    let text = text.to_string();
+
+    let thread = std::thread::Builder::new().stack_size(STACK_SIZE).spawn(move || {
+        let tokens = crate::tokenizer::tokenize(&text);
+        let program = crate::ast::parse(&tokens);
+
+        let mut runtime = Runtime::new(Box::new(VirtualClock::default()), Box::new(FileSystemLoader));
+        setup(&mut runtime);
+
+        let result = runtime.run(&program, Rc::new(Script::new("test.js", &text)));
+        let outcome = run_event_loop(&mut runtime, result, None);
+
+        check(&mut runtime, outcome);
+    });
+
+    if let Err(panic) = thread.expect("Failed to start the test thread").join() {
+        std::panic::resume_unwind(panic);
+    }
+}
@@ -0,0 +1,951 @@
+use std::{cell::{Cell, RefCell}, collections::HashMap, panic, rc::Rc, sync::Once};
+
+use crate::{ast::{self, AstProgram, nodes::{AstNode, ExportDefaultKind, ImportSpecifier}}, tokenizer};
+
+use super::{builtins::{new_promise, on_settled, reject_promise, resolve_promise, start_async_function, ErrorKind}, call_stack::{Script, StackFrame}, coroutine::Coroutine, declarations::instantiate_function_declarations, environment::{Binding, Environment, EnvironmentRef}, module_loader::ModuleLoader, nodes::RuntimeResult, object::{Object, ObjectKind, ObjectRef, PropertyKey}, parsers::pattern::bound_names, value::Value, Runtime};
+
+pub type ModuleRef = Rc<RefCell<Module>>;
+
+// Every module that was loaded, by the key its loader resolved it to, so each is only loaded and run once
+pub struct ModuleMap {
+    pub loader: Box<dyn ModuleLoader>,
+    pub modules: HashMap<String, ModuleRef>,
+    // Modules that wait for an async dependency or await themselves run in the order they got here
+    async_evaluation_count: usize,
+}
+
+impl ModuleMap {
+    pub fn new(loader: Box<dyn ModuleLoader>) -> ModuleMap {
+        ModuleMap {
+            loader,
+            modules: HashMap::new(),
+            async_evaluation_count: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ModuleStatus {
+    Unlinked,
+    Linking,
+    Linked,
+    Evaluating,
+    // Waiting for its own top-level await or for an async dependency
+    EvaluatingAsync,
+    Evaluated,
+}
+
+// [[AsyncEvaluation]], a module that evaluates asynchronously keeps its place in line until it is done
+#[derive(Clone, Copy, PartialEq)]
+enum AsyncEvaluation {
+    Unset,
+    Order(usize),
+    Done,
+}
+
+#[derive(Clone)]
+enum ImportName {
+    Name(String),
+    // `import * as ns` and `export * as ns`
+    Namespace,
+}
+
+struct ImportEntry {
+    module_request: String,
+    import_name: ImportName,
+    local_name: String,
+}
+
+// `export { local as export_name }`, or a declaration that exports its own name
+struct LocalExport {
+    export_name: String,
+    local_name: String,
+}
+
+// `export { import_name as export_name } from`, which needs no binding in this module
+#[derive(Clone)]
+struct IndirectExport {
+    export_name: String,
+    module_request: String,
+    import_name: ImportName,
+}
+
+// A Source Text Module Record, with the fields the linking and evaluation algorithms keep on it
+pub struct Module {
+    script: Rc<Script>,
+    program: Rc<AstProgram>,
+    status: ModuleStatus,
+    evaluation_error: Option<Value>,
+    pub environment: Option<EnvironmentRef>,
+    namespace: Option<ObjectRef>,
+    meta: Option<ObjectRef>,
+    // The specifiers of the modules this one imports from, in source order, and what they resolved to
+    requested_modules: Vec<String>,
+    loaded_modules: HashMap<String, ModuleRef>,
+    import_entries: Vec<ImportEntry>,
+    local_exports: Vec<LocalExport>,
+    indirect_exports: Vec<IndirectExport>,
+    star_exports: Vec<String>,
+    dfs_index: usize,
+    dfs_ancestor_index: usize,
+    // The module whose strongly connected component this one is in, which evaluates for all of it
+    cycle_root: Option<ModuleRef>,
+    has_top_level_await: bool,
+    async_evaluation: AsyncEvaluation,
+    // The promise of an evaluation that started at this module
+    top_level_capability: Option<ObjectRef>,
+    async_parent_modules: Vec<ModuleRef>,
+    pending_async_dependencies: usize,
+}
+
+// Where an export ends up after following re-exports, a binding in some module or a module's namespace
+#[derive(Clone)]
+enum ResolvedBinding {
+    Binding(ModuleRef, String),
+    Namespace(ModuleRef),
+}
+
+enum Resolution {
+    Found(ResolvedBinding),
+    NotFound,
+    // Two `export *` provide the same name from different bindings
+    Ambiguous,
+}
+
+impl Module {
+    // ParseModule, the entries are collected from the imports and exports at the top level
+    fn new(name: &str, text: &str, program: AstProgram) -> Module {
+        let mut module = Module {
+            script: Rc::new(Script::new(name, text)),
+            program: Rc::new(AstProgram::new()),
+            status: ModuleStatus::Unlinked,
+            evaluation_error: None,
+            environment: None,
+            namespace: None,
+            meta: None,
+            requested_modules: Vec::new(),
+            loaded_modules: HashMap::new(),
+            import_entries: Vec::new(),
+            local_exports: Vec::new(),
+            indirect_exports: Vec::new(),
+            star_exports: Vec::new(),
+            dfs_index: 0,
+            dfs_ancestor_index: 0,
+            cycle_root: None,
+            has_top_level_await: program.has_top_level_await,
+            async_evaluation: AsyncEvaluation::Unset,
+            top_level_capability: None,
+            async_parent_modules: Vec::new(),
+            pending_async_dependencies: 0,
+        };
+
+        let mut local_exports = Vec::new();
+
+        for statement in program.body.iter() {
+            match statement {
+                AstNode::ImportDeclaration(declaration) => {
+                    module.request(&declaration.source.value);
+
+                    for specifier in declaration.specifiers.iter() {
+                        let (import_name, local) = match specifier {
+                            ImportSpecifier::Default(local) => (ImportName::Name("default".to_string()), local),
+                            ImportSpecifier::Namespace(local) => (ImportName::Namespace, local),
+                            ImportSpecifier::Named { imported, local } => (ImportName::Name(imported.clone()), local),
+                        };
+
+                        module.import_entries.push(ImportEntry {
+                            module_request: declaration.source.value.clone(),
+                            import_name,
+                            local_name: local.name.clone(),
+                        });
+                    }
+                },
+                AstNode::ExportNamedDeclaration(declaration) => match (&declaration.declaration, &declaration.source) {
+                    (Some(declaration), _) => {
+                        for name in declared_names(declaration) {
+                            local_exports.push(LocalExport { export_name: name.clone(), local_name: name });
+                        }
+                    },
+                    (None, None) => {
+                        for specifier in declaration.specifiers.iter() {
+                            local_exports.push(LocalExport { export_name: specifier.exported.clone(), local_name: specifier.local.clone() });
+                        }
+                    },
+                    (None, Some(source)) => {
+                        module.request(&source.value);
+
+                        for specifier in declaration.specifiers.iter() {
+                            module.indirect_exports.push(IndirectExport {
+                                export_name: specifier.exported.clone(),
+                                module_request: source.value.clone(),
+                                import_name: ImportName::Name(specifier.local.clone()),
+                            });
+                        }
+                    },
+                },
+                AstNode::ExportDefaultDeclaration(declaration) => {
+                    let local_name = match &declaration.declaration {
+                        ExportDefaultKind::Declaration(declaration) => declared_names(declaration).remove(0),
+                        ExportDefaultKind::Expression(_) => "default".to_string(),
+                    };
+
+                    local_exports.push(LocalExport { export_name: "default".to_string(), local_name });
+                },
+                AstNode::ExportAllDeclaration(declaration) => {
+                    module.request(&declaration.source.value);
+
+                    match &declaration.exported {
+                        Some(exported) => module.indirect_exports.push(IndirectExport {
+                            export_name: exported.clone(),
+                            module_request: declaration.source.value.clone(),
+                            import_name: ImportName::Namespace,
+                        }),
+                        None => module.star_exports.push(declaration.source.value.clone()),
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        // Exporting a name that was imported re-exports the binding it imports, except for namespace imports
+        for export in local_exports {
+            let import = module.import_entries.iter().find(|import| import.local_name == export.local_name);
+
+            match import {
+                Some(ImportEntry { module_request, import_name: ImportName::Name(import_name), .. }) => {
+                    module.indirect_exports.push(IndirectExport {
+                        export_name: export.export_name,
+                        module_request: module_request.clone(),
+                        import_name: ImportName::Name(import_name.clone()),
+                    });
+                },
+                _ => module.local_exports.push(export),
+            }
+        }
+
+        module.program = Rc::new(program);
+        module
+    }
+
+    fn request(&mut self, specifier: &str) {
+        if !self.requested_modules.iter().any(|requested| requested == specifier) {
+            self.requested_modules.push(specifier.to_string());
+        }
+    }
+
+    fn loaded_module(&self, specifier: &str) -> ModuleRef {
+        self.loaded_modules.get(specifier).expect("Imported modules are loaded before the module is linked").clone()
+    }
+}
+
+// The names a declaration after `export` binds
+fn declared_names(declaration: &AstNode) -> Vec<String> {
+    let mut names = Vec::new();
+
+    match declaration {
+        AstNode::VariableDeclaration(declaration) => {
+            for declarator in declaration.declarations.iter() {
+                bound_names(&declarator.id, &mut names);
+            }
+        },
+        AstNode::FunctionDeclaration(declaration) => names.push(declaration.id.name.clone()),
+        AstNode::ClassDeclaration(declaration) => names.push(declaration.id.name.clone()),
+        _ => {},
+    }
+
+    names
+}
+
+// HostLoadImportedModule for the module and everything it imports that was not loaded before.
+// A module is in the map before its imports are loaded, so a cycle finds it there.
+pub fn load_module(runtime: &mut Runtime, specifier: &str, referrer: Option<&str>) -> RuntimeResult<ModuleRef> {
+    let key = match runtime.modules.loader.resolve(specifier, referrer) {
+        Ok(key) => key,
+        Err(message) => return runtime.throw_error(ErrorKind::Error, &message),
+    };
+
+    if let Some(module) = runtime.modules.modules.get(&key) {
+        return Ok(module.clone());
+    }
+
+    let text = match runtime.modules.loader.load(&key) {
+        Ok(text) => text,
+        Err(message) => return runtime.throw_error(ErrorKind::Error, &message),
+    };
+
+    let program = match parse_module_text(&text) {
+        Ok(program) => program,
+        Err(message) => return runtime.syntax_error(&message),
+    };
+
+    let module = Rc::new(RefCell::new(Module::new(&key, &text, program)));
+    runtime.modules.modules.insert(key.clone(), module.clone());
+
+    let requests = module.borrow().requested_modules.clone();
+
+    for request in requests {
+        match load_module(runtime, &request, Some(&key)) {
+            Ok(loaded) => {
+                module.borrow_mut().loaded_modules.insert(request, loaded);
+            },
+            Err(error) => {
+                runtime.modules.modules.remove(&key);
+                return Err(error);
+            },
+        }
+    }
+
+    Ok(module)
+}
+
+thread_local! {
+    // Set while a module is parsed, its syntax errors are thrown to the importer instead of reported as panics
+    static PARSING: Cell<bool> = const { Cell::new(false) };
+}
+
+// The parser panics on a syntax error, for a module that becomes a message for a SyntaxError
+fn parse_module_text(text: &str) -> Result<AstProgram, String> {
+    static QUIET_PANICS: Once = Once::new();
+
+    QUIET_PANICS.call_once(|| {
+        let report = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if !PARSING.with(Cell::get) {
+                report(info);
+            }
+        }));
+    });
+
+    PARSING.with(|parsing| parsing.set(true));
+    let result = panic::catch_unwind(|| ast::parse_module(&tokenizer::tokenize(text)));
+    PARSING.with(|parsing| parsing.set(false));
+
+    result.map_err(|payload| match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map(|message| message.to_string()).unwrap_or_else(|| "Invalid module".to_string()),
+    })
+}
+
+// Loads, links and evaluates a module, which is what `import()` and running a module as the program do.
+// The promise settles once the module and everything it imports have run.
+pub fn import_module(runtime: &mut Runtime, specifier: &str, referrer: Option<&str>) -> RuntimeResult<(ModuleRef, ObjectRef)> {
+    let module = load_module(runtime, specifier, referrer)?;
+
+    link_module(runtime, &module)?;
+
+    let promise = evaluate_module(runtime, &module);
+
+    Ok((module, promise))
+}
+
+// ResolveExport, `resolve_set` holds the exports already being resolved so cycles of re-exports end
+fn resolve_export(module: &ModuleRef, export_name: &str, resolve_set: &mut Vec<(ModuleRef, String)>) -> Resolution {
+    if resolve_set.iter().any(|(resolving, name)| Rc::ptr_eq(resolving, module) && name == export_name) {
+        return Resolution::NotFound;
+    }
+
+    resolve_set.push((module.clone(), export_name.to_string()));
+
+    let (local, indirect, star_modules) = {
+        let record = module.borrow();
+
+        let local = record.local_exports.iter()
+            .find(|export| export.export_name == export_name)
+            .map(|export| export.local_name.clone());
+
+        let indirect = record.indirect_exports.iter()
+            .find(|export| export.export_name == export_name)
+            .map(|export| (record.loaded_module(&export.module_request), export.import_name.clone()));
+
+        let star_modules: Vec<ModuleRef> = record.star_exports.iter().map(|request| record.loaded_module(request)).collect();
+
+        (local, indirect, star_modules)
+    };
+
+    if let Some(local_name) = local {
+        return Resolution::Found(ResolvedBinding::Binding(module.clone(), local_name));
+    }
+
+    if let Some((imported, import_name)) = indirect {
+        return match import_name {
+            ImportName::Namespace => Resolution::Found(ResolvedBinding::Namespace(imported)),
+            ImportName::Name(name) => resolve_export(&imported, &name, resolve_set),
+        };
+    }
+
+    // `export *` never provides a default export
+    if export_name == "default" {
+        return Resolution::NotFound;
+    }
+
+    let mut star_resolution: Option<ResolvedBinding> = None;
+
+    for star_module in star_modules {
+        let resolution = match resolve_export(&star_module, export_name, resolve_set) {
+            Resolution::Ambiguous => return Resolution::Ambiguous,
+            Resolution::NotFound => continue,
+            Resolution::Found(resolution) => resolution,
+        };
+
+        match &star_resolution {
+            None => star_resolution = Some(resolution),
+            Some(existing) if !same_binding(existing, &resolution) => return Resolution::Ambiguous,
+            Some(_) => {},
+        }
+    }
+
+    match star_resolution {
+        Some(resolution) => Resolution::Found(resolution),
+        None => Resolution::NotFound,
+    }
+}
+
+fn same_binding(left: &ResolvedBinding, right: &ResolvedBinding) -> bool {
+    match (left, right) {
+        (ResolvedBinding::Binding(left, left_name), ResolvedBinding::Binding(right, right_name)) => Rc::ptr_eq(left, right) && left_name == right_name,
+        (ResolvedBinding::Namespace(left), ResolvedBinding::Namespace(right)) => Rc::ptr_eq(left, right),
+        _ => false,
+    }
+}
+
+// GetExportedNames, names that come from two `export *` are left for ResolveExport to sort out
+fn exported_names(module: &ModuleRef, export_star_set: &mut Vec<ModuleRef>) -> Vec<String> {
+    if export_star_set.iter().any(|visited| Rc::ptr_eq(visited, module)) {
+        return Vec::new();
+    }
+
+    export_star_set.push(module.clone());
+
+    let (mut names, star_modules) = {
+        let record = module.borrow();
+
+        let names: Vec<String> = record.local_exports.iter()
+            .map(|export| export.export_name.clone())
+            .chain(record.indirect_exports.iter().map(|export| export.export_name.clone()))
+            .collect();
+
+        let star_modules: Vec<ModuleRef> = record.star_exports.iter().map(|request| record.loaded_module(request)).collect();
+
+        (names, star_modules)
+    };
+
+    for star_module in star_modules {
+        for name in exported_names(&star_module, export_star_set) {
+            if name != "default" && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    names
+}
+
+// GetModuleNamespace, created the first time it is asked for with the exports that resolve unambiguously
+pub fn module_namespace(module: &ModuleRef) -> ObjectRef {
+    if let Some(namespace) = &module.borrow().namespace {
+        return namespace.clone();
+    }
+
+    let mut exports: Vec<(String, ResolvedBinding)> = exported_names(module, &mut Vec::new()).into_iter()
+        .filter_map(|name| match resolve_export(module, &name, &mut Vec::new()) {
+            Resolution::Found(resolution) => Some((name, resolution)),
+            Resolution::NotFound | Resolution::Ambiguous => None,
+        })
+        .collect();
+
+    // Sorted by code units, like the spec sorts the names
+    exports.sort_by(|(left, _), (right, _)| left.encode_utf16().cmp(right.encode_utf16()));
+
+    let namespace = Object::new(ObjectKind::ModuleNamespace(ModuleNamespace { exports }), None);
+    module.borrow_mut().namespace = Some(namespace.clone());
+
+    namespace
+}
+
+// The exports of a module namespace object, whose properties read the current values of the bindings
+pub struct ModuleNamespace {
+    exports: Vec<(String, ResolvedBinding)>,
+}
+
+impl ModuleNamespace {
+    pub fn export_names(&self) -> impl Iterator<Item = &String> {
+        self.exports.iter().map(|(name, _)| name)
+    }
+
+    // The binding behind an export, None when the module does not export the name
+    pub fn binding(&self, name: &str) -> Option<Binding> {
+        let (_, resolution) = self.exports.iter().find(|(export, _)| export == name)?;
+
+        Some(match resolution {
+            ResolvedBinding::Namespace(module) => Binding::Initialized(Value::Object(module_namespace(module))),
+            ResolvedBinding::Binding(module, binding_name) => match &module.borrow().environment {
+                Some(environment) => environment.borrow().get_binding(binding_name).unwrap_or(Binding::Uninitialized),
+                None => Binding::Uninitialized,
+            },
+        })
+    }
+}
+
+// Link, which creates the environments of the module and every module it imports that is not linked yet
+fn link_module(runtime: &mut Runtime, module: &ModuleRef) -> RuntimeResult<()> {
+    let mut stack = Vec::new();
+
+    if let Err(error) = inner_module_linking(runtime, module, &mut stack, 0) {
+        for module in stack {
+            module.borrow_mut().status = ModuleStatus::Unlinked;
+        }
+
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+// InnerModuleLinking, a depth-first search that finds the strongly connected components of the graph.
+// The modules of a cycle are only marked as linked together once the search is back at the first of them.
+fn inner_module_linking(runtime: &mut Runtime, module: &ModuleRef, stack: &mut Vec<ModuleRef>, mut index: usize) -> RuntimeResult<usize> {
+    if module.borrow().status != ModuleStatus::Unlinked {
+        return Ok(index);
+    }
+
+    let requests = {
+        let mut record = module.borrow_mut();
+        record.status = ModuleStatus::Linking;
+        record.dfs_index = index;
+        record.dfs_ancestor_index = index;
+
+        record.requested_modules.iter().map(|request| record.loaded_module(request)).collect::<Vec<_>>()
+    };
+
+    index += 1;
+    stack.push(module.clone());
+
+    for required in requests {
+        index = inner_module_linking(runtime, &required, stack, index)?;
+
+        let (status, ancestor_index) = {
+            let required = required.borrow();
+            (required.status, required.dfs_ancestor_index)
+        };
+
+        if status == ModuleStatus::Linking {
+            let mut record = module.borrow_mut();
+            record.dfs_ancestor_index = record.dfs_ancestor_index.min(ancestor_index);
+        }
+    }
+
+    initialize_environment(runtime, module)?;
+
+    let is_root = {
+        let record = module.borrow();
+        record.dfs_ancestor_index == record.dfs_index
+    };
+
+    if is_root {
+        while let Some(required) = stack.pop() {
+            required.borrow_mut().status = ModuleStatus::Linked;
+
+            if Rc::ptr_eq(&required, module) {
+                break;
+            }
+        }
+    }
+
+    Ok(index)
+}
+
+// InitializeEnvironment, checks that every import and re-export resolves and binds the imports.
+// Functions are created now, so modules in a cycle can call each other's functions before either body runs.
+fn initialize_environment(runtime: &mut Runtime, module: &ModuleRef) -> RuntimeResult<()> {
+    let (indirect_exports, imports, script, program) = {
+        let record = module.borrow();
+
+        let imports: Vec<(ModuleRef, String, ImportName, String)> = record.import_entries.iter()
+            .map(|entry| (record.loaded_module(&entry.module_request), entry.module_request.clone(), entry.import_name.clone(), entry.local_name.clone()))
+            .collect();
+
+        (record.indirect_exports.clone(), imports, record.script.clone(), record.program.clone())
+    };
+
+    for export in indirect_exports {
+        if let ImportName::Name(import_name) = &export.import_name {
+            match resolve_export(module, &export.export_name, &mut Vec::new()) {
+                Resolution::Found(_) => {},
+                Resolution::NotFound => return unresolvable_import(runtime, &export.module_request, import_name),
+                Resolution::Ambiguous => return ambiguous_import(runtime, &export.module_request, import_name),
+            }
+        }
+    }
+
+    let environment = Environment::new_module(&runtime.global_environment);
+
+    for (imported, module_request, import_name, local_name) in imports {
+        let resolution = match import_name {
+            ImportName::Namespace => ResolvedBinding::Namespace(imported),
+            ImportName::Name(import_name) => match resolve_export(&imported, &import_name, &mut Vec::new()) {
+                Resolution::Found(resolution) => resolution,
+                Resolution::NotFound => return unresolvable_import(runtime, &module_request, &import_name),
+                Resolution::Ambiguous => return ambiguous_import(runtime, &module_request, &import_name),
+            },
+        };
+
+        match resolution {
+            ResolvedBinding::Namespace(imported) => {
+                let mut environment = environment.borrow_mut();
+                environment.declare_uninitialized(&local_name, true);
+                environment.initialize(&local_name, Value::Object(module_namespace(&imported)));
+            },
+            ResolvedBinding::Binding(imported, binding_name) => {
+                environment.borrow_mut().imports.insert(local_name, (imported, binding_name));
+            },
+        }
+    }
+
+    module.borrow_mut().environment = Some(environment.clone());
+
+    let outer_environment = runtime.enter_environment(environment);
+    runtime.call_stack.push(StackFrame { function_name: None, position: 0, script });
+    instantiate_function_declarations(runtime, &program.body, &[]);
+    runtime.call_stack.pop();
+    runtime.enter_environment(outer_environment);
+
+    Ok(())
+}
+
+fn unresolvable_import<T>(runtime: &Runtime, module_request: &str, import_name: &str) -> RuntimeResult<T> {
+    runtime.syntax_error(&format!("The requested module '{}' does not provide an export named '{}'", module_request, import_name))
+}
+
+fn ambiguous_import<T>(runtime: &Runtime, module_request: &str, import_name: &str) -> RuntimeResult<T> {
+    runtime.syntax_error(&format!("The requested module '{}' contains conflicting star exports for name '{}'", module_request, import_name))
+}
+
+// Evaluate, the promise settles when the module and its imports have run, or with the first error one of them threw.
+// Evaluating a module again gives the promise of the first evaluation.
+fn evaluate_module(runtime: &mut Runtime, module: &ModuleRef) -> ObjectRef {
+    // Modules that failed before their cycle was done have no cycle root, they fail again by themselves
+    let cycle_root = match module.borrow().status {
+        ModuleStatus::EvaluatingAsync | ModuleStatus::Evaluated => module.borrow().cycle_root.clone(),
+        _ => None,
+    };
+    let module = cycle_root.unwrap_or_else(|| module.clone());
+
+    if let Some(promise) = &module.borrow().top_level_capability {
+        return promise.clone();
+    }
+
+    let promise = new_promise(runtime);
+    module.borrow_mut().top_level_capability = Some(promise.clone());
+
+    let mut stack = Vec::new();
+
+    match inner_module_evaluation(runtime, &module, &mut stack, 0) {
+        Ok(_) => {
+            if module.borrow().async_evaluation == AsyncEvaluation::Unset {
+                resolve_promise(runtime, &promise, Value::Undefined);
+            }
+        },
+        Err(error) => {
+            for module in stack {
+                let mut module = module.borrow_mut();
+                module.status = ModuleStatus::Evaluated;
+                module.evaluation_error = Some(error.clone());
+            }
+
+            reject_promise(runtime, &promise, error);
+        },
+    }
+
+    promise
+}
+
+// InnerModuleEvaluation, imports run before the modules that import them and each module runs once.
+// A module that awaits, or imports one that does, is left to run once its async imports are done.
+fn inner_module_evaluation(runtime: &mut Runtime, module: &ModuleRef, stack: &mut Vec<ModuleRef>, mut index: usize) -> RuntimeResult<usize> {
+    let requests = {
+        let mut record = module.borrow_mut();
+
+        match record.status {
+            ModuleStatus::EvaluatingAsync | ModuleStatus::Evaluated => return match &record.evaluation_error {
+                Some(error) => Err(error.clone()),
+                None => Ok(index),
+            },
+            ModuleStatus::Evaluating => return Ok(index),
+            _ => {},
+        }
+
+        record.status = ModuleStatus::Evaluating;
+        record.dfs_index = index;
+        record.dfs_ancestor_index = index;
+        record.pending_async_dependencies = 0;
+
+        record.requested_modules.iter().map(|request| record.loaded_module(request)).collect::<Vec<_>>()
+    };
+
+    index += 1;
+    stack.push(module.clone());
+
+    for required in requests {
+        index = inner_module_evaluation(runtime, &required, stack, index)?;
+
+        let (status, ancestor_index) = {
+            let required = required.borrow();
+            (required.status, required.dfs_ancestor_index)
+        };
+
+        let required = match status {
+            ModuleStatus::Evaluating => {
+                let mut record = module.borrow_mut();
+                record.dfs_ancestor_index = record.dfs_ancestor_index.min(ancestor_index);
+
+                required.clone()
+            },
+            _ => {
+                let cycle_root = required.borrow().cycle_root.clone().expect("Evaluated modules have a cycle root");
+
+                if let Some(error) = &cycle_root.borrow().evaluation_error {
+                    return Err(error.clone());
+                }
+
+                cycle_root
+            },
+        };
+
+        let is_async = matches!(required.borrow().async_evaluation, AsyncEvaluation::Order(_));
+
+        if is_async {
+            module.borrow_mut().pending_async_dependencies += 1;
+            required.borrow_mut().async_parent_modules.push(module.clone());
+        }
+    }
+
+    let (pending, has_top_level_await) = {
+        let record = module.borrow();
+        (record.pending_async_dependencies, record.has_top_level_await)
+    };
+
+    if pending > 0 || has_top_level_await {
+        runtime.modules.async_evaluation_count += 1;
+        module.borrow_mut().async_evaluation = AsyncEvaluation::Order(runtime.modules.async_evaluation_count);
+
+        if pending == 0 {
+            execute_async_module(runtime, module);
+        }
+    } else {
+        execute_module(runtime, module)?;
+    }
+
+    let is_root = {
+        let record = module.borrow();
+        record.dfs_ancestor_index == record.dfs_index
+    };
+
+    if is_root {
+        while let Some(required) = stack.pop() {
+            {
+                let mut record = required.borrow_mut();
+
+                record.status = match record.async_evaluation {
+                    AsyncEvaluation::Unset => ModuleStatus::Evaluated,
+                    _ => ModuleStatus::EvaluatingAsync,
+                };
+                record.cycle_root = Some(module.clone());
+            }
+
+            if Rc::ptr_eq(&required, module) {
+                break;
+            }
+        }
+    }
+
+    Ok(index)
+}
+
+// Runs the body of a module without top-level await to its end
+fn execute_module(runtime: &mut Runtime, module: &ModuleRef) -> RuntimeResult<()> {
+    let (environment, script, program) = module_body(module);
+
+    let outer_environment = runtime.enter_environment(environment);
+    runtime.call_stack.push(StackFrame { function_name: None, position: 0, script });
+    let result = runtime.run_body(&program.body);
+    runtime.call_stack.pop();
+    runtime.enter_environment(outer_environment);
+
+    result
+}
+
+// A module with top-level await runs like the body of an async function, its dependents continue when it is done
+fn execute_async_module(runtime: &mut Runtime, module: &ModuleRef) {
+    let (environment, script, program) = module_body(module);
+
+    let coroutine = Coroutine::new(move |runtime| {
+        runtime.enter_environment(environment);
+        runtime.call_stack.push(StackFrame { function_name: None, position: 0, script });
+        let result = runtime.run_body(&program.body);
+        runtime.call_stack.pop();
+
+        result.map(|_| Value::Undefined)
+    });
+
+    let promise = start_async_function(runtime, coroutine);
+    let module = module.clone();
+
+    on_settled(runtime, &promise, move |runtime, result| match result {
+        Ok(_) => async_module_execution_fulfilled(runtime, &module),
+        Err(error) => async_module_execution_rejected(runtime, &module, error),
+    });
+}
+
+fn module_body(module: &ModuleRef) -> (EnvironmentRef, Rc<Script>, Rc<AstProgram>) {
+    let record = module.borrow();
+    let environment = record.environment.clone().expect("Modules are linked before they run");
+
+    (environment, record.script.clone(), record.program.clone())
+}
+
+// AsyncModuleExecutionFulfilled, the dependents that were only waiting for this module run now, in the order they
+// started waiting. Those without top-level await run right here, the others start their bodies.
+fn async_module_execution_fulfilled(runtime: &mut Runtime, module: &ModuleRef) {
+    if module.borrow().status == ModuleStatus::Evaluated {
+        return;
+    }
+
+    finish_async_evaluation(runtime, module);
+
+    let mut execution_list = Vec::new();
+    gather_available_ancestors(module, &mut execution_list);
+
+    execution_list.sort_by_key(|module| match module.borrow().async_evaluation {
+        AsyncEvaluation::Order(order) => order,
+        _ => unreachable!(),
+    });
+
+    for module in execution_list {
+        let (status, has_top_level_await) = {
+            let record = module.borrow();
+            (record.status, record.has_top_level_await)
+        };
+
+        // Already failed because another of its imports did
+        if status == ModuleStatus::Evaluated {
+            continue;
+        }
+
+        if has_top_level_await {
+            execute_async_module(runtime, &module);
+            continue;
+        }
+
+        match execute_module(runtime, &module) {
+            Ok(()) => finish_async_evaluation(runtime, &module),
+            Err(error) => async_module_execution_rejected(runtime, &module, error),
+        }
+    }
+}
+
+fn finish_async_evaluation(runtime: &mut Runtime, module: &ModuleRef) {
+    let promise = {
+        let mut record = module.borrow_mut();
+        record.async_evaluation = AsyncEvaluation::Done;
+        record.status = ModuleStatus::Evaluated;
+
+        record.top_level_capability.clone()
+    };
+
+    if let Some(promise) = promise {
+        resolve_promise(runtime, &promise, Value::Undefined);
+    }
+}
+
+// GatherAvailableAncestors, the modules waiting on this one that have no other async imports left
+fn gather_available_ancestors(module: &ModuleRef, execution_list: &mut Vec<ModuleRef>) {
+    let parents = module.borrow().async_parent_modules.clone();
+
+    for parent in parents {
+        if execution_list.iter().any(|listed| Rc::ptr_eq(listed, &parent)) {
+            continue;
+        }
+
+        let cycle_root = parent.borrow().cycle_root.clone().expect("Modules waiting for an import have a cycle root");
+
+        if cycle_root.borrow().evaluation_error.is_some() {
+            continue;
+        }
+
+        let (pending, has_top_level_await) = {
+            let mut record = parent.borrow_mut();
+            record.pending_async_dependencies -= 1;
+
+            (record.pending_async_dependencies, record.has_top_level_await)
+        };
+
+        if pending == 0 {
+            execution_list.push(parent.clone());
+
+            if !has_top_level_await {
+                gather_available_ancestors(&parent, execution_list);
+            }
+        }
+    }
+}
+
+// AsyncModuleExecutionRejected, the error is the result of every module waiting on this one
+fn async_module_execution_rejected(runtime: &mut Runtime, module: &ModuleRef, error: Value) {
+    let (parents, promise) = {
+        let mut record = module.borrow_mut();
+
+        if record.status == ModuleStatus::Evaluated {
+            return;
+        }
+
+        record.evaluation_error = Some(error.clone());
+        record.status = ModuleStatus::Evaluated;
+
+        (record.async_parent_modules.clone(), record.top_level_capability.clone())
+    };
+
+    for parent in parents {
+        async_module_execution_rejected(runtime, &parent, error.clone());
+    }
+
+    if let Some(promise) = promise {
+        reject_promise(runtime, &promise, error);
+    }
+}
+
+// `import.meta` of the running module, an object with its `url` that is created the first time it is asked for
+pub fn import_meta(runtime: &mut Runtime) -> Value {
+    let name = runtime.current_script().name.clone();
+    let module = runtime.modules.modules.get(&name).expect("import.meta is only parsed in modules").clone();
+
+    if let Some(meta) = &module.borrow().meta {
+        return Value::Object(meta.clone());
+    }
+
+    let meta = Object::new_ordinary(None);
+    meta.borrow_mut().create_data_property(PropertyKey::from("url"), Value::string(&runtime.modules.loader.url(&name)));
+    module.borrow_mut().meta = Some(meta.clone());
+
+    Value::Object(meta)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::run_test_script;
+
+    #[test]
+    fn a_module_with_a_syntax_error_rejects_its_import_with_a_syntax_error() {
+        let path = std::env::temp_dir().join(format!("syntax-error-{}.mjs", std::process::id()));
+        std::fs::write(&path, "export const = ;").unwrap();
+
+        let script = format!("var result; import({:?}).then(function () {{ result = \"loaded\"; }}, function (error) {{ result = error.name; }});", path.display().to_string());
+
+        run_test_script(&script, |runtime| {
+            let result = runtime.variable("result").ok().flatten().unwrap();
+            assert_eq!(result.to_display_string(), "SyntaxError");
+        });
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+
+// How the host finds modules. A specifier resolves to a key, and modules with the same key are the same module.
+// Errors are messages, the runtime throws them as errors from the `import` that asked for the module.
+pub trait ModuleLoader {
+    // `referrer` is the key of the importing module, or the name of the importing script
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String, String>;
+
+    // The source text of the module behind a key
+    fn load(&self, key: &str) -> Result<String, String>;
+
+    // What `import.meta.url` says for a module
+    fn url(&self, key: &str) -> String {
+        key.to_string()
+    }
+}
+
+// Resolves relative and absolute paths against the directory of the importing file, or the working directory.
+// Keys are canonical paths, so one file is one module however it is imported.
+pub struct FileSystemLoader;
+
+impl ModuleLoader for FileSystemLoader {
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String, String> {
+        let path = specifier.strip_prefix("file://").unwrap_or(specifier);
+
+        if !(path.starts_with('/') || path.starts_with("./") || path.starts_with("../")) {
+            return Err(format!("Cannot resolve module specifier '{}', relative references must start with \"/\", \"./\" or \"../\"", specifier));
+        }
+
+        let base = referrer
+            .and_then(|referrer| Path::new(referrer).parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        // Collecting the components drops the `.` in the middle of the path
+        let resolved: PathBuf = match base.as_os_str().is_empty() {
+            true => PathBuf::from(path),
+            false => base.join(path).components().collect(),
+        };
+
+        match resolved.canonicalize() {
+            Ok(path) => Ok(path.to_string_lossy().into_owned()),
+            Err(_) => Err(match referrer {
+                Some(referrer) => format!("Cannot find module '{}' imported from {}", resolved.display(), referrer),
+                None => format!("Cannot find module '{}'", resolved.display()),
+            }),
+        }
+    }
+
+    fn load(&self, key: &str) -> Result<String, String> {
+        std::fs::read_to_string(key).map_err(|error| format!("Cannot load module '{}': {}", key, error))
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("file://{}", key)
+    }
+}
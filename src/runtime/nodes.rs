@@ -1,61 +1,97 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, rc::{Rc, Weak}};
 
-use crate::ast::nodes::{BlockStatement, Literal, VariableLiteral};
+use crate::ast::nodes::{BlockStatement, Pattern};
+
+use super::{call_stack::Script, environment::EnvironmentRef, object::{Object, ObjectRef, Property, PropertyKey}, value::Value, Runtime};
 
 pub struct FunctionCall {
+    pub name: String,
     pub function_type: FunctionCallType,
-    pub arguments: Vec<Literal>,
+    // The number of parameters before the first one with a default or the rest parameter
+    pub length: usize,
+    // Whether `new` may be used with the function, methods and most built-ins are only callable
+    pub constructor: bool,
 }
 
+// Native functions get the runtime so they can convert values and call back into scripts, the `this` value and the arguments
+type SystemCallFn = dyn Fn(&mut Runtime, &Value, &[Value]) -> RuntimeResult<Option<Value>>;
+
 pub struct SystemCall {
-    pub func: Box<dyn Fn(&HashMap<String, Literal>) -> Option<Literal>>,
+    pub func: Box<SystemCallFn>,
 }
 
 impl SystemCall {
-    fn new(func: impl Fn(&HashMap<String, Literal>) -> Option<Literal> + 'static) -> Self {
-        Self { 
+    pub fn new(func: impl Fn(&mut Runtime, &Value, &[Value]) -> RuntimeResult<Option<Value>> + 'static) -> Self {
+        Self {
             func: Box::new(func)
         }
     }
 }
 
-pub enum FunctionCallType {
-    SystemCall(SystemCall),
-    RuntimeCall(BlockStatement)
+pub struct RuntimeCall {
+    pub params: Vec<Pattern>,
+    pub body: BlockStatement,
+    // The environment the function was declared in, its body resolves variables from here
+    pub environment: EnvironmentRef,
+    // Strict functions see `this` as it was passed, sloppy ones replace undefined and null with the global object
+    pub strict: bool,
+    // Methods remember the object they were defined on, `super.name` looks up its prototype
+    pub home_object: Option<ObjectRef>,
+    // Set for class constructors, which can only be called with `new`
+    pub class: Option<Rc<ClassDefinition>>,
+    // Arrow functions take `this`, `arguments` and `super` from the code around them
+    pub arrow: bool,
+    // Calling a generator function returns a generator object instead of running the body
+    pub generator: bool,
+    // Calling an async function returns a promise for the result of the body, `await` suspends it
+    pub is_async: bool,
+    // The script or module the function was written in, for the frames of its calls
+    pub script: Rc<Script>,
+    // The function object itself, a generator function reads its current `prototype` from it when it is called
+    pub function_object: RefCell<Weak<RefCell<Object>>>,
 }
 
-pub struct BlockScope {
-    pub functions: HashMap<String, FunctionCall>,
-    pub variables: HashMap<String, VariableLiteral>,
+// What a class constructor adds to every instance, before its body runs or when `super()` returns
+pub struct ClassDefinition {
+    // Whether the class has an `extends` clause, derived constructors leave creating `this` to `super()`
+    pub derived: bool,
+    // A class without a `constructor` method, a derived one passes its arguments on to the parent
+    pub default_constructor: bool,
+    pub fields: RefCell<Vec<ClassField>>,
+    pub private_methods: RefCell<Vec<(PropertyKey, Property)>>,
 }
 
-impl BlockScope {
-    pub fn new() -> BlockScope {
-        BlockScope {
-            functions: HashMap::<String, FunctionCall>::new(),
-            variables: HashMap::<String, VariableLiteral>::new(),
-        }
-    }
+pub struct ClassField {
+    // Private fields are keyed by the symbol the class gave their `#name`
+    pub key: PropertyKey,
+    pub private: bool,
+    // A method that returns the initial value, it runs with the new instance as `this`
+    pub initializer: Option<Value>,
+    // An anonymous function or class as the initializer is named after the field
+    pub names_initializer: bool,
+}
 
-    pub fn new_root() -> BlockScope {
-        let mut functions = HashMap::<String, FunctionCall>::new();
-
-        functions.insert("console.log".to_string(), FunctionCall { 
-            function_type: FunctionCallType::SystemCall(
-                SystemCall::new(|x| { 
-                    let output = x.get(&"output".to_string()).unwrap(); 
-
-                    println!("{}", output.value);
-                    
-                    None
-                })), 
-                arguments: vec![Literal::from_str("output")]
-            }
-        );
-
-        BlockScope {
-            functions,
-            variables: HashMap::<String, VariableLiteral>::new(),
-        }
-    }
-}
\ No newline at end of file
+// The function `bind` returns, calls go to the target with the bound `this` and leading arguments
+pub struct BoundFunction {
+    pub target: Value,
+    pub this_value: Value,
+    pub arguments: Vec<Value>,
+}
+
+pub enum FunctionCallType {
+    SystemCall(SystemCall),
+    RuntimeCall(RuntimeCall),
+    BoundFunction(BoundFunction),
+}
+
+// Code that can throw returns the thrown value as its error, `?` passes it on to the closest `catch`
+pub type RuntimeResult<T> = Result<T, Value>;
+
+// The way a statement finished, so return, break and continue can unwind enclosing statements
+#[derive(Debug)]
+pub enum Completion {
+    Normal,
+    Return(Value),
+    Break,
+    Continue,
+}
@@ -0,0 +1,719 @@
+use std::{cell::RefCell, collections::HashMap, fmt, hash::{Hash, Hasher}, rc::Rc};
+
+use super::{builtins::{AsyncGeneratorData, GeneratorState, IterationKind, MapData, PromiseData}, environment::Binding, module::ModuleNamespace, nodes::{FunctionCall, RuntimeResult}, operations::{same_value, string_to_number, to_uint32}, parsers::call_expression::call_function, value::{Symbol, Value}, Runtime};
+
+pub type ObjectRef = Rc<RefCell<Object>>;
+
+pub struct Object {
+    pub kind: ObjectKind,
+    // [[Prototype]], property lookups continue here when the object does not have the key itself
+    pub prototype: Option<ObjectRef>,
+    properties: HashMap<PropertyKey, Property>,
+    // Insertion order, `own_property_keys` sorts it into the order the spec wants
+    keys: Vec<PropertyKey>,
+    // Private fields, methods and accessors, keyed by the symbol of their `#name`
+    private_elements: HashMap<PropertyKey, Property>,
+}
+
+pub enum ObjectKind {
+    Ordinary,
+    Function(Rc<FunctionCall>),
+    // Elements are ordinary index properties, `length` is kept one past the highest of them
+    Array,
+    RegExp { pattern: String, flags: String },
+    // The `arguments` of a function call, an array-like object with its own `length`
+    Arguments,
+    // Instances of Error and the native errors
+    Error,
+    // The state says whether the body is running, finished or where it continues when it is resumed
+    Generator(GeneratorState),
+    // Thrown through the body of a generator by its `return` method, so only `finally` blocks run on the way out
+    GeneratorReturn(Value),
+    AsyncGenerator(AsyncGeneratorData),
+    Promise(PromiseData),
+    Map(MapData),
+    // A Set keeps its values as both key and value of the entries
+    Set(MapData),
+    // The iterators of arrays, strings, Maps and Sets. Once they are done they let go of what they iterated.
+    ArrayIterator { iterated: Option<ObjectRef>, index: usize, kind: IterationKind },
+    StringIterator { text: Option<Rc<str>>, position: usize },
+    MapIterator { map: Option<ObjectRef>, index: usize, kind: IterationKind },
+    SetIterator { set: Option<ObjectRef>, index: usize, kind: IterationKind },
+    // The exports of a module as properties, which can not be changed and have no prototype
+    ModuleNamespace(ModuleNamespace),
+}
+
+#[derive(Clone)]
+pub enum PropertyKey {
+    String(Rc<str>),
+    Symbol(Rc<Symbol>),
+}
+
+#[derive(Clone)]
+pub enum Property {
+    Data { value: Value, writable: bool, enumerable: bool, configurable: bool },
+    Accessor { get: Option<Value>, set: Option<Value>, enumerable: bool, configurable: bool },
+}
+
+// The fields `Object.defineProperty` reads, absent fields keep their current value
+#[derive(Clone, Default)]
+pub struct PropertyDescriptor {
+    pub value: Option<Value>,
+    pub writable: Option<bool>,
+    pub get: Option<Value>,
+    pub set: Option<Value>,
+    pub enumerable: Option<bool>,
+    pub configurable: Option<bool>,
+}
+
+impl Object {
+    pub fn new(kind: ObjectKind, prototype: Option<ObjectRef>) -> ObjectRef {
+        Rc::new(RefCell::new(Object {
+            kind,
+            prototype,
+            properties: HashMap::new(),
+            keys: Vec::new(),
+            private_elements: HashMap::new(),
+        }))
+    }
+
+    pub fn new_ordinary(prototype: Option<ObjectRef>) -> ObjectRef {
+        Object::new(ObjectKind::Ordinary, prototype)
+    }
+
+    pub fn new_function(function: FunctionCall, prototype: &ObjectRef) -> ObjectRef {
+        let length = function.length as f64;
+        let name = Value::string(&function.name);
+        let object = Object::new(ObjectKind::Function(Rc::new(function)), Some(prototype.clone()));
+
+        // Both are read-only but configurable, like on any function
+        for (key, value) in [("length", Value::Number(length)), ("name", name)] {
+            object.borrow_mut().define_own_property(PropertyKey::from(key), PropertyDescriptor {
+                value: Some(value),
+                writable: Some(false),
+                enumerable: Some(false),
+                configurable: Some(true),
+                ..Default::default()
+            });
+        }
+
+        object
+    }
+
+    pub fn new_array(prototype: &ObjectRef) -> ObjectRef {
+        let array = Object::new(ObjectKind::Array, Some(prototype.clone()));
+
+        array.borrow_mut().ordinary_define_own_property(PropertyKey::from("length"), PropertyDescriptor {
+            value: Some(Value::Number(0.0)),
+            writable: Some(true),
+            enumerable: Some(false),
+            configurable: Some(false),
+            ..Default::default()
+        });
+
+        array
+    }
+
+    pub fn new_arguments(prototype: &ObjectRef) -> ObjectRef {
+        Object::new(ObjectKind::Arguments, Some(prototype.clone()))
+    }
+
+    pub fn new_error(prototype: &ObjectRef) -> ObjectRef {
+        Object::new(ObjectKind::Error, Some(prototype.clone()))
+    }
+
+    pub fn new_regexp(pattern: &str, flags: &str, prototype: &ObjectRef) -> ObjectRef {
+        Object::new(ObjectKind::RegExp { pattern: pattern.to_string(), flags: flags.to_string() }, Some(prototype.clone()))
+    }
+
+    pub fn function(&self) -> Option<Rc<FunctionCall>> {
+        match &self.kind {
+            ObjectKind::Function(function) => Some(function.clone()),
+            _ => None,
+        }
+    }
+
+    // The exports of a namespace are writable data properties that can not be deleted, whose values are the bindings.
+    // Bindings in their temporal dead zone read as undefined here, `get` throws for them.
+    pub fn get_own_property(&self, key: &PropertyKey) -> Option<Property> {
+        if let (ObjectKind::ModuleNamespace(namespace), PropertyKey::String(name)) = (&self.kind, key) {
+            let value = match namespace.binding(name)? {
+                Binding::Initialized(value) => value,
+                Binding::Uninitialized => Value::Undefined,
+            };
+
+            return Some(Property::Data { value, writable: true, enumerable: true, configurable: false });
+        }
+
+        self.properties.get(key).cloned()
+    }
+
+    pub fn private_element(&self, key: &PropertyKey) -> Option<Property> {
+        self.private_elements.get(key).cloned()
+    }
+
+    // PrivateFieldAdd and PrivateMethodOrAccessorAdd, returns false when the object already has the element
+    pub fn add_private_element(&mut self, key: PropertyKey, property: Property) -> bool {
+        if self.private_elements.contains_key(&key) {
+            return false;
+        }
+
+        self.private_elements.insert(key, property);
+
+        true
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self.kind, ObjectKind::Array)
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.kind, ObjectKind::Error)
+    }
+
+    // The `length` of an array, which is always a data property holding a valid array length
+    pub fn array_length(&self) -> u32 {
+        match self.properties.get(&PropertyKey::from("length")) {
+            Some(Property::Data { value: Value::Number(length), .. }) => *length as u32,
+            _ => 0,
+        }
+    }
+
+    // Integer indices in ascending order, then strings and then symbols in the order they were added
+    pub fn own_property_keys(&self) -> Vec<PropertyKey> {
+        if let ObjectKind::ModuleNamespace(namespace) = &self.kind {
+            return namespace.export_names().map(|name| PropertyKey::from(name.as_str())).collect();
+        }
+
+        let mut indices: Vec<(u32, PropertyKey)> = self.keys.iter()
+            .filter_map(|key| key.array_index().map(|index| (index, key.clone())))
+            .collect();
+        indices.sort_by_key(|(index, _)| *index);
+
+        let strings = self.keys.iter()
+            .filter(|key| matches!(key, PropertyKey::String(_)) && key.array_index().is_none());
+        let symbols = self.keys.iter()
+            .filter(|key| matches!(key, PropertyKey::Symbol(_)));
+
+        indices.into_iter()
+            .map(|(_, key)| key)
+            .chain(strings.cloned())
+            .chain(symbols.cloned())
+            .collect()
+    }
+
+    // Adds a data property that is writable, enumerable and configurable, like an assignment would
+    pub fn create_data_property(&mut self, key: PropertyKey, value: Value) -> bool {
+        self.define_own_property(key, PropertyDescriptor {
+            value: Some(value),
+            writable: Some(true),
+            enumerable: Some(true),
+            configurable: Some(true),
+            ..Default::default()
+        })
+    }
+
+    // Built-in methods are writable and configurable, but not enumerable
+    pub fn define_builtin(&mut self, key: impl Into<PropertyKey>, value: Value) {
+        self.define_own_property(key.into(), PropertyDescriptor {
+            value: Some(value),
+            writable: Some(true),
+            enumerable: Some(false),
+            configurable: Some(true),
+            ..Default::default()
+        });
+    }
+
+    // [[DefineOwnProperty]], returns false when the change is not allowed
+    pub fn define_own_property(&mut self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+        if let ObjectKind::ModuleNamespace(_) = self.kind {
+            return false;
+        }
+
+        if !self.is_array() {
+            return self.ordinary_define_own_property(key, descriptor);
+        }
+
+        if key == PropertyKey::from("length") {
+            return self.array_set_length(descriptor);
+        }
+
+        let index = match key.array_index() {
+            Some(index) => index,
+            None => return self.ordinary_define_own_property(key, descriptor),
+        };
+
+        let length = self.array_length();
+        let length_writable = matches!(self.properties.get(&PropertyKey::from("length")), Some(Property::Data { writable: true, .. }));
+
+        if index >= length && !length_writable {
+            return false;
+        }
+
+        if !self.ordinary_define_own_property(key, descriptor) {
+            return false;
+        }
+
+        if index >= length {
+            self.set_array_length(index + 1);
+        }
+
+        true
+    }
+
+    // ArraySetLength, shrinking deletes the elements from the end until one refuses to go
+    fn array_set_length(&mut self, descriptor: PropertyDescriptor) -> bool {
+        let length_key = PropertyKey::from("length");
+
+        // Invalid lengths are rejected with a RangeError before the array is touched, see `check_array_length`
+        let new_length = match &descriptor.value {
+            Some(value) => match array_length_value(value) {
+                Some(length) => length,
+                None => return false,
+            },
+            None => return self.ordinary_define_own_property(length_key, descriptor),
+        };
+
+        let old_length = self.array_length();
+        let descriptor = PropertyDescriptor { value: Some(Value::Number(new_length as f64)), ..descriptor };
+
+        if new_length >= old_length {
+            return self.ordinary_define_own_property(length_key, descriptor);
+        }
+
+        if !matches!(self.properties.get(&length_key), Some(Property::Data { writable: true, .. })) {
+            return false;
+        }
+
+        // The length stays writable until the elements are gone, so it can still be corrected if a delete fails
+        let make_read_only = descriptor.writable == Some(false);
+        let descriptor = PropertyDescriptor { writable: None, ..descriptor };
+
+        if !self.ordinary_define_own_property(length_key.clone(), descriptor) {
+            return false;
+        }
+
+        let mut indices: Vec<u32> = self.keys.iter()
+            .filter_map(|key| key.array_index())
+            .filter(|index| *index >= new_length)
+            .collect();
+        indices.sort_unstable_by(|left, right| right.cmp(left));
+
+        for index in indices {
+            if !self.delete(&PropertyKey::from(index as usize)) {
+                self.set_array_length(index + 1);
+
+                if make_read_only {
+                    self.ordinary_define_own_property(length_key, PropertyDescriptor { writable: Some(false), ..Default::default() });
+                }
+
+                return false;
+            }
+        }
+
+        if make_read_only {
+            self.ordinary_define_own_property(length_key, PropertyDescriptor { writable: Some(false), ..Default::default() });
+        }
+
+        true
+    }
+
+    fn set_array_length(&mut self, length: u32) {
+        self.ordinary_define_own_property(PropertyKey::from("length"), PropertyDescriptor {
+            value: Some(Value::Number(length as f64)),
+            ..Default::default()
+        });
+    }
+
+    // [[Delete]], non-configurable properties stay and make it return false
+    pub fn delete(&mut self, key: &PropertyKey) -> bool {
+        if let (ObjectKind::ModuleNamespace(namespace), PropertyKey::String(name)) = (&self.kind, key) {
+            return namespace.binding(name).is_none();
+        }
+
+        match self.properties.get(key) {
+            Some(property) if !property.configurable() => false,
+            Some(_) => {
+                self.properties.remove(key);
+                self.keys.retain(|existing| existing != key);
+
+                true
+            },
+            None => true,
+        }
+    }
+
+    // ValidateAndApplyPropertyDescriptor, returns false when the change is not allowed
+    fn ordinary_define_own_property(&mut self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+        let current = match self.properties.get(&key) {
+            Some(current) => current.clone(),
+            None => {
+                let property = if descriptor.is_accessor() {
+                    Property::Accessor {
+                        get: descriptor.get.filter(|get| !matches!(get, Value::Undefined)),
+                        set: descriptor.set.filter(|set| !matches!(set, Value::Undefined)),
+                        enumerable: descriptor.enumerable.unwrap_or(false),
+                        configurable: descriptor.configurable.unwrap_or(false),
+                    }
+                } else {
+                    Property::Data {
+                        value: descriptor.value.unwrap_or(Value::Undefined),
+                        writable: descriptor.writable.unwrap_or(false),
+                        enumerable: descriptor.enumerable.unwrap_or(false),
+                        configurable: descriptor.configurable.unwrap_or(false),
+                    }
+                };
+
+                self.keys.push(key.clone());
+                self.properties.insert(key, property);
+
+                return true;
+            },
+        };
+
+        if !current.configurable() {
+            if descriptor.configurable == Some(true) {
+                return false;
+            }
+
+            if descriptor.enumerable.is_some_and(|enumerable| enumerable != current.enumerable()) {
+                return false;
+            }
+
+            let changes_kind = match current {
+                Property::Data { .. } => descriptor.is_accessor(),
+                Property::Accessor { .. } => descriptor.value.is_some() || descriptor.writable.is_some(),
+            };
+
+            if changes_kind {
+                return false;
+            }
+
+            match &current {
+                Property::Data { value, writable: false, .. } => {
+                    if descriptor.writable == Some(true) {
+                        return false;
+                    }
+
+                    if descriptor.value.as_ref().is_some_and(|new_value| !same_value(new_value, value)) {
+                        return false;
+                    }
+                },
+                Property::Accessor { get, set, .. } => {
+                    let same_function = |new: &Option<Value>, old: &Option<Value>| match new {
+                        None => true,
+                        Some(new) => same_value(new, old.as_ref().unwrap_or(&Value::Undefined)),
+                    };
+
+                    if !same_function(&descriptor.get, get) || !same_function(&descriptor.set, set) {
+                        return false;
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        let enumerable = descriptor.enumerable.unwrap_or(current.enumerable());
+        let configurable = descriptor.configurable.unwrap_or(current.configurable());
+
+        let property = match current {
+            Property::Data { .. } if descriptor.is_accessor() => Property::Accessor {
+                get: descriptor.get.filter(|get| !matches!(get, Value::Undefined)),
+                set: descriptor.set.filter(|set| !matches!(set, Value::Undefined)),
+                enumerable,
+                configurable,
+            },
+            Property::Accessor { .. } if descriptor.value.is_some() || descriptor.writable.is_some() => Property::Data {
+                value: descriptor.value.unwrap_or(Value::Undefined),
+                writable: descriptor.writable.unwrap_or(false),
+                enumerable,
+                configurable,
+            },
+            Property::Data { value, writable, .. } => Property::Data {
+                value: descriptor.value.unwrap_or(value),
+                writable: descriptor.writable.unwrap_or(writable),
+                enumerable,
+                configurable,
+            },
+            Property::Accessor { get, set, .. } => Property::Accessor {
+                get: match descriptor.get { Some(Value::Undefined) => None, Some(get) => Some(get), None => get },
+                set: match descriptor.set { Some(Value::Undefined) => None, Some(set) => Some(set), None => set },
+                enumerable,
+                configurable,
+            },
+        };
+
+        self.properties.insert(key, property);
+
+        true
+    }
+}
+
+// A new array length has to be a number that survives ToUint32 unchanged
+fn array_length_value(value: &Value) -> Option<u32> {
+    let number = match value {
+        Value::Number(number) => *number,
+        Value::Boolean(value) => *value as u8 as f64,
+        Value::Null => 0.0,
+        Value::String(text) => string_to_number(text),
+        _ => f64::NAN,
+    };
+
+    (number == to_uint32(number) as f64).then_some(number as u32)
+}
+
+// ArraySetLength throws a RangeError when the `length` of an array is given an invalid value
+pub fn check_array_length(runtime: &Runtime, object: &ObjectRef, key: &PropertyKey, value: &Value) -> RuntimeResult<()> {
+    if object.borrow().is_array() && *key == PropertyKey::from("length") && array_length_value(value).is_none() {
+        return runtime.range_error("Invalid array length");
+    }
+
+    Ok(())
+}
+
+// Follows the prototype chain to the object that has the key itself
+pub fn find_property(object: &ObjectRef, key: &PropertyKey) -> Option<Property> {
+    let mut current = object.clone();
+
+    loop {
+        if let Some(property) = current.borrow().get_own_property(key) {
+            return Some(property);
+        }
+
+        let prototype = current.borrow().prototype.clone();
+
+        match prototype {
+            Some(prototype) => current = prototype,
+            None => return None,
+        }
+    }
+}
+
+// [[Get]], inherited accessors run with `receiver` as `this`
+pub fn get(runtime: &mut Runtime, object: &ObjectRef, key: &PropertyKey, receiver: &Value) -> RuntimeResult<Value> {
+    let binding = match (&object.borrow().kind, key) {
+        (ObjectKind::ModuleNamespace(namespace), PropertyKey::String(name)) => namespace.binding(name),
+        _ => None,
+    };
+
+    if let Some(Binding::Uninitialized) = binding {
+        return runtime.reference_error(&format!("Cannot access '{}' before initialization", private_name(key)));
+    }
+
+    match find_property(object, key) {
+        Some(Property::Data { value, .. }) => Ok(value),
+        Some(Property::Accessor { get: Some(getter), .. }) => match getter.as_function() {
+            Some(function) => call_function(runtime, &function, receiver.clone(), vec![]),
+            None => Ok(Value::Undefined),
+        },
+        Some(Property::Accessor { get: None, .. }) | None => Ok(Value::Undefined),
+    }
+}
+
+// [[Set]], returns false when the property cannot be written.
+// Inherited data properties are shadowed by a new own property on the receiver.
+pub fn set(runtime: &mut Runtime, object: &ObjectRef, key: PropertyKey, value: Value, receiver: &Value) -> RuntimeResult<bool> {
+    match find_property(object, &key) {
+        Some(Property::Data { writable: false, .. }) => Ok(false),
+        Some(Property::Accessor { set: Some(setter), .. }) => match setter.as_function() {
+            Some(function) => {
+                call_function(runtime, &function, receiver.clone(), vec![value])?;
+                Ok(true)
+            },
+            None => Ok(false),
+        },
+        Some(Property::Accessor { set: None, .. }) => Ok(false),
+        Some(Property::Data { .. }) | None => {
+            let receiver = match receiver {
+                Value::Object(receiver) => receiver,
+                _ => return Ok(false),
+            };
+
+            let existing = receiver.borrow().get_own_property(&key);
+
+            Ok(match existing {
+                Some(Property::Accessor { .. }) | Some(Property::Data { writable: false, .. }) => false,
+                Some(Property::Data { .. }) => {
+                    check_array_length(runtime, receiver, &key, &value)?;
+
+                    receiver.borrow_mut().define_own_property(key, PropertyDescriptor {
+                        value: Some(value),
+                        ..Default::default()
+                    })
+                },
+                None => receiver.borrow_mut().create_data_property(key, value),
+            })
+        },
+    }
+}
+
+// PrivateGet, objects only have the private elements their classes added to them
+pub fn private_get(runtime: &mut Runtime, object: &Value, key: &PropertyKey) -> RuntimeResult<Value> {
+    let element = match object {
+        Value::Object(object) => object.borrow().private_element(key),
+        _ => None,
+    };
+
+    match element {
+        Some(Property::Data { value, .. }) => Ok(value),
+        Some(Property::Accessor { get: Some(getter), .. }) => {
+            let getter = getter.as_function().expect("private getters are functions");
+            call_function(runtime, &getter, object.clone(), vec![])
+        },
+        Some(Property::Accessor { get: None, .. }) => runtime.type_error(&format!("'{}' was defined without a getter", private_name(key))),
+        None => runtime.type_error(&format!("Cannot read private member {} from an object whose class did not declare it", private_name(key))),
+    }
+}
+
+// PrivateSet, private methods are read-only
+pub fn private_set(runtime: &mut Runtime, object: &Value, key: &PropertyKey, value: Value) -> RuntimeResult<()> {
+    let element = match object {
+        Value::Object(object) => object.borrow().private_element(key),
+        _ => None,
+    };
+
+    match element {
+        Some(Property::Data { writable: true, enumerable, configurable, .. }) => {
+            if let Value::Object(object) = object {
+                object.borrow_mut().private_elements.insert(key.clone(), Property::Data { value, writable: true, enumerable, configurable });
+            }
+        },
+        Some(Property::Data { .. }) => return runtime.type_error("Private method is not writable"),
+        Some(Property::Accessor { set: Some(setter), .. }) => {
+            let setter = setter.as_function().expect("private setters are functions");
+            call_function(runtime, &setter, object.clone(), vec![value])?;
+        },
+        Some(Property::Accessor { set: None, .. }) => return runtime.type_error(&format!("'{}' was defined without a setter", private_name(key))),
+        None => return runtime.type_error(&format!("Cannot write private member {} to an object whose class did not declare it", private_name(key))),
+    }
+
+    Ok(())
+}
+
+// The `#name` a private symbol was created for
+pub fn private_name(key: &PropertyKey) -> String {
+    match key {
+        PropertyKey::Symbol(symbol) => symbol.description.clone().unwrap_or_default(),
+        PropertyKey::String(name) => name.to_string(),
+    }
+}
+
+// [[SetPrototypeOf]], refuses to create a cycle in the chain
+pub fn set_prototype_of(object: &ObjectRef, prototype: Option<ObjectRef>) -> bool {
+    // The prototype of a namespace is always null
+    if let ObjectKind::ModuleNamespace(_) = object.borrow().kind {
+        return prototype.is_none();
+    }
+
+    if let Some(prototype) = &prototype {
+        if Rc::ptr_eq(object, prototype) || has_in_prototype_chain(prototype, object) {
+            return false;
+        }
+    }
+
+    object.borrow_mut().prototype = prototype;
+
+    true
+}
+
+// OrdinaryHasInstance, whether `prototype` is somewhere on the chain of `object`
+pub fn has_in_prototype_chain(object: &ObjectRef, prototype: &ObjectRef) -> bool {
+    let mut current = object.borrow().prototype.clone();
+
+    while let Some(object) = current {
+        if Rc::ptr_eq(&object, prototype) {
+            return true;
+        }
+
+        current = object.borrow().prototype.clone();
+    }
+
+    false
+}
+
+impl Property {
+    pub fn enumerable(&self) -> bool {
+        match self {
+            Property::Data { enumerable, .. } | Property::Accessor { enumerable, .. } => *enumerable,
+        }
+    }
+
+    pub fn configurable(&self) -> bool {
+        match self {
+            Property::Data { configurable, .. } | Property::Accessor { configurable, .. } => *configurable,
+        }
+    }
+}
+
+impl PropertyDescriptor {
+    pub fn is_accessor(&self) -> bool {
+        self.get.is_some() || self.set.is_some()
+    }
+}
+
+impl PropertyKey {
+    // Canonical numeric strings below 2^32 - 1 are array indices, "01" and "4294967295" are not
+    pub fn array_index(&self) -> Option<u32> {
+        let key = match self {
+            PropertyKey::String(key) => key,
+            PropertyKey::Symbol(_) => return None,
+        };
+
+        let index = key.parse::<u32>().ok()?;
+
+        if index == u32::MAX || index.to_string() != key.as_ref() {
+            return None;
+        }
+
+        Some(index)
+    }
+}
+
+impl fmt::Display for PropertyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyKey::String(key) => write!(f, "{}", key),
+            PropertyKey::Symbol(symbol) => write!(f, "Symbol({})", symbol.description.clone().unwrap_or_default()),
+        }
+    }
+}
+
+impl From<usize> for PropertyKey {
+    fn from(index: usize) -> Self {
+        PropertyKey::String(Rc::from(index.to_string()))
+    }
+}
+
+impl From<&str> for PropertyKey {
+    fn from(key: &str) -> Self {
+        PropertyKey::String(Rc::from(key))
+    }
+}
+
+impl From<&Rc<Symbol>> for PropertyKey {
+    fn from(symbol: &Rc<Symbol>) -> Self {
+        PropertyKey::Symbol(symbol.clone())
+    }
+}
+
+// Symbols are compared by identity, strings by their contents
+impl PartialEq for PropertyKey {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PropertyKey::String(left), PropertyKey::String(right)) => left == right,
+            (PropertyKey::Symbol(left), PropertyKey::Symbol(right)) => Rc::ptr_eq(left, right),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for PropertyKey {}
+
+impl Hash for PropertyKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            PropertyKey::String(key) => key.hash(state),
+            PropertyKey::Symbol(symbol) => Rc::as_ptr(symbol).hash(state),
+        }
+    }
+}
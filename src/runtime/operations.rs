@@ -0,0 +1,902 @@
+// The abstract operations from the spec that convert and compare values, shared by operators and built-ins
+use std::{cmp::Ordering, rc::Rc};
+
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive as _, Zero};
+
+use super::{nodes::{FunctionCallType, RuntimeResult}, object::{get, has_in_prototype_chain, set, Object, ObjectRef, PropertyDescriptor, PropertyKey}, parsers::call_expression::call_function, value::Value, Runtime};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PreferredType {
+    Default,
+    Number,
+    String,
+}
+
+pub fn to_primitive(runtime: &mut Runtime, value: &Value, preferred_type: PreferredType) -> RuntimeResult<Value> {
+    let object = match value {
+        Value::Object(object) => object.clone(),
+        _ => return Ok(value.clone()),
+    };
+
+    // An object can take over the conversion with a `[Symbol.toPrimitive]` method, which gets the hint as a string
+    let key = PropertyKey::from(&runtime.intrinsics.to_primitive_symbol);
+
+    if let Some(method) = get_method(runtime, value, &key)? {
+        let hint = match preferred_type {
+            PreferredType::Default => "default",
+            PreferredType::Number => "number",
+            PreferredType::String => "string",
+        };
+
+        return match call(runtime, &method, value.clone(), vec![Value::string(hint)])? {
+            Value::Object(_) => runtime.type_error("Cannot convert object to primitive value"),
+            result => Ok(result),
+        };
+    }
+
+    // Only a string hint tries toString first, the default hint behaves like number
+    let method_names = match preferred_type {
+        PreferredType::String => ["toString", "valueOf"],
+        PreferredType::Default | PreferredType::Number => ["valueOf", "toString"],
+    };
+
+    for method_name in method_names {
+        let method = get(runtime, &object, &PropertyKey::from(method_name), value)?;
+
+        if let Some(function) = method.as_function() {
+            let result = call_function(runtime, &function, value.clone(), vec![])?;
+
+            if !matches!(result, Value::Object(_)) {
+                return Ok(result);
+            }
+        }
+    }
+
+    runtime.type_error("Cannot convert object to primitive value")
+}
+
+pub fn to_boolean(value: &Value) -> bool {
+    match value {
+        Value::Undefined | Value::Null => false,
+        Value::Boolean(value) => *value,
+        Value::Number(number) => *number != 0.0 && !number.is_nan(),
+        Value::BigInt(value) => !value.is_zero(),
+        Value::String(value) => !value.is_empty(),
+        Value::Symbol(_) | Value::Object(_) => true,
+    }
+}
+
+pub fn to_number(runtime: &mut Runtime, value: &Value) -> RuntimeResult<f64> {
+    Ok(match value {
+        Value::Undefined => f64::NAN,
+        Value::Null => 0.0,
+        Value::Boolean(value) => if *value { 1.0 } else { 0.0 },
+        Value::Number(number) => *number,
+        Value::String(value) => string_to_number(value),
+        Value::BigInt(_) => return runtime.type_error("Cannot convert a BigInt value to a number"),
+        Value::Symbol(_) => return runtime.type_error("Cannot convert a Symbol value to a number"),
+        Value::Object(_) => {
+            let primitive = to_primitive(runtime, value, PreferredType::Number)?;
+            return to_number(runtime, &primitive);
+        },
+    })
+}
+
+// Like ToNumber, but keeps BigInts as they are
+pub fn to_numeric(runtime: &mut Runtime, value: &Value) -> RuntimeResult<Value> {
+    Ok(match to_primitive(runtime, value, PreferredType::Number)? {
+        Value::BigInt(value) => Value::BigInt(value),
+        primitive => Value::Number(to_number(runtime, &primitive)?),
+    })
+}
+
+// Strings that do not parse are a SyntaxError, other primitives a TypeError
+pub fn to_bigint(runtime: &mut Runtime, value: &Value) -> RuntimeResult<Rc<BigInt>> {
+    match to_primitive(runtime, value, PreferredType::Number)? {
+        Value::BigInt(value) => Ok(value),
+        Value::Boolean(value) => Ok(Rc::new(BigInt::from(value as u8))),
+        Value::String(text) => match string_to_bigint(&text) {
+            Some(value) => Ok(Rc::new(value)),
+            None => runtime.syntax_error(&format!("Cannot convert {} to a BigInt", text)),
+        },
+        primitive => runtime.type_error(&format!("Cannot convert {} to a BigInt", primitive.to_display_string())),
+    }
+}
+
+pub fn to_string(runtime: &mut Runtime, value: &Value) -> RuntimeResult<String> {
+    Ok(match value {
+        Value::Undefined => "undefined".to_string(),
+        Value::Null => "null".to_string(),
+        Value::Boolean(value) => value.to_string(),
+        Value::Number(number) => number_to_string(*number),
+        Value::BigInt(value) => value.to_string(),
+        Value::String(value) => value.to_string(),
+        Value::Symbol(_) => return runtime.type_error("Cannot convert a Symbol value to a string"),
+        Value::Object(_) => {
+            let primitive = to_primitive(runtime, value, PreferredType::String)?;
+            return to_string(runtime, &primitive);
+        },
+    })
+}
+
+pub fn to_property_key(runtime: &mut Runtime, value: &Value) -> RuntimeResult<PropertyKey> {
+    Ok(match to_primitive(runtime, value, PreferredType::String)? {
+        Value::Symbol(symbol) => PropertyKey::Symbol(symbol),
+        key => PropertyKey::from(to_string(runtime, &key)?.as_str()),
+    })
+}
+
+// GetV, reads a property of any value, primitives read from their prototype
+pub fn get_property(runtime: &mut Runtime, value: &Value, key: &PropertyKey) -> RuntimeResult<Value> {
+    match value {
+        Value::Object(object) => return get(runtime, object, key, value),
+        Value::Undefined | Value::Null => {
+            return runtime.type_error(&format!("Cannot read properties of {} (reading '{}')", value.to_display_string(), key));
+        },
+        Value::String(text) => {
+            let property = string_property(text, key);
+
+            if !matches!(property, Value::Undefined) {
+                return Ok(property);
+            }
+        },
+        _ => {},
+    }
+
+    let prototype = runtime.intrinsics.primitive_prototype(value).expect("primitives have a prototype");
+
+    get(runtime, &prototype, key, value)
+}
+
+// Strings expose their length and UTF-16 code units as read-only properties
+pub fn string_property(text: &str, key: &PropertyKey) -> Value {
+    if let PropertyKey::String(name) = key {
+        if name.as_ref() == "length" {
+            return Value::Number(text.encode_utf16().count() as f64);
+        }
+    }
+
+    match key.array_index().and_then(|index| text.encode_utf16().nth(index as usize)) {
+        Some(unit) => Value::string(&String::from_utf16_lossy(&[unit])),
+        None => Value::Undefined,
+    }
+}
+
+// PutValue on a property, failed writes are ignored in sloppy mode and throw in strict mode
+pub fn set_property(runtime: &mut Runtime, base: &Value, key: PropertyKey, value: Value) -> RuntimeResult<()> {
+    let succeeded = match base {
+        Value::Object(object) => set(runtime, object, key.clone(), value, base)?,
+        Value::Undefined | Value::Null => {
+            return runtime.type_error(&format!("Cannot set properties of {} (setting '{}')", base.to_display_string(), key));
+        },
+        _ => false,
+    };
+
+    if !succeeded && runtime.is_strict() {
+        return runtime.type_error(&format!("Cannot assign to read only property '{}' of {}", key, base.to_display_string()));
+    }
+
+    Ok(())
+}
+
+// InstanceofOperator, a Symbol.hasInstance method on the right side decides before the prototype chain does
+pub fn instance_of(runtime: &mut Runtime, value: &Value, target: &Value) -> RuntimeResult<bool> {
+    let object = match target {
+        Value::Object(object) => object,
+        _ => return runtime.type_error("Right-hand side of 'instanceof' is not an object"),
+    };
+
+    let key = PropertyKey::Symbol(runtime.intrinsics.has_instance_symbol.clone());
+
+    match get(runtime, object, &key, target)? {
+        Value::Undefined | Value::Null => {},
+        method => {
+            let result = call(runtime, &method, target.clone(), vec![value.clone()])?;
+            return Ok(to_boolean(&result));
+        },
+    }
+
+    if !is_callable(target) {
+        return runtime.type_error("Right-hand side of 'instanceof' is not callable");
+    }
+
+    ordinary_has_instance(runtime, target, value)
+}
+
+// OrdinaryHasInstance, whether `constructor.prototype` is in the prototype chain of the value
+pub fn ordinary_has_instance(runtime: &mut Runtime, constructor: &Value, value: &Value) -> RuntimeResult<bool> {
+    let (object, function) = match (constructor, constructor.as_function()) {
+        (Value::Object(object), Some(function)) => (object, function),
+        _ => return Ok(false),
+    };
+
+    // Bound functions answer for their target
+    if let FunctionCallType::BoundFunction(bound) = &function.function_type {
+        return instance_of(runtime, value, &bound.target);
+    }
+
+    let instance = match value {
+        Value::Object(instance) => instance,
+        _ => return Ok(false),
+    };
+
+    match get(runtime, object, &PropertyKey::from("prototype"), constructor)? {
+        Value::Object(prototype) => Ok(has_in_prototype_chain(instance, &prototype)),
+        _ => runtime.type_error("Function has non-object prototype in instanceof check"),
+    }
+}
+
+// Call, for built-ins that invoke a value they were handed
+pub fn call(runtime: &mut Runtime, callee: &Value, this_value: Value, arguments: Vec<Value>) -> RuntimeResult<Value> {
+    match callee.as_function() {
+        Some(function) => call_function(runtime, &function, this_value, arguments),
+        None => runtime.type_error(&format!("{} is not a function", callee.to_display_string())),
+    }
+}
+
+pub fn is_callable(value: &Value) -> bool {
+    value.as_function().is_some()
+}
+
+// The result of `typeof`, callable objects are functions
+pub fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Undefined => "undefined",
+        Value::Null => "object",
+        Value::Boolean(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::BigInt(_) => "bigint",
+        Value::String(_) => "string",
+        Value::Symbol(_) => "symbol",
+        Value::Object(_) if is_callable(value) => "function",
+        Value::Object(_) => "object",
+    }
+}
+
+// ToObject, without wrapper objects a primitive becomes a plain object on its prototype.
+// Strings copy their indices and length so generic methods can read them.
+pub fn to_object(runtime: &mut Runtime, value: &Value) -> RuntimeResult<ObjectRef> {
+    let prototype = match value {
+        Value::Object(object) => return Ok(object.clone()),
+        Value::Undefined | Value::Null => return runtime.type_error("Cannot convert undefined or null to object"),
+        _ => runtime.intrinsics.primitive_prototype(value).expect("primitives have a prototype"),
+    };
+
+    let object = Object::new_ordinary(Some(prototype));
+
+    if let Value::String(text) = value {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let mut object = object.borrow_mut();
+
+        for (index, unit) in units.iter().enumerate() {
+            object.define_own_property(PropertyKey::from(index), PropertyDescriptor {
+                value: Some(Value::string(&String::from_utf16_lossy(&[*unit]))),
+                writable: Some(false),
+                enumerable: Some(true),
+                configurable: Some(false),
+                ..Default::default()
+            });
+        }
+
+        object.define_own_property(PropertyKey::from("length"), PropertyDescriptor {
+            value: Some(Value::Number(units.len() as f64)),
+            writable: Some(false),
+            enumerable: Some(false),
+            configurable: Some(false),
+            ..Default::default()
+        });
+    }
+
+    Ok(object)
+}
+
+pub fn is_array(value: &Value) -> bool {
+    match value {
+        Value::Object(object) => object.borrow().is_array(),
+        _ => false,
+    }
+}
+
+// LengthOfArrayLike
+pub fn length_of_array_like(runtime: &mut Runtime, object: &ObjectRef) -> RuntimeResult<usize> {
+    let length = get(runtime, object, &PropertyKey::from("length"), &Value::Object(object.clone()))?;
+    to_length(runtime, &length)
+}
+
+// CreateArrayFromList
+pub fn create_array_from_list(runtime: &Runtime, values: Vec<Value>) -> Value {
+    let array = Object::new_array(&runtime.intrinsics.array_prototype);
+
+    {
+        let mut array = array.borrow_mut();
+
+        for (index, value) in values.into_iter().enumerate() {
+            array.create_data_property(PropertyKey::from(index), value);
+        }
+    }
+
+    Value::Object(array)
+}
+
+// CopyDataProperties, the own enumerable properties of `source` other than `excluded`, read through their getters
+pub fn copy_data_properties(runtime: &mut Runtime, target: &ObjectRef, source: &Value, excluded: &[PropertyKey]) -> RuntimeResult<()> {
+    if let Value::Undefined | Value::Null = source {
+        return Ok(());
+    }
+
+    let source = to_object(runtime, source)?;
+    let keys = source.borrow().own_property_keys();
+
+    for key in keys {
+        let enumerable = source.borrow().get_own_property(&key).is_some_and(|property| property.enumerable());
+
+        if enumerable && !excluded.contains(&key) {
+            let value = get(runtime, &source, &key, &Value::Object(source.clone()))?;
+            target.borrow_mut().create_data_property(key, value);
+        }
+    }
+
+    Ok(())
+}
+
+// An iterator and its `next` method, which is only read once when iteration starts
+pub struct IteratorRecord {
+    pub iterator: Value,
+    pub next_method: Value,
+}
+
+// GetIterator, calls the `[Symbol.iterator]` method of the value
+pub fn get_iterator(runtime: &mut Runtime, value: &Value) -> RuntimeResult<IteratorRecord> {
+    let key = PropertyKey::from(&runtime.intrinsics.iterator_symbol);
+
+    let method = match value {
+        Value::Undefined | Value::Null => None,
+        value => get_method(runtime, value, &key)?,
+    };
+
+    match method {
+        Some(method) => get_iterator_from_method(runtime, value, &method),
+        None => runtime.type_error(&format!("{} is not iterable", value.to_display_string())),
+    }
+}
+
+pub fn get_iterator_from_method(runtime: &mut Runtime, value: &Value, method: &Value) -> RuntimeResult<IteratorRecord> {
+    let iterator = call(runtime, method, value.clone(), vec![])?;
+
+    if !matches!(iterator, Value::Object(_)) {
+        return runtime.type_error("Result of the Symbol.iterator method is not an object");
+    }
+
+    let next_method = get_property(runtime, &iterator, &PropertyKey::from("next"))?;
+
+    Ok(IteratorRecord { iterator, next_method })
+}
+
+// IteratorStepValue, None once the iterator says it is done
+pub fn iterator_step_value(runtime: &mut Runtime, record: &IteratorRecord) -> RuntimeResult<Option<Value>> {
+    let result = call(runtime, &record.next_method, record.iterator.clone(), vec![])?;
+
+    if !matches!(result, Value::Object(_)) {
+        return runtime.type_error(&format!("Iterator result {} is not an object", result.to_display_string()));
+    }
+
+    let done = get_property(runtime, &result, &PropertyKey::from("done"))?;
+
+    if to_boolean(&done) {
+        return Ok(None);
+    }
+
+    Ok(Some(get_property(runtime, &result, &PropertyKey::from("value"))?))
+}
+
+// IteratorClose, lets the iterator clean up when iteration stops early.
+// An exception that stopped the iteration wins over anything `return` does.
+pub fn iterator_close<T>(runtime: &mut Runtime, record: &IteratorRecord, completion: RuntimeResult<T>) -> RuntimeResult<T> {
+    let result = match get_method(runtime, &record.iterator, &PropertyKey::from("return")) {
+        Ok(Some(method)) => call(runtime, &method, record.iterator.clone(), vec![]),
+        Ok(None) => return completion,
+        Err(error) => Err(error),
+    };
+
+    let completion = completion?;
+
+    match result? {
+        Value::Object(_) => Ok(completion),
+        result => runtime.type_error(&format!("Iterator result {} is not an object", result.to_display_string())),
+    }
+}
+
+// CreateIterResultObject, what `next` returns
+pub fn create_iter_result_object(runtime: &Runtime, value: Value, done: bool) -> Value {
+    let result = Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()));
+
+    {
+        let mut result = result.borrow_mut();
+
+        result.create_data_property(PropertyKey::from("value"), value);
+        result.create_data_property(PropertyKey::from("done"), Value::Boolean(done));
+    }
+
+    Value::Object(result)
+}
+
+// GetMethod, undefined and null mean there is no method
+pub fn get_method(runtime: &mut Runtime, value: &Value, key: &PropertyKey) -> RuntimeResult<Option<Value>> {
+    match get_property(runtime, value, key)? {
+        Value::Undefined | Value::Null => Ok(None),
+        method if is_callable(&method) => Ok(Some(method)),
+        method => runtime.type_error(&format!("{} is not a function", method.to_display_string())),
+    }
+}
+
+// IteratorToList over the iterator of the value, for spread syntax and Array.from
+pub fn iterable_to_list(runtime: &mut Runtime, value: &Value) -> RuntimeResult<Vec<Value>> {
+    let record = get_iterator(runtime, value)?;
+    let mut values = Vec::new();
+
+    while let Some(value) = iterator_step_value(runtime, &record)? {
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+pub fn to_int32(number: f64) -> i32 {
+    to_uint32(number) as i32
+}
+
+pub fn to_uint32(number: f64) -> u32 {
+    if !number.is_finite() {
+        return 0;
+    }
+
+    number.trunc().rem_euclid(4294967296.0) as u32
+}
+
+// ToLength, the length of an array-like, clamped to the integers an index can reach
+pub fn to_length(runtime: &mut Runtime, value: &Value) -> RuntimeResult<usize> {
+    let length = to_number(runtime, value)?;
+
+    if length.is_nan() || length <= 0.0 {
+        return Ok(0);
+    }
+
+    Ok(length.min(9007199254740991.0).trunc() as usize)
+}
+
+// ToIntegerOrInfinity, NaN becomes 0 and fractions are cut off towards zero
+pub fn to_integer_or_infinity(runtime: &mut Runtime, value: &Value) -> RuntimeResult<f64> {
+    let number = to_number(runtime, value)?;
+
+    if number.is_nan() {
+        return Ok(0.0);
+    }
+
+    Ok(number.trunc() + 0.0)
+}
+
+// Resolves a relative index argument like the `start` of `slice`, negative values count back from `length`
+pub fn relative_index(runtime: &mut Runtime, value: &Value, length: usize, default: usize) -> RuntimeResult<usize> {
+    if matches!(value, Value::Undefined) {
+        return Ok(default);
+    }
+
+    let relative = to_integer_or_infinity(runtime, value)?;
+
+    Ok(if relative < 0.0 {
+        (length as f64 + relative).max(0.0) as usize
+    } else {
+        relative.min(length as f64) as usize
+    })
+}
+
+// The whitespace and line terminators the spec allows around numeric strings
+fn is_whitespace(char: char) -> bool {
+    matches!(char, '\u{9}' | '\u{B}' | '\u{C}' | ' ' | '\u{A0}' | '\u{FEFF}' | '\n' | '\r' | '\u{2028}' | '\u{2029}')
+        || char.is_whitespace()
+}
+
+pub fn string_to_number(text: &str) -> f64 {
+    let text = text.trim_matches(is_whitespace);
+
+    if text.is_empty() {
+        return 0.0;
+    }
+
+    // Prefixed integers cannot carry a sign
+    if let Some(value) = parse_prefixed_integer(text) {
+        return value.and_then(|value| value.to_f64()).unwrap_or(f64::NAN);
+    }
+
+    let (sign, unsigned) = match text.as_bytes()[0] {
+        b'+' => (1.0, &text[1..]),
+        b'-' => (-1.0, &text[1..]),
+        _ => (1.0, text),
+    };
+
+    if unsigned == "Infinity" {
+        return sign * f64::INFINITY;
+    }
+
+    if !is_decimal_literal(unsigned) {
+        return f64::NAN;
+    }
+
+    sign * unsigned.parse::<f64>().unwrap_or(f64::NAN)
+}
+
+// Some(None) means the text has a radix prefix but invalid digits
+fn parse_prefixed_integer(text: &str) -> Option<Option<BigInt>> {
+    let radix = match text.get(..2)? {
+        "0x" | "0X" => 16,
+        "0o" | "0O" => 8,
+        "0b" | "0B" => 2,
+        _ => return None,
+    };
+
+    let digits = &text[2..];
+
+    Some(BigInt::parse_bytes(digits.as_bytes(), radix).filter(|_| {
+        digits.chars().all(|char| char.is_digit(radix))
+    }))
+}
+
+// StrUnsignedDecimalLiteral: digits with an optional fraction and exponent, at least one digit in the mantissa
+fn is_decimal_literal(text: &str) -> bool {
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(index) => (&text[..index], Some(&text[index + 1..])),
+        None => (text, None),
+    };
+
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+        None => (mantissa, ""),
+    };
+
+    let is_digits = |text: &str| text.chars().all(|char| char.is_ascii_digit());
+
+    if integer.len() + fraction.len() == 0 || !is_digits(integer) || !is_digits(fraction) {
+        return false;
+    }
+
+    match exponent {
+        None => true,
+        Some(exponent) => {
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            !digits.is_empty() && is_digits(digits)
+        },
+    }
+}
+
+pub fn string_to_bigint(text: &str) -> Option<BigInt> {
+    let text = text.trim_matches(is_whitespace);
+
+    if text.is_empty() {
+        return Some(BigInt::zero());
+    }
+
+    if let Some(value) = parse_prefixed_integer(text) {
+        return value;
+    }
+
+    let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+
+    if digits.is_empty() || !digits.chars().all(|char| char.is_ascii_digit()) {
+        return None;
+    }
+
+    text.parse::<BigInt>().ok()
+}
+
+// Number::toString, using the shortest digits that round trip and exponents outside of 1e-7..1e21
+pub fn number_to_string(number: f64) -> String {
+    if number.is_nan() {
+        return "NaN".to_string();
+    }
+
+    if number == 0.0 {
+        return "0".to_string();
+    }
+
+    if number.is_infinite() {
+        return if number > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+
+    if number < 0.0 {
+        return format!("-{}", number_to_string(-number));
+    }
+
+    // Rust's exponent formatting already gives the shortest digits, e.g. "1.2345e-7"
+    let formatted = format!("{:e}", number);
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|char| *char != '.').collect();
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap() + 1;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { "-" } else { "+" };
+        let exponent = (n - 1).abs();
+
+        match k {
+            1 => format!("{}e{}{}", digits, sign, exponent),
+            _ => format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, exponent),
+        }
+    }
+}
+
+// IsStrictlyEqual, used by ===
+pub fn is_strictly_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left == right,
+        _ => same_value_non_number(left, right),
+    }
+}
+
+// IsLooselyEqual, used by ==
+pub fn is_loosely_equal(runtime: &mut Runtime, left: &Value, right: &Value) -> RuntimeResult<bool> {
+    Ok(match (left, right) {
+        (Value::Undefined | Value::Null, Value::Undefined | Value::Null) => true,
+        (Value::Number(left), Value::String(right)) => *left == string_to_number(right),
+        (Value::String(left), Value::Number(right)) => string_to_number(left) == *right,
+        (Value::BigInt(left), Value::String(right)) | (Value::String(right), Value::BigInt(left)) => {
+            string_to_bigint(right).is_some_and(|right| left.as_ref() == &right)
+        },
+        (Value::Boolean(left), right) => {
+            let left = Value::Number(if *left { 1.0 } else { 0.0 });
+            return is_loosely_equal(runtime, &left, right);
+        },
+        (left, Value::Boolean(right)) => {
+            let right = Value::Number(if *right { 1.0 } else { 0.0 });
+            return is_loosely_equal(runtime, left, &right);
+        },
+        (Value::Object(_), Value::Number(_) | Value::String(_) | Value::BigInt(_) | Value::Symbol(_)) => {
+            let left = to_primitive(runtime, left, PreferredType::Default)?;
+            return is_loosely_equal(runtime, &left, right);
+        },
+        (Value::Number(_) | Value::String(_) | Value::BigInt(_) | Value::Symbol(_), Value::Object(_)) => {
+            let right = to_primitive(runtime, right, PreferredType::Default)?;
+            return is_loosely_equal(runtime, left, &right);
+        },
+        (Value::BigInt(left), Value::Number(right)) | (Value::Number(right), Value::BigInt(left)) => {
+            compare_bigint_to_number(left, *right) == Some(Ordering::Equal)
+        },
+        _ => is_strictly_equal(left, right),
+    })
+}
+
+// SameValue, like === but NaN equals itself and 0 differs from -0
+pub fn same_value(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => {
+            (left.is_nan() && right.is_nan()) || (left == right && left.is_sign_negative() == right.is_sign_negative())
+        },
+        _ => same_value_non_number(left, right),
+    }
+}
+
+// SameValueZero, like SameValue but 0 equals -0
+pub fn same_value_zero(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => (left.is_nan() && right.is_nan()) || left == right,
+        _ => same_value_non_number(left, right),
+    }
+}
+
+fn same_value_non_number(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Undefined, Value::Undefined) | (Value::Null, Value::Null) => true,
+        (Value::Boolean(left), Value::Boolean(right)) => left == right,
+        (Value::BigInt(left), Value::BigInt(right)) => left == right,
+        (Value::String(left), Value::String(right)) => left == right,
+        (Value::Symbol(left), Value::Symbol(right)) => Rc::ptr_eq(left, right),
+        (Value::Object(left), Value::Object(right)) => Rc::ptr_eq(left, right),
+        _ => false,
+    }
+}
+
+// IsLessThan after both sides are primitives, None when either side is NaN or not comparable
+pub fn compare_values(runtime: &mut Runtime, left: &Value, right: &Value) -> RuntimeResult<Option<Ordering>> {
+    Ok(match (left, right) {
+        // Strings compare by UTF-16 code units, not by code points
+        (Value::String(left), Value::String(right)) => Some(left.encode_utf16().cmp(right.encode_utf16())),
+        (Value::BigInt(left), Value::String(right)) => {
+            string_to_bigint(right).map(|right| left.as_ref().cmp(&right))
+        },
+        (Value::String(left), Value::BigInt(right)) => {
+            string_to_bigint(left).map(|left| left.cmp(right.as_ref()))
+        },
+        _ => {
+            let left = to_numeric(runtime, left)?;
+            let right = to_numeric(runtime, right)?;
+
+            match (&left, &right) {
+                (Value::BigInt(left), Value::BigInt(right)) => Some(left.cmp(right)),
+                (Value::BigInt(left), Value::Number(right)) => compare_bigint_to_number(left, *right),
+                (Value::Number(left), Value::BigInt(right)) => {
+                    compare_bigint_to_number(right, *left).map(Ordering::reverse)
+                },
+                (Value::Number(left), Value::Number(right)) => left.partial_cmp(right),
+                _ => unreachable!(),
+            }
+        },
+    })
+}
+
+// Compares the mathematical values, without rounding the BigInt to a double
+fn compare_bigint_to_number(bigint: &BigInt, number: f64) -> Option<Ordering> {
+    if number.is_nan() {
+        return None;
+    }
+
+    if number.is_infinite() {
+        return Some(if number > 0.0 { Ordering::Less } else { Ordering::Greater });
+    }
+
+    let floor = BigInt::from_f64(number.floor()).unwrap();
+
+    match bigint.cmp(&floor) {
+        Ordering::Equal if number.fract() != 0.0 => Some(Ordering::Less),
+        ordering => Some(ordering),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::run_test_script;
+
+    use super::*;
+
+    fn global(runtime: &Runtime, name: &str) -> Value {
+        runtime.variable(name).ok().flatten().unwrap_or_else(|| panic!("{} is not defined", name))
+    }
+
+    fn primitive_text(runtime: &mut Runtime, value: &Value, preferred_type: PreferredType) -> String {
+        match to_primitive(runtime, value, preferred_type) {
+            Ok(value) => value.to_display_string(),
+            Err(error) => panic!("ToPrimitive threw {}", error.to_display_string()),
+        }
+    }
+
+    #[test]
+    fn string_to_number_follows_the_string_numeric_literal_grammar() {
+        assert_eq!(string_to_number(""), 0.0);
+        assert_eq!(string_to_number(" \n\t\u{a0}\u{feff}"), 0.0);
+        assert_eq!(string_to_number("  12  "), 12.0);
+        assert_eq!(string_to_number("+1.5"), 1.5);
+        assert_eq!(string_to_number(".5"), 0.5);
+        assert_eq!(string_to_number("5."), 5.0);
+        assert_eq!(string_to_number("1e3"), 1000.0);
+        assert_eq!(string_to_number("-1E-2"), -0.01);
+        assert_eq!(string_to_number("0x1F"), 31.0);
+        assert_eq!(string_to_number("0b101"), 5.0);
+        assert_eq!(string_to_number("0o17"), 15.0);
+        assert_eq!(string_to_number("Infinity"), f64::INFINITY);
+        assert_eq!(string_to_number("-Infinity"), f64::NEG_INFINITY);
+        assert!(string_to_number("-0").is_sign_negative());
+
+        for text in ["infinity", "12px", "1_000", "-0x10", "0x", "1e", ".", "e5", "0b2", "NaN"] {
+            assert!(string_to_number(text).is_nan(), "{:?} should be NaN", text);
+        }
+    }
+
+    #[test]
+    fn number_to_string_uses_the_shortest_round_trip_digits() {
+        let cases = [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (1.5, "1.5"),
+            (-1.5, "-1.5"),
+            (100.0, "100"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (9007199254740992.0, "9007199254740992"),
+            (123456789012345680000.0, "123456789012345680000"),
+            (1e21, "1e+21"),
+            (1.5e300, "1.5e+300"),
+            (0.000001, "0.000001"),
+            (1e-7, "1e-7"),
+            (1.2345e-7, "1.2345e-7"),
+            (f64::NAN, "NaN"),
+            (f64::INFINITY, "Infinity"),
+            (f64::NEG_INFINITY, "-Infinity"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (5e-324, "5e-324"),
+        ];
+
+        for (number, expected) in cases {
+            assert_eq!(number_to_string(number), expected);
+        }
+    }
+
+    #[test]
+    fn strict_equality_compares_numbers_by_value_and_objects_by_identity() {
+        assert!(!is_strictly_equal(&Value::Number(f64::NAN), &Value::Number(f64::NAN)));
+        assert!(is_strictly_equal(&Value::Number(0.0), &Value::Number(-0.0)));
+        assert!(is_strictly_equal(&Value::string("a"), &Value::string("a")));
+        assert!(!is_strictly_equal(&Value::Number(1.0), &Value::string("1")));
+        assert!(!is_strictly_equal(&Value::Null, &Value::Undefined));
+        assert!(is_strictly_equal(&Value::BigInt(Rc::new(BigInt::from(2))), &Value::BigInt(Rc::new(BigInt::from(2)))));
+
+        let object = Object::new_ordinary(None);
+        assert!(is_strictly_equal(&Value::Object(object.clone()), &Value::Object(object)));
+        assert!(!is_strictly_equal(&Value::Object(Object::new_ordinary(None)), &Value::Object(Object::new_ordinary(None))));
+    }
+
+    #[test]
+    fn loose_equality_converts_primitives_and_objects() {
+        run_test_script("
+            var object = { valueOf() { return 42; } };
+            var results = [
+                null == undefined, '1' == 1, true == 1, '' == 0, '0x10' == 16, 1n == 1, '10' == 10n,
+                object == 42, object == '42', null == 0, undefined == 0, NaN == NaN, null == false, 'a' == 'b'
+            ];
+        ", |runtime| {
+            let results = global(runtime, "results").to_display_string();
+            assert_eq!(results, "[ true, true, true, true, true, true, true, true, true, false, false, false, false, false ]");
+        });
+    }
+
+    #[test]
+    fn same_value_distinguishes_zeros_and_same_value_zero_does_not() {
+        let nan = Value::Number(f64::NAN);
+        let zero = Value::Number(0.0);
+        let negative_zero = Value::Number(-0.0);
+
+        assert!(same_value(&nan, &nan));
+        assert!(!same_value(&zero, &negative_zero));
+        assert!(same_value(&Value::string("x"), &Value::string("x")));
+        assert!(!same_value(&Value::Number(1.0), &Value::string("1")));
+
+        assert!(same_value_zero(&nan, &nan));
+        assert!(same_value_zero(&zero, &negative_zero));
+        assert!(!same_value_zero(&Value::Null, &Value::Undefined));
+    }
+
+    #[test]
+    fn to_primitive_tries_value_of_and_to_string_in_hint_order() {
+        run_test_script("
+            var log = [];
+            var object = {
+                valueOf() { log.push('valueOf'); return {}; },
+                toString() { log.push('toString'); return 'text'; }
+            };
+        ", |runtime| {
+            let object = global(runtime, "object");
+
+            assert_eq!(primitive_text(runtime, &object, PreferredType::Number), "text");
+            assert_eq!(primitive_text(runtime, &object, PreferredType::String), "text");
+            assert_eq!(global(runtime, "log").to_display_string(), "[ 'valueOf', 'toString', 'toString' ]");
+        });
+    }
+
+    #[test]
+    fn to_primitive_calls_symbol_to_primitive_with_the_hint() {
+        run_test_script("
+            var object = {
+                [Symbol.toPrimitive](hint) { return hint; },
+                valueOf() { return 'valueOf'; }
+            };
+            var bad = { [Symbol.toPrimitive]() { return {}; } };
+            var neither = { valueOf() { return {}; }, toString() { return {}; } };
+            var added = object + '';
+        ", |runtime| {
+            let object = global(runtime, "object");
+
+            assert_eq!(primitive_text(runtime, &object, PreferredType::Number), "number");
+            assert_eq!(primitive_text(runtime, &object, PreferredType::String), "string");
+            assert_eq!(global(runtime, "added").to_display_string(), "default");
+
+            let bad = global(runtime, "bad");
+            assert!(to_primitive(runtime, &bad, PreferredType::Default).is_err());
+
+            let neither = global(runtime, "neither");
+            assert!(to_primitive(runtime, &neither, PreferredType::Default).is_err());
+        });
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{ast::nodes::{ArrayExpression, Expression}, runtime::{nodes::RuntimeResult, object::{Object, PropertyDescriptor, PropertyKey}, operations::iterable_to_list, Runtime, Value}};
+
+use super::expression::parse_expression;
+
+pub fn parse_array_expression(runtime: &mut Runtime, expression: &ArrayExpression) -> RuntimeResult<Value> {
+    let array = Object::new_array(&runtime.intrinsics.array_prototype);
+    let mut index = 0;
+
+    // Holes only move the index along, spread elements add every value of their iterable
+    for element in &expression.elements {
+        let values = match element {
+            None => {
+                index += 1;
+                continue;
+            },
+            Some(Expression::SpreadElement(spread)) => {
+                let iterable = parse_expression(runtime, &spread.argument)?;
+                iterable_to_list(runtime, &iterable)?
+            },
+            Some(element) => vec![parse_expression(runtime, element)?],
+        };
+
+        for value in values {
+            array.borrow_mut().create_data_property(PropertyKey::from(index), value);
+            index += 1;
+        }
+    }
+
+    // Trailing holes still count towards the length
+    array.borrow_mut().define_own_property(PropertyKey::from("length"), PropertyDescriptor {
+        value: Some(Value::Number(index as f64)),
+        ..Default::default()
+    });
+
+    Ok(Value::Object(array))
+}
@@ -0,0 +1,59 @@
+use crate::{ast::nodes::{AssignmentExpression, Pattern}, runtime::{nodes::RuntimeResult, operations::to_boolean, reference::{evaluate_member, get_value, put_value, Reference}, Runtime, Value}};
+
+use super::{binary_expression::apply_binary_operator, expression::{parse_expression, parse_named_expression}, pattern::bind_pattern};
+
+pub fn parse_assignment_expression(runtime: &mut Runtime, expression: &AssignmentExpression) -> RuntimeResult<Value> {
+    // The target is resolved before the right side runs, and only once
+    let reference = match &expression.left {
+        Pattern::Identifier(identifier) => Reference::Binding(identifier.name.clone()),
+        Pattern::MemberExpression(member) => evaluate_member(runtime, member)?,
+        // Destructuring assigns each target in turn, the value of the expression is the whole right side
+        pattern => {
+            let value = parse_expression(runtime, &expression.right)?;
+            bind_pattern(runtime, pattern, value.clone(), None)?;
+
+            return Ok(value);
+        },
+    };
+
+    let value = match expression.operator.as_str() {
+        "=" => assigned_value(runtime, expression)?,
+        "&&=" | "||=" => {
+            let current = get_value(runtime, &reference)?;
+
+            // Logical assignments only assign when the left side does not decide the result
+            if to_boolean(&current) == (expression.operator == "||=") {
+                return Ok(current);
+            }
+
+            assigned_value(runtime, expression)?
+        },
+        "??=" => {
+            let current = get_value(runtime, &reference)?;
+
+            if !matches!(current, Value::Undefined | Value::Null) {
+                return Ok(current);
+            }
+
+            assigned_value(runtime, expression)?
+        },
+        operator => {
+            let current = get_value(runtime, &reference)?;
+            let right = parse_expression(runtime, &expression.right)?;
+
+            apply_binary_operator(runtime, operator.trim_end_matches('='), &current, &right)?
+        }
+    };
+
+    put_value(runtime, reference, value.clone())?;
+
+    Ok(value)
+}
+
+// `f = () => {}` names the function after the variable, but `o.f = () => {}` does not
+fn assigned_value(runtime: &mut Runtime, expression: &AssignmentExpression) -> RuntimeResult<Value> {
+    match &expression.left {
+        Pattern::Identifier(identifier) => parse_named_expression(runtime, &expression.right, &identifier.name),
+        _ => parse_expression(runtime, &expression.right),
+    }
+}
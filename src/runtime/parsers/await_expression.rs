@@ -0,0 +1,91 @@
+use crate::{ast::nodes::AwaitExpression, runtime::{builtins::promise_resolve, coroutine::{suspend, ResumeAction, Suspension}, nodes::RuntimeResult, object::PropertyKey, operations::{call, get_iterator, get_iterator_from_method, get_method, get_property, iterator_close, to_boolean, IteratorRecord}, Runtime, Value}};
+
+use super::expression::parse_expression;
+
+// An async iterator, or a sync one standing in for it whose values are awaited (CreateAsyncFromSyncIterator)
+pub struct AsyncIteratorRecord {
+    pub record: IteratorRecord,
+    pub from_sync: bool,
+}
+
+pub fn parse_await_expression(runtime: &mut Runtime, expression: &AwaitExpression) -> RuntimeResult<Value> {
+    let value = parse_expression(runtime, &expression.argument)?;
+    await_value(runtime, value)
+}
+
+// Await, suspends the async function until the promise for `value` settles.
+// A rejection is thrown where the function continues.
+pub fn await_value(runtime: &mut Runtime, value: Value) -> RuntimeResult<Value> {
+    let promise = promise_resolve(runtime, None, value)?;
+
+    match suspend(runtime, Suspension::Await(promise)) {
+        ResumeAction::Next(value) => Ok(value),
+        ResumeAction::Throw(reason) => Err(reason),
+        // Only generators are resumed with `return`, and only where they yield
+        ResumeAction::Return(_) => unreachable!(),
+    }
+}
+
+// GetIterator with the async hint, `[Symbol.asyncIterator]` is used before `[Symbol.iterator]`
+pub fn get_async_iterator(runtime: &mut Runtime, value: &Value) -> RuntimeResult<AsyncIteratorRecord> {
+    let key = PropertyKey::from(&runtime.intrinsics.async_iterator_symbol);
+
+    let method = match value {
+        Value::Undefined | Value::Null => None,
+        value => get_method(runtime, value, &key)?,
+    };
+
+    Ok(match method {
+        Some(method) => AsyncIteratorRecord { record: get_iterator_from_method(runtime, value, &method)?, from_sync: false },
+        None => AsyncIteratorRecord { record: get_iterator(runtime, value)?, from_sync: true },
+    })
+}
+
+// The next value, None once the iterator is done. A sync iterator whose value rejects is closed.
+pub fn async_iterator_step_value(runtime: &mut Runtime, iterator: &AsyncIteratorRecord) -> RuntimeResult<Option<Value>> {
+    let record = &iterator.record;
+    let result = call(runtime, &record.next_method, record.iterator.clone(), vec![])?;
+
+    let result = match iterator.from_sync {
+        true => result,
+        false => await_value(runtime, result)?,
+    };
+
+    if !matches!(result, Value::Object(_)) {
+        return runtime.type_error(&format!("Iterator result {} is not an object", result.to_display_string()));
+    }
+
+    let done = get_property(runtime, &result, &PropertyKey::from("done"))?;
+
+    if to_boolean(&done) {
+        return Ok(None);
+    }
+
+    let value = get_property(runtime, &result, &PropertyKey::from("value"))?;
+
+    match iterator.from_sync {
+        true => match await_value(runtime, value) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => iterator_close(runtime, record, Err(error)),
+        },
+        false => Ok(Some(value)),
+    }
+}
+
+// AsyncIteratorClose, like IteratorClose but the result of `return` is awaited
+pub fn async_iterator_close<T>(runtime: &mut Runtime, iterator: &AsyncIteratorRecord, completion: RuntimeResult<T>) -> RuntimeResult<T> {
+    let record = &iterator.record;
+
+    let result = match get_method(runtime, &record.iterator, &PropertyKey::from("return")) {
+        Ok(Some(method)) => call(runtime, &method, record.iterator.clone(), vec![]).and_then(|result| await_value(runtime, result)),
+        Ok(None) => return completion,
+        Err(error) => Err(error),
+    };
+
+    let completion = completion?;
+
+    match result? {
+        Value::Object(_) => Ok(completion),
+        result => runtime.type_error(&format!("Iterator result {} is not an object", result.to_display_string())),
+    }
+}
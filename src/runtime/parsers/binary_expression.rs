@@ -0,0 +1,142 @@
+use std::rc::Rc;
+
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+
+use crate::{ast::nodes::{BinaryExpression, Expression, PrivateIdentifier}, runtime::{nodes::RuntimeResult, object::{find_property, PropertyKey}, operations::{compare_values, instance_of, is_loosely_equal, is_strictly_equal, to_int32, to_numeric, to_primitive, to_property_key, to_string, to_uint32, PreferredType}, Runtime, Value}};
+
+use super::expression::parse_expression;
+
+pub fn parse_binary_expression(runtime: &mut Runtime, expression: &BinaryExpression) -> RuntimeResult<Value> {
+    if let Expression::PrivateIdentifier(identifier) = &expression.left {
+        return parse_private_in(runtime, identifier, &expression.right);
+    }
+
+    let left = parse_expression(runtime, &expression.left)?;
+    let right = parse_expression(runtime, &expression.right)?;
+
+    apply_binary_operator(runtime, &expression.operator, &left, &right)
+}
+
+pub fn apply_binary_operator(runtime: &mut Runtime, operator: &str, left: &Value, right: &Value) -> RuntimeResult<Value> {
+    Ok(match operator {
+        "+" => {
+            let left = to_primitive(runtime, left, PreferredType::Default)?;
+            let right = to_primitive(runtime, right, PreferredType::Default)?;
+
+            if matches!(left, Value::String(_)) || matches!(right, Value::String(_)) {
+                let left = to_string(runtime, &left)?;
+                let right = to_string(runtime, &right)?;
+
+                return Ok(Value::string(&format!("{}{}", left, right)));
+            }
+
+            return apply_numeric_operator(runtime, operator, &left, &right);
+        },
+        "-" | "*" | "/" | "%" | "**" | "&" | "|" | "^" | "<<" | ">>" | ">>>" => {
+            return apply_numeric_operator(runtime, operator, left, right);
+        },
+        "<" | ">" | "<=" | ">=" => {
+            let left = to_primitive(runtime, left, PreferredType::Number)?;
+            let right = to_primitive(runtime, right, PreferredType::Number)?;
+
+            let result = match compare_values(runtime, &left, &right)? {
+                None => false,
+                Some(ordering) => match operator {
+                    "<" => ordering.is_lt(),
+                    ">" => ordering.is_gt(),
+                    "<=" => ordering.is_le(),
+                    _ => ordering.is_ge(),
+                }
+            };
+
+            Value::Boolean(result)
+        },
+        "==" => Value::Boolean(is_loosely_equal(runtime, left, right)?),
+        "!=" => Value::Boolean(!is_loosely_equal(runtime, left, right)?),
+        "===" => Value::Boolean(is_strictly_equal(left, right)),
+        "instanceof" => Value::Boolean(instance_of(runtime, left, right)?),
+        "in" => match right {
+            Value::Object(object) => {
+                let key = to_property_key(runtime, left)?;
+                Value::Boolean(find_property(object, &key).is_some())
+            },
+            _ => {
+                let key = to_string(runtime, left)?;
+                return runtime.type_error(&format!("Cannot use 'in' operator to search for '{}' in {}", key, right.to_display_string()));
+            },
+        },
+        "!==" => Value::Boolean(!is_strictly_equal(left, right)),
+        _ => panic!("Unsupported binary operator: {}", operator)
+    })
+}
+
+// `#x in object`, whether the object has the private element the enclosing class created for `#x`
+fn parse_private_in(runtime: &mut Runtime, identifier: &PrivateIdentifier, right: &Expression) -> RuntimeResult<Value> {
+    let key = match runtime.variable(&identifier.name)? {
+        Some(Value::Symbol(symbol)) => PropertyKey::Symbol(symbol),
+        _ => return runtime.syntax_error(&format!("Private field '{}' must be declared in an enclosing class", identifier.name)),
+    };
+
+    match parse_expression(runtime, right)? {
+        Value::Object(object) => Ok(Value::Boolean(object.borrow().private_element(&key).is_some())),
+        right => runtime.type_error(&format!("Cannot use 'in' operator to search for '{}' in {}", identifier.name, right.to_display_string())),
+    }
+}
+
+fn apply_numeric_operator(runtime: &mut Runtime, operator: &str, left: &Value, right: &Value) -> RuntimeResult<Value> {
+    let left = to_numeric(runtime, left)?;
+    let right = to_numeric(runtime, right)?;
+
+    match (&left, &right) {
+        (Value::Number(left), Value::Number(right)) => Ok(Value::Number(apply_number_operator(operator, *left, *right))),
+        (Value::BigInt(left), Value::BigInt(right)) => apply_bigint_operator(runtime, operator, left, right),
+        _ => runtime.type_error("Cannot mix BigInt and other types, use explicit conversions"),
+    }
+}
+
+fn apply_number_operator(operator: &str, left: f64, right: f64) -> f64 {
+    match operator {
+        "+" => left + right,
+        "-" => left - right,
+        "*" => left * right,
+        "/" => left / right,
+        "%" => left % right,
+        "**" if right.is_nan() || (left.abs() == 1.0 && right.is_infinite()) => f64::NAN,
+        "**" => left.powf(right),
+        "&" => (to_int32(left) & to_int32(right)) as f64,
+        "|" => (to_int32(left) | to_int32(right)) as f64,
+        "^" => (to_int32(left) ^ to_int32(right)) as f64,
+        "<<" => to_int32(left).wrapping_shl(to_uint32(right) & 31) as f64,
+        ">>" => to_int32(left).wrapping_shr(to_uint32(right) & 31) as f64,
+        _ => to_uint32(left).wrapping_shr(to_uint32(right) & 31) as f64,
+    }
+}
+
+fn apply_bigint_operator(runtime: &Runtime, operator: &str, left: &BigInt, right: &BigInt) -> RuntimeResult<Value> {
+    let result = match operator {
+        "+" => left + right,
+        "-" => left - right,
+        "*" => left * right,
+        "/" | "%" if right.is_zero() => return runtime.range_error("Division by zero"),
+        "/" => left / right,
+        "%" => left % right,
+        "**" => match right.to_u32() {
+            Some(exponent) => left.pow(exponent),
+            None => return runtime.range_error("Exponent must be non-negative"),
+        },
+        "&" => left & right,
+        "|" => left | right,
+        "^" => left ^ right,
+        "<<" | ">>" => {
+            // A negative shift goes the other way
+            let amount = right.to_isize().unwrap_or(0);
+            let amount = if operator == "<<" { amount } else { -amount };
+
+            if amount >= 0 { left << amount as usize } else { left >> (-amount) as usize }
+        },
+        _ => return runtime.type_error("BigInts have no unsigned right shift, use >> instead"),
+    };
+
+    Ok(Value::BigInt(Rc::new(result)))
+}
@@ -0,0 +1,19 @@
+use crate::{ast::nodes::BlockStatement, runtime::{declarations::{has_block_declarations, instantiate_block_declarations}, environment::Environment, nodes::{Completion, RuntimeResult}, Runtime}};
+
+pub fn parse_block_statement(runtime: &mut Runtime, block: &BlockStatement) -> RuntimeResult<Completion> {
+    if !has_block_declarations(&block.body) {
+        return runtime.run_statements(&block.body);
+    }
+
+    // Blocks with their own declarations get an environment for them
+    let environment = Environment::new(&runtime.environment);
+    let outer_environment = runtime.enter_environment(environment);
+
+    instantiate_block_declarations(runtime, &block.body);
+    let completion = runtime.run_statements(&block.body);
+
+    // The outer environment comes back even when the block throws
+    runtime.enter_environment(outer_environment);
+
+    completion
+}
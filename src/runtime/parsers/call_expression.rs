@@ -0,0 +1,279 @@
+use std::rc::Rc;
+
+use crate::{ast::nodes::{CallExpression, Expression, Pattern}, runtime::{builtins::{start_async_function, AsyncGeneratorData, GeneratorState}, call_stack::{check_call_depth, StackFrame}, coroutine::Coroutine, declarations::instantiate_function_declarations, environment::{Environment, EnvironmentRef, ThisBinding}, nodes::{Completion, FunctionCall, FunctionCallType, RuntimeCall, RuntimeResult}, object::{get, Object, ObjectKind, PropertyKey}, operations::{create_array_from_list, iterable_to_list}, reference::{evaluate_member, get_value}, Runtime, Value}};
+
+use super::{await_expression::await_value, chain_expression::evaluate_chain, expression::parse_expression, new_expression::super_call, pattern::{bind_pattern, bound_names}};
+
+pub fn parse_call_expression(runtime: &mut Runtime, expression: &CallExpression) -> RuntimeResult<Value> {
+    if let Expression::Super(_) = &expression.callee {
+        let args_values = evaluate_arguments(runtime, &expression.arguments)?;
+
+        let environment = runtime.environment.clone();
+        runtime.set_position(expression.range.0);
+
+        return super_call(runtime, &environment, args_values);
+    }
+
+    // A method call gets the object it was read from as `this`
+    let (callee, this_value) = match &expression.callee {
+        Expression::MemberExpression(member) => {
+            let reference = evaluate_member(runtime, member)?;
+            (get_value(runtime, &reference)?, reference.this_value())
+        },
+        // `(a?.b)()` still calls `b` on `a`, a short-circuited chain leaves undefined to call
+        Expression::ChainExpression(chain) => evaluate_chain(runtime, &chain.expression)?
+            .unwrap_or((Value::Undefined, Value::Undefined)),
+        callee => (parse_expression(runtime, callee)?, Value::Undefined),
+    };
+
+    evaluate_call(runtime, expression, callee, this_value)
+}
+
+// Calls a callee that was already evaluated, for the links of an optional chain
+pub fn evaluate_call(runtime: &mut Runtime, expression: &CallExpression, callee: Value, this_value: Value) -> RuntimeResult<Value> {
+    // Arguments are evaluated left to right in the scope of the caller, before the callee is checked
+    let args_values = evaluate_arguments(runtime, &expression.arguments)?;
+
+    let function = match callee.as_function() {
+        Some(function) => function,
+        None => return runtime.type_error(&format!("{} is not a function", callee_name(&expression.callee))),
+    };
+
+    runtime.set_position(expression.range.0);
+
+    call_function(runtime, &function, this_value, args_values)
+}
+
+// ArgumentListEvaluation, a spread argument passes every value of its iterable
+pub fn evaluate_arguments(runtime: &mut Runtime, arguments: &[Expression]) -> RuntimeResult<Vec<Value>> {
+    let mut values = Vec::new();
+
+    for argument in arguments {
+        match argument {
+            Expression::SpreadElement(spread) => {
+                let iterable = parse_expression(runtime, &spread.argument)?;
+                values.extend(iterable_to_list(runtime, &iterable)?);
+            },
+            argument => values.push(parse_expression(runtime, argument)?),
+        }
+    }
+
+    Ok(values)
+}
+
+pub fn call_function(runtime: &mut Runtime, function: &Rc<FunctionCall>, this_value: Value, args_values: Vec<Value>) -> RuntimeResult<Value> {
+    check_call_depth(runtime)?;
+
+    match &function.function_type {
+        FunctionCallType::SystemCall(syscall) => {
+            Ok((syscall.func)(runtime, &this_value, &args_values)?.unwrap_or_else(Value::undefined))
+        },
+        FunctionCallType::BoundFunction(bound) => {
+            let target = bound.target.as_function().expect("bound functions wrap a function");
+            let arguments = bound.arguments.iter().cloned().chain(args_values).collect();
+
+            call_function(runtime, &target, bound.this_value.clone(), arguments)
+        },
+        FunctionCallType::RuntimeCall(runtime_call) => {
+            if runtime_call.class.is_some() {
+                return runtime.type_error(&format!("Class constructor {} cannot be invoked without 'new'", function.name));
+            }
+
+            // OrdinaryCallBindThis, without wrapper objects primitives are passed through as they are
+            let this_binding = match this_value {
+                _ if runtime_call.arrow => ThisBinding::Lexical,
+                Value::Undefined | Value::Null if !runtime_call.strict => ThisBinding::Initialized(Value::Object(runtime.global_object.clone())),
+                this_value => ThisBinding::Initialized(this_value),
+            };
+
+            // The body runs in a fresh environment on top of the one the function was declared in
+            let environment = Environment::new_function(&runtime_call.environment, runtime_call, this_binding);
+
+            if runtime_call.generator || runtime_call.is_async {
+                return start_coroutine(runtime, function, runtime_call, environment, args_values);
+            }
+
+            evaluate_body(runtime, function, runtime_call, environment, args_values)
+        },
+    }
+}
+
+// Binds the parameters in the function environment and runs the body there
+pub fn evaluate_body(runtime: &mut Runtime, function: &FunctionCall, runtime_call: &RuntimeCall, environment: EnvironmentRef, args_values: Vec<Value>) -> RuntimeResult<Value> {
+    // Constructors show in stack traces as `new Name`
+    let function_name = match environment.borrow().new_target {
+        Value::Undefined => function.name.clone(),
+        _ => format!("new {}", function.name),
+    };
+
+    let caller_environment = runtime.enter_environment(environment.clone());
+    runtime.call_stack.push(StackFrame { function_name: Some(function_name), position: runtime_call.body.range.0, script: runtime_call.script.clone() });
+
+    let completion = bind_parameters(runtime, runtime_call, &environment, args_values)
+        .and_then(|_| runtime.run_statements(&runtime_call.body.body));
+
+    // The caller's environment and frame come back before an exception moves on to its handler
+    runtime.call_stack.pop();
+    runtime.enter_environment(caller_environment);
+
+    completion_value(runtime, completion?)
+}
+
+// The value a call gives for the way its body finished
+fn completion_value(runtime: &Runtime, completion: Completion) -> RuntimeResult<Value> {
+    match completion {
+        Completion::Return(value) => Ok(value),
+        Completion::Normal => Ok(Value::undefined()),
+        Completion::Break => runtime.syntax_error("Illegal break statement"),
+        Completion::Continue => runtime.syntax_error("Illegal continue statement"),
+    }
+}
+
+// Calling a generator function binds the parameters right away, but the body only starts running on the first `next`.
+// The generator object inherits from the function's `prototype` at the time of the call.
+// An async function runs its body right away, on a coroutine so that `await` can suspend it. Its parameters are
+// bound on the coroutine as well, an error there rejects the promise instead of being thrown.
+fn start_coroutine(runtime: &mut Runtime, function: &Rc<FunctionCall>, runtime_call: &RuntimeCall, environment: EnvironmentRef, args_values: Vec<Value>) -> RuntimeResult<Value> {
+    let frame = || StackFrame { function_name: Some(function.name.clone()), position: runtime_call.body.range.0, script: runtime_call.script.clone() };
+
+    let args_values = match runtime_call.generator {
+        true => {
+            let caller_environment = runtime.enter_environment(environment.clone());
+            runtime.call_stack.push(frame());
+
+            let bound = bind_parameters(runtime, runtime_call, &environment, args_values);
+
+            runtime.call_stack.pop();
+            runtime.enter_environment(caller_environment);
+            bound?;
+
+            None
+        },
+        false => Some(args_values),
+    };
+
+    let body_function = function.clone();
+    let frame = frame();
+
+    // Whoever resumes the coroutine puts their own environment back afterwards
+    let coroutine = Coroutine::new(move |runtime| {
+        let runtime_call = match &body_function.function_type {
+            FunctionCallType::RuntimeCall(runtime_call) => runtime_call,
+            _ => unreachable!(),
+        };
+
+        runtime.enter_environment(environment.clone());
+        runtime.call_stack.push(frame);
+
+        let completion = match args_values {
+            Some(args_values) => bind_parameters(runtime, runtime_call, &environment, args_values)
+                .and_then(|_| runtime.run_statements(&runtime_call.body.body)),
+            None => runtime.run_statements(&runtime_call.body.body),
+        };
+
+        // An async generator awaits what it returns, while its frame is still the innermost one
+        let result = completion
+            .and_then(|completion| completion_value(runtime, completion))
+            .and_then(|value| match runtime_call.generator && runtime_call.is_async {
+                true => await_value(runtime, value),
+                false => Ok(value),
+            });
+
+        runtime.call_stack.pop();
+
+        result
+    });
+
+    if !runtime_call.generator {
+        return Ok(start_async_function(runtime, coroutine));
+    }
+
+    let prototype = match runtime_call.function_object.borrow().upgrade() {
+        Some(object) => get(runtime, &object, &PropertyKey::from("prototype"), &Value::Object(object.clone()))?,
+        None => Value::Undefined,
+    };
+
+    let (kind, default_prototype) = match runtime_call.is_async {
+        true => (ObjectKind::AsyncGenerator(AsyncGeneratorData::new(coroutine)), &runtime.intrinsics.async_generator_prototype),
+        false => (ObjectKind::Generator(GeneratorState::SuspendedStart(coroutine)), &runtime.intrinsics.generator_prototype),
+    };
+
+    let prototype = match prototype {
+        Value::Object(prototype) => prototype,
+        _ => default_prototype.clone(),
+    };
+
+    Ok(Value::Object(Object::new(kind, Some(prototype))))
+}
+
+// FunctionDeclarationInstantiation, parameter defaults run in order inside the function environment
+fn bind_parameters(runtime: &mut Runtime, runtime_call: &RuntimeCall, environment: &EnvironmentRef, args_values: Vec<Value>) -> RuntimeResult<()> {
+    let mut parameter_names = Vec::new();
+
+    for param in runtime_call.params.iter() {
+        bound_names(param, &mut parameter_names);
+    }
+
+    // Arrow functions see the `arguments` of the function around them, and a parameter of that name wins
+    if !runtime_call.arrow && !parameter_names.iter().any(|name| name == "arguments") {
+        let arguments = create_arguments_object(runtime, &args_values);
+        environment.borrow_mut().declare("arguments", arguments);
+    }
+
+    // Missing arguments are undefined, extra arguments are ignored unless there is a rest parameter
+    for (index, param) in runtime_call.params.iter().enumerate() {
+        let value = match param {
+            Pattern::RestElement(_) => create_array_from_list(runtime, args_values.get(index..).unwrap_or_default().to_vec()),
+            _ => args_values.get(index).cloned().unwrap_or_else(Value::undefined),
+        };
+
+        bind_pattern(runtime, param, value, Some(environment))?;
+    }
+
+    instantiate_function_declarations(runtime, &runtime_call.body.body, &parameter_names);
+
+    Ok(())
+}
+
+// CreateUnmappedArgumentsObject, the elements do not follow later assignments to the parameters
+fn create_arguments_object(runtime: &Runtime, args_values: &[Value]) -> Value {
+    let arguments = Object::new_arguments(&runtime.intrinsics.object_prototype);
+
+    {
+        let mut arguments = arguments.borrow_mut();
+
+        for (index, value) in args_values.iter().enumerate() {
+            arguments.create_data_property(PropertyKey::from(index), value.clone());
+        }
+
+        arguments.define_builtin("length", Value::Number(args_values.len() as f64));
+    }
+
+    Value::Object(arguments)
+}
+
+// Describes the callee the way V8 does when it turns out not to be callable, `a.b`, `a[0]`, `f(...)`
+// or "(intermediate value)" for anything that has no short source form. Literals are shown as written
+pub fn callee_name(callee: &Expression) -> String {
+    match callee {
+        Expression::Identifier(identifier) => identifier.name.clone(),
+        Expression::PrivateIdentifier(identifier) => identifier.name.clone(),
+        Expression::ThisExpression(_) => "this".to_string(),
+        Expression::Super(_) => "super".to_string(),
+        Expression::NumericLiteral(literal) => literal.raw.clone(),
+        Expression::StringLiteral(literal) => literal.raw.clone(),
+        Expression::BooleanLiteral(literal) => literal.raw.clone(),
+        Expression::NullLiteral(literal) => literal.raw.clone(),
+        Expression::BigIntLiteral(literal) => literal.raw.clone(),
+        Expression::RegExpLiteral(literal) => literal.raw.clone(),
+        Expression::MemberExpression(member_expression) if member_expression.computed => match &member_expression.property {
+            Expression::StringLiteral(literal) => format!("{}.{}", callee_name(&member_expression.object), literal.value),
+            property => format!("{}[{}]", callee_name(&member_expression.object), callee_name(property))
+        },
+        Expression::MemberExpression(member_expression) => {
+            format!("{}.{}", callee_name(&member_expression.object), callee_name(&member_expression.property))
+        },
+        Expression::CallExpression(call_expression) => format!("{}(...)", callee_name(&call_expression.callee)),
+        _ => "(intermediate value)".to_string()
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{ast::nodes::{ChainExpression, Expression}, runtime::{nodes::RuntimeResult, reference::{get_value, member_reference}, Runtime, Value}};
+
+use super::{call_expression::evaluate_call, expression::parse_expression};
+
+pub fn parse_chain_expression(runtime: &mut Runtime, expression: &ChainExpression) -> RuntimeResult<Value> {
+    Ok(match evaluate_chain(runtime, &expression.expression)? {
+        Some((value, _)) => value,
+        None => Value::Undefined,
+    })
+}
+
+// Evaluates a link of the chain together with the `this` a call on it receives.
+// None once an optional link met null or undefined, which skips the rest of the chain.
+pub fn evaluate_chain(runtime: &mut Runtime, expression: &Expression) -> RuntimeResult<Option<(Value, Value)>> {
+    match expression {
+        Expression::MemberExpression(member) if !matches!(member.object, Expression::Super(_)) => {
+            let base = match evaluate_chain(runtime, &member.object)? {
+                Some((base, _)) => base,
+                None => return Ok(None),
+            };
+
+            if member.optional && matches!(base, Value::Undefined | Value::Null) {
+                return Ok(None);
+            }
+
+            let reference = member_reference(runtime, member, base)?;
+
+            Ok(Some((get_value(runtime, &reference)?, reference.this_value())))
+        },
+        Expression::CallExpression(call) if !matches!(call.callee, Expression::Super(_)) => {
+            let (callee, this_value) = match evaluate_chain(runtime, &call.callee)? {
+                Some(callee) => callee,
+                None => return Ok(None),
+            };
+
+            if call.optional && matches!(callee, Value::Undefined | Value::Null) {
+                return Ok(None);
+            }
+
+            Ok(Some((evaluate_call(runtime, call, callee, this_value)?, Value::Undefined)))
+        },
+        expression => Ok(Some((parse_expression(runtime, expression)?, Value::Undefined))),
+    }
+}
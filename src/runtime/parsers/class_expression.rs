@@ -0,0 +1,309 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{ast::nodes::{BlockStatement, ClassBody, ClassDeclaration, ClassElement, ClassExpression, Expression, MethodKind}, runtime::{environment::{Environment, EnvironmentRef}, nodes::{ClassDefinition, ClassField, RuntimeResult}, object::{get, private_name, Object, ObjectRef, Property, PropertyDescriptor, PropertyKey}, operations::call, value::Symbol, Runtime, Value}};
+
+use super::{expression::{is_anonymous_function_definition, parse_expression}, function_expression::return_block, new_expression::is_constructor, object_expression::property_key, parse_function_declaration::{create_function, function_name, set_function_name, FunctionKind}};
+
+// Static fields and blocks run in source order once the class is set up
+enum StaticElement {
+    Field(ClassField),
+    Block(Value),
+}
+
+// The binding is created uninitialized with the rest of the scope and initialized once the class is defined
+pub fn parse_class_declaration(runtime: &mut Runtime, declaration: &ClassDeclaration) -> RuntimeResult<()> {
+    let name = &declaration.id.name;
+    let class = class_definition_evaluation(runtime, Some(name), name, &declaration.super_class, &declaration.body)?;
+
+    runtime.environment.borrow_mut().initialize(name, class);
+
+    Ok(())
+}
+
+// `name` is used when the class has no name of its own
+pub fn parse_class_expression(runtime: &mut Runtime, expression: &ClassExpression, name: &str) -> RuntimeResult<Value> {
+    let binding_name = expression.id.as_ref().map(|id| id.name.as_str());
+
+    class_definition_evaluation(runtime, binding_name, binding_name.unwrap_or(name), &expression.super_class, &expression.body)
+}
+
+// ClassDefinitionEvaluation, the constructor and the methods close over a scope that holds the class name and the private names
+fn class_definition_evaluation(runtime: &mut Runtime, binding_name: Option<&str>, name: &str, super_class: &Option<Expression>, body: &ClassBody) -> RuntimeResult<Value> {
+    let class_environment = Environment::new_class(&runtime.environment);
+    let outer_environment = runtime.enter_environment(class_environment.clone());
+
+    // The heritage and the computed keys run in the class scope, which is left again even when they throw
+    let elements = class_elements(runtime, &class_environment, name, super_class, body);
+    runtime.enter_environment(outer_environment);
+
+    let (constructor, static_elements) = elements?;
+
+    if let Some(binding_name) = binding_name {
+        class_environment.borrow_mut().declare(binding_name, constructor.clone());
+    }
+
+    for element in static_elements {
+        match element {
+            StaticElement::Field(field) => define_field(runtime, &constructor, &field)?,
+            StaticElement::Block(block) => {
+                call(runtime, &block, constructor.clone(), vec![])?;
+            },
+        }
+    }
+
+    Ok(constructor)
+}
+
+// Creates the constructor with its prototype and methods, the static fields and blocks are returned to run afterwards
+fn class_elements(runtime: &mut Runtime, class_environment: &EnvironmentRef, name: &str, super_class: &Option<Expression>, body: &ClassBody) -> RuntimeResult<(Value, Vec<StaticElement>)> {
+    // Every evaluation of a class creates new private names, a getter and a setter share theirs
+    for element in &body.body {
+        if let Some(Expression::PrivateIdentifier(identifier)) = element_key(element) {
+            if !class_environment.borrow().has_own_binding(&identifier.name) {
+                let symbol = Symbol { description: Some(identifier.name.clone()) };
+                class_environment.borrow_mut().declare(&identifier.name, Value::Symbol(Rc::new(symbol)));
+            }
+        }
+    }
+
+    let (prototype_parent, constructor_parent) = match super_class {
+        None => (Some(runtime.intrinsics.object_prototype.clone()), runtime.intrinsics.function_prototype.clone()),
+        Some(super_class) => class_heritage(runtime, super_class)?,
+    };
+
+    let prototype = Object::new_ordinary(prototype_parent);
+
+    let constructor_method = body.body.iter().find_map(|element| match element {
+        ClassElement::MethodDefinition(method) if method.kind == MethodKind::Constructor => Some(method),
+        _ => None,
+    });
+
+    let class = Rc::new(ClassDefinition {
+        derived: super_class.is_some(),
+        default_constructor: constructor_method.is_none(),
+        fields: RefCell::new(Vec::new()),
+        private_methods: RefCell::new(Vec::new()),
+    });
+
+    let kind = FunctionKind::ClassConstructor { home_object: prototype.clone(), class: class.clone() };
+
+    let constructor = match constructor_method {
+        Some(method) => create_function(runtime, name, &method.value.params, &method.value.body, kind, false, false),
+        None => create_function(runtime, name, &[], &BlockStatement { body: Vec::new(), range: body.range }, kind, false, false),
+    };
+
+    let constructor_object = match &constructor {
+        Value::Object(object) => object.clone(),
+        _ => unreachable!(),
+    };
+
+    // A subclass inherits the static members of its parent
+    constructor_object.borrow_mut().prototype = Some(constructor_parent);
+    constructor_object.borrow_mut().define_own_property(PropertyKey::from("prototype"), PropertyDescriptor {
+        value: Some(Value::Object(prototype.clone())),
+        writable: Some(false),
+        enumerable: Some(false),
+        configurable: Some(false),
+        ..Default::default()
+    });
+    prototype.borrow_mut().define_builtin("constructor", constructor.clone());
+
+    let mut instance_fields = Vec::new();
+    let mut instance_private_methods = Vec::new();
+    let mut static_private_methods = Vec::new();
+    let mut static_elements = Vec::new();
+
+    for element in &body.body {
+        match element {
+            ClassElement::MethodDefinition(method) if method.kind == MethodKind::Constructor => {},
+            ClassElement::MethodDefinition(method) => {
+                let home_object = if method.is_static { &constructor_object } else { &prototype };
+                let (key, private) = class_element_key(runtime, &method.key, method.computed)?;
+
+                let prefix = match method.kind {
+                    MethodKind::Get => Some("get"),
+                    MethodKind::Set => Some("set"),
+                    _ => None,
+                };
+
+                let name = match private {
+                    true => function_name(&PropertyKey::from(private_name(&key).as_str()), prefix),
+                    false => function_name(&key, prefix),
+                };
+
+                let function = create_function(runtime, &name, &method.value.params, &method.value.body, FunctionKind::Method(home_object.clone()), method.value.generator, method.value.is_async);
+
+                // Methods and accessors are not enumerable, private methods cannot be assigned to
+                let property = match method.kind {
+                    MethodKind::Get => Property::Accessor { get: Some(function), set: None, enumerable: false, configurable: true },
+                    MethodKind::Set => Property::Accessor { get: None, set: Some(function), enumerable: false, configurable: true },
+                    _ => Property::Data { value: function, writable: !private, enumerable: false, configurable: true },
+                };
+
+                match (private, method.is_static) {
+                    (true, false) => add_private_method(&mut instance_private_methods, key, property),
+                    (true, true) => add_private_method(&mut static_private_methods, key, property),
+                    (false, _) => define_method(runtime, home_object, key, property)?,
+                }
+            },
+            ClassElement::PropertyDefinition(definition) => {
+                let home_object = if definition.is_static { &constructor_object } else { &prototype };
+                let (key, private) = class_element_key(runtime, &definition.key, definition.computed)?;
+
+                // The initializer becomes a method, so `this` and `super` inside it refer to the instance
+                let initializer = definition.value.as_ref()
+                    .map(|value| create_function(runtime, "", &[], &return_block(value), FunctionKind::Method(home_object.clone()), false, false));
+
+                let names_initializer = definition.value.as_ref().is_some_and(is_anonymous_function_definition);
+
+                let field = ClassField { key, private, initializer, names_initializer };
+
+                match definition.is_static {
+                    true => static_elements.push(StaticElement::Field(field)),
+                    false => instance_fields.push(field),
+                }
+            },
+            ClassElement::StaticBlock(block) => {
+                let body = BlockStatement { body: block.body.clone(), range: block.range };
+                let function = create_function(runtime, "", &[], &body, FunctionKind::Method(constructor_object.clone()), false, false);
+
+                static_elements.push(StaticElement::Block(function));
+            },
+        }
+    }
+
+    *class.fields.borrow_mut() = instance_fields;
+    *class.private_methods.borrow_mut() = instance_private_methods;
+
+    for (key, property) in static_private_methods {
+        constructor_object.borrow_mut().add_private_element(key, property);
+    }
+
+    Ok((constructor, static_elements))
+}
+
+// The prototype parent and constructor parent for `extends`, `extends null` makes instances inherit from nothing
+fn class_heritage(runtime: &mut Runtime, super_class: &Expression) -> RuntimeResult<(Option<ObjectRef>, ObjectRef)> {
+    let super_class = parse_expression(runtime, super_class)?;
+
+    let object = match &super_class {
+        Value::Null => return Ok((None, runtime.intrinsics.function_prototype.clone())),
+        Value::Object(object) if is_constructor(&super_class) => object.clone(),
+        _ => return runtime.type_error(&format!("Class extends value {} is not a constructor or null", super_class.to_display_string())),
+    };
+
+    match get(runtime, &object, &PropertyKey::from("prototype"), &super_class)? {
+        Value::Object(prototype) => Ok((Some(prototype), object)),
+        Value::Null => Ok((None, object)),
+        prototype => runtime.type_error(&format!("Class extends value does not have valid prototype property {}", prototype.to_display_string())),
+    }
+}
+
+fn element_key(element: &ClassElement) -> Option<&Expression> {
+    match element {
+        ClassElement::MethodDefinition(method) => Some(&method.key),
+        ClassElement::PropertyDefinition(field) => Some(&field.key),
+        ClassElement::StaticBlock(_) => None,
+    }
+}
+
+// The key of a class element and whether it is a private name
+fn class_element_key(runtime: &mut Runtime, key: &Expression, computed: bool) -> RuntimeResult<(PropertyKey, bool)> {
+    match key {
+        Expression::PrivateIdentifier(identifier) => match runtime.variable(&identifier.name)? {
+            Some(Value::Symbol(symbol)) => Ok((PropertyKey::Symbol(symbol), true)),
+            _ => unreachable!("private names are declared before the elements are evaluated"),
+        },
+        key => Ok((property_key(runtime, key, computed)?, false)),
+    }
+}
+
+// A private getter and setter with the same name end up on one accessor
+fn add_private_method(methods: &mut Vec<(PropertyKey, Property)>, key: PropertyKey, property: Property) {
+    let existing = methods.iter_mut().find(|(existing, _)| *existing == key);
+
+    match (existing, property) {
+        (Some((_, Property::Accessor { get, .. })), Property::Accessor { get: Some(getter), .. }) => *get = Some(getter),
+        (Some((_, Property::Accessor { set, .. })), Property::Accessor { set: Some(setter), .. }) => *set = Some(setter),
+        (_, property) => methods.push((key, property)),
+    }
+}
+
+fn define_method(runtime: &Runtime, home_object: &ObjectRef, key: PropertyKey, property: Property) -> RuntimeResult<()> {
+    let descriptor = match property {
+        Property::Data { value, writable, enumerable, configurable } => PropertyDescriptor {
+            value: Some(value),
+            writable: Some(writable),
+            enumerable: Some(enumerable),
+            configurable: Some(configurable),
+            ..Default::default()
+        },
+        Property::Accessor { get, set, enumerable, configurable } => PropertyDescriptor {
+            get,
+            set,
+            enumerable: Some(enumerable),
+            configurable: Some(configurable),
+            ..Default::default()
+        },
+    };
+
+    if !home_object.borrow_mut().define_own_property(key.clone(), descriptor) {
+        return runtime.type_error(&format!("Cannot redefine property: {}", key));
+    }
+
+    Ok(())
+}
+
+// DefineField, runs the initializer with the receiver as `this`
+fn define_field(runtime: &mut Runtime, receiver: &Value, field: &ClassField) -> RuntimeResult<()> {
+    let value = match &field.initializer {
+        Some(initializer) => call(runtime, initializer, receiver.clone(), vec![])?,
+        None => Value::Undefined,
+    };
+
+    if field.names_initializer {
+        let name = match field.private {
+            true => private_name(&field.key),
+            false => function_name(&field.key, None),
+        };
+
+        set_function_name(&value, &name);
+    }
+
+    let object = match receiver {
+        Value::Object(object) => object,
+        _ => unreachable!("fields are only defined on objects"),
+    };
+
+    if field.private {
+        let property = Property::Data { value, writable: true, enumerable: false, configurable: false };
+
+        if !object.borrow_mut().add_private_element(field.key.clone(), property) {
+            return runtime.type_error(&format!("Cannot initialize {} twice on the same object", private_name(&field.key)));
+        }
+    } else if !object.borrow_mut().create_data_property(field.key.clone(), value) {
+        return runtime.type_error(&format!("Cannot redefine property: {}", field.key));
+    }
+
+    Ok(())
+}
+
+// InitializeInstanceElements, private methods come first so field initializers can already call them
+pub fn initialize_instance_elements(runtime: &mut Runtime, this_value: &Value, class: &ClassDefinition) -> RuntimeResult<()> {
+    let object = match this_value {
+        Value::Object(object) => object,
+        _ => return Ok(()),
+    };
+
+    for (key, property) in class.private_methods.borrow().iter() {
+        if !object.borrow_mut().add_private_element(key.clone(), property.clone()) {
+            return runtime.type_error("Cannot initialize private methods of class twice on the same object");
+        }
+    }
+
+    for field in class.fields.borrow().iter() {
+        define_field(runtime, this_value, field)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::{ast::nodes::{ExportDefaultDeclaration, ExportDefaultKind, ExportNamedDeclaration}, runtime::{nodes::{Completion, RuntimeResult}, Runtime}};
+
+use super::expression::parse_named_expression;
+
+// `export` in front of a declaration runs the declaration, a list of names has nothing left to do once the module is linked
+pub fn parse_export_named_declaration(runtime: &mut Runtime, declaration: &ExportNamedDeclaration) -> RuntimeResult<Completion> {
+    match &declaration.declaration {
+        Some(declaration) => runtime.run_statement(declaration),
+        None => Ok(Completion::Normal),
+    }
+}
+
+// Functions are created when the module is linked, an anonymous function or class expression is named `default`
+pub fn parse_export_default_declaration(runtime: &mut Runtime, declaration: &ExportDefaultDeclaration) -> RuntimeResult<Completion> {
+    match &declaration.declaration {
+        ExportDefaultKind::Declaration(declaration) => runtime.run_statement(declaration),
+        ExportDefaultKind::Expression(expression) => {
+            let value = parse_named_expression(runtime, expression, "default")?;
+            runtime.environment.borrow_mut().initialize("default", value);
+
+            Ok(Completion::Normal)
+        },
+    }
+}
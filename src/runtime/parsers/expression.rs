@@ -0,0 +1,67 @@
+use std::rc::Rc;
+
+use crate::{ast::nodes::Expression, runtime::{call_stack::check_stack, environment::this_value, module::import_meta, nodes::RuntimeResult, object::Object, Runtime, Value}};
+
+use super::{array_expression::parse_array_expression, class_expression::parse_class_expression, function_expression::{parse_arrow_function_expression, parse_function_expression}, assignment_expression::parse_assignment_expression, binary_expression::parse_binary_expression, call_expression::parse_call_expression, import_expression::parse_import_expression, chain_expression::parse_chain_expression, logical_expression::parse_logical_expression, member_expression::parse_member_expression, new_expression::parse_new_expression, object_expression::parse_object_expression, unary_expression::parse_unary_expression, update_expression::parse_update_expression, await_expression::parse_await_expression, yield_expression::parse_yield_expression};
+
+pub fn parse_expression(runtime: &mut Runtime, expression: &Expression) -> RuntimeResult<Value> {
+    check_stack(runtime)?;
+
+    Ok(match expression {
+        Expression::NumericLiteral(literal) => Value::Number(literal.value),
+        Expression::StringLiteral(literal) => Value::string(&literal.value),
+        Expression::BooleanLiteral(literal) => Value::Boolean(literal.value),
+        Expression::NullLiteral(_) => Value::Null,
+        Expression::BigIntLiteral(literal) => Value::BigInt(Rc::new(literal.value.clone())),
+        // Every evaluation of a regular expression literal creates a new object
+        Expression::RegExpLiteral(literal) => Value::Object(Object::new_regexp(&literal.pattern, &literal.flags, &runtime.intrinsics.regexp_prototype)),
+        Expression::Identifier(identifier) => match runtime.variable(&identifier.name)? {
+            Some(variable) => variable,
+            None => return runtime.reference_error(&format!("{} is not defined", identifier.name)),
+        },
+        Expression::ObjectExpression(object) => return parse_object_expression(runtime, object),
+        Expression::ArrayExpression(array) => return parse_array_expression(runtime, array),
+        Expression::SpreadElement(spread) => return runtime.syntax_error(&format!("Unexpected spread element at {:?}", spread.range)),
+        Expression::FunctionExpression(function) => parse_function_expression(runtime, function, ""),
+        Expression::ArrowFunctionExpression(function) => parse_arrow_function_expression(runtime, function, ""),
+        Expression::ClassExpression(class) => return parse_class_expression(runtime, class, ""),
+        Expression::ThisExpression(_) => return this_value(runtime, &runtime.environment),
+        Expression::Super(keyword) => return runtime.syntax_error(&format!("'super' keyword unexpected here at {:?}", keyword.range)),
+        Expression::PrivateIdentifier(identifier) => {
+            return runtime.syntax_error(&format!("Unexpected private name {} at {:?}", identifier.name, identifier.range));
+        },
+        Expression::CallExpression(call) => return parse_call_expression(runtime, call),
+        Expression::NewExpression(new) => return parse_new_expression(runtime, new),
+        Expression::MemberExpression(member) => return parse_member_expression(runtime, member),
+        Expression::ChainExpression(chain) => return parse_chain_expression(runtime, chain),
+        Expression::BinaryExpression(binary) => return parse_binary_expression(runtime, binary),
+        Expression::LogicalExpression(logical) => return parse_logical_expression(runtime, logical),
+        Expression::UnaryExpression(unary) => return parse_unary_expression(runtime, unary),
+        Expression::UpdateExpression(update) => return parse_update_expression(runtime, update),
+        Expression::AssignmentExpression(assignment) => return parse_assignment_expression(runtime, assignment),
+        Expression::YieldExpression(expression) => return parse_yield_expression(runtime, expression),
+        Expression::AwaitExpression(expression) => return parse_await_expression(runtime, expression),
+        Expression::ImportExpression(expression) => return parse_import_expression(runtime, expression),
+        Expression::ImportMeta(_) => import_meta(runtime),
+    })
+}
+
+// NamedEvaluation, anonymous functions and classes are named after the binding or property they are assigned to
+pub fn parse_named_expression(runtime: &mut Runtime, expression: &Expression, name: &str) -> RuntimeResult<Value> {
+    match expression {
+        Expression::FunctionExpression(function) => Ok(parse_function_expression(runtime, function, name)),
+        Expression::ArrowFunctionExpression(function) => Ok(parse_arrow_function_expression(runtime, function, name)),
+        Expression::ClassExpression(class) => parse_class_expression(runtime, class, name),
+        expression => parse_expression(runtime, expression),
+    }
+}
+
+// IsAnonymousFunctionDefinition
+pub fn is_anonymous_function_definition(expression: &Expression) -> bool {
+    match expression {
+        Expression::FunctionExpression(function) => function.id.is_none(),
+        Expression::ArrowFunctionExpression(_) => true,
+        Expression::ClassExpression(class) => class.id.is_none(),
+        _ => false,
+    }
+}
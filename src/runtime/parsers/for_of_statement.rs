@@ -0,0 +1,88 @@
+use crate::{ast::nodes::{ForOfStatement, VariableKind}, runtime::{environment::{Environment, EnvironmentRef}, nodes::{Completion, RuntimeResult}, operations::{get_iterator, iterator_close, iterator_step_value}, Runtime, Value}};
+
+use super::{await_expression::{async_iterator_close, async_iterator_step_value, get_async_iterator}, expression::parse_expression, pattern::{bind_pattern, bound_names}};
+
+// The iterator is closed when the loop stops before it is done, by `break`, `return` or an exception
+pub fn parse_for_of_statement(runtime: &mut Runtime, statement: &ForOfStatement) -> RuntimeResult<Completion> {
+    let iterable = match lexical_kind(statement) {
+        // The names of the loop are already in scope, but not yet initialized, while the iterable is evaluated
+        Some(kind) => {
+            let environment = Environment::new(&runtime.environment);
+            declare_names(&environment, statement, kind);
+
+            let outer_environment = runtime.enter_environment(environment);
+            let iterable = parse_expression(runtime, &statement.right);
+            runtime.enter_environment(outer_environment);
+
+            iterable?
+        },
+        None => parse_expression(runtime, &statement.right)?,
+    };
+
+    if statement.is_await {
+        return run_for_await(runtime, statement, &iterable);
+    }
+
+    let record = get_iterator(runtime, &iterable)?;
+
+    while let Some(value) = iterator_step_value(runtime, &record)? {
+        match run_iteration(runtime, statement, value) {
+            Ok(Completion::Normal | Completion::Continue) => {},
+            Ok(Completion::Break) => return iterator_close(runtime, &record, Ok(Completion::Normal)),
+            completion => return iterator_close(runtime, &record, completion),
+        }
+    }
+
+    Ok(Completion::Normal)
+}
+
+// `for await`, every step awaits the result of `next` and closing the iterator awaits the result of `return`
+fn run_for_await(runtime: &mut Runtime, statement: &ForOfStatement, iterable: &Value) -> RuntimeResult<Completion> {
+    let iterator = get_async_iterator(runtime, iterable)?;
+
+    while let Some(value) = async_iterator_step_value(runtime, &iterator)? {
+        match run_iteration(runtime, statement, value) {
+            Ok(Completion::Normal | Completion::Continue) => {},
+            Ok(Completion::Break) => return async_iterator_close(runtime, &iterator, Ok(Completion::Normal)),
+            completion => return async_iterator_close(runtime, &iterator, completion),
+        }
+    }
+
+    Ok(Completion::Normal)
+}
+
+// `let` and `const` get a fresh binding for every value, so closures in the body each keep their own
+fn run_iteration(runtime: &mut Runtime, statement: &ForOfStatement, value: Value) -> RuntimeResult<Completion> {
+    let kind = match lexical_kind(statement) {
+        Some(kind) => kind,
+        None => {
+            bind_pattern(runtime, &statement.left, value, None)?;
+            return runtime.run_statement(&statement.body);
+        },
+    };
+
+    let environment = Environment::new(&runtime.environment);
+    declare_names(&environment, statement, kind);
+
+    let outer_environment = runtime.enter_environment(environment.clone());
+
+    let completion = bind_pattern(runtime, &statement.left, value, Some(&environment))
+        .and_then(|_| runtime.run_statement(&statement.body));
+
+    runtime.enter_environment(outer_environment);
+
+    completion
+}
+
+fn lexical_kind(statement: &ForOfStatement) -> Option<VariableKind> {
+    statement.kind.filter(|kind| *kind != VariableKind::Var)
+}
+
+fn declare_names(environment: &EnvironmentRef, statement: &ForOfStatement, kind: VariableKind) {
+    let mut names = Vec::new();
+    bound_names(&statement.left, &mut names);
+
+    for name in names {
+        environment.borrow_mut().declare_uninitialized(&name, kind == VariableKind::Const);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{ast::nodes::{ArrowFunctionBody, ArrowFunctionExpression, AstNode, BlockStatement, Expression, FunctionExpression, ReturnStatement}, runtime::{environment::Environment, Runtime, Value}};
+
+use super::parse_function_declaration::{create_function, FunctionKind};
+
+// `name` is used when the expression has no name of its own.
+// A named function expression can refer to itself, its name lives in a scope between the function and the surrounding code.
+pub fn parse_function_expression(runtime: &mut Runtime, expression: &FunctionExpression, name: &str) -> Value {
+    let id = match &expression.id {
+        Some(id) => id,
+        None => return create_function(runtime, name, &expression.params, &expression.body, FunctionKind::Normal, expression.generator, expression.is_async),
+    };
+
+    let environment = Environment::new(&runtime.environment);
+    let outer_environment = runtime.enter_environment(environment.clone());
+
+    let function = create_function(runtime, &id.name, &expression.params, &expression.body, FunctionKind::Normal, expression.generator, expression.is_async);
+
+    runtime.enter_environment(outer_environment);
+    environment.borrow_mut().declare(&id.name, function.clone());
+
+    function
+}
+
+pub fn parse_arrow_function_expression(runtime: &mut Runtime, expression: &ArrowFunctionExpression, name: &str) -> Value {
+    match &expression.body {
+        ArrowFunctionBody::BlockStatement(body) => create_function(runtime, name, &expression.params, body, FunctionKind::Arrow, false, expression.is_async),
+        ArrowFunctionBody::Expression(body) => create_function(runtime, name, &expression.params, &return_block(body), FunctionKind::Arrow, false, expression.is_async),
+    }
+}
+
+// A body that returns the value of `expression`, for concise arrow bodies and class field initializers
+pub fn return_block(expression: &Expression) -> BlockStatement {
+    let statement = ReturnStatement {
+        argument: Some(expression.clone()),
+        range: expression.range(),
+    };
+
+    BlockStatement {
+        body: vec![AstNode::ReturnStatement(statement)],
+        range: expression.range(),
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{ast::nodes::IfStatement, runtime::{operations::to_boolean, nodes::{Completion, RuntimeResult}, Runtime}};
+
+use super::expression::parse_expression;
+
+pub fn parse_if_statement(runtime: &mut Runtime, statement: &IfStatement) -> RuntimeResult<Completion> {
+    let test = parse_expression(runtime, &statement.test)?;
+
+    if to_boolean(&test) {
+        return runtime.run_statement(&statement.consequent);
+    }
+
+    match &statement.alternate {
+        Some(alternate) => runtime.run_statement(alternate),
+        None => Ok(Completion::Normal),
+    }
+}
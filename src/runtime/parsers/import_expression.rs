@@ -0,0 +1,38 @@
+use crate::{ast::nodes::ImportExpression, runtime::{builtins::{new_promise, on_settled, reject_promise, resolve_promise}, module::{import_module, module_namespace}, nodes::RuntimeResult, operations::to_string, Runtime, Value}};
+
+use super::expression::parse_expression;
+
+// `import(specifier)`, the specifier is resolved against the script or module the call is in.
+// The module is loaded and run in a job, so like in other hosts it never runs in the middle of the code importing it.
+// Errors while loading, linking or running the module reject the promise instead of being thrown.
+pub fn parse_import_expression(runtime: &mut Runtime, expression: &ImportExpression) -> RuntimeResult<Value> {
+    let specifier = parse_expression(runtime, &expression.source)?;
+    let referrer = runtime.current_script().name.clone();
+    let promise = new_promise(runtime);
+
+    let specifier = match to_string(runtime, &specifier) {
+        Ok(specifier) => specifier,
+        Err(error) => {
+            reject_promise(runtime, &promise, error);
+            return Ok(Value::Object(promise));
+        },
+    };
+
+    let import_promise = promise.clone();
+
+    runtime.event_loop.enqueue(move |runtime| {
+        let promise = import_promise;
+
+        match import_module(runtime, &specifier, Some(&referrer)) {
+            Ok((module, evaluation)) => on_settled(runtime, &Value::Object(evaluation), move |runtime, result| match result {
+                Ok(_) => resolve_promise(runtime, &promise, Value::Object(module_namespace(&module))),
+                Err(error) => reject_promise(runtime, &promise, error),
+            }),
+            Err(error) => reject_promise(runtime, &promise, error),
+        }
+
+        Ok(())
+    });
+
+    Ok(Value::Object(promise))
+}
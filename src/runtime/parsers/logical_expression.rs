@@ -0,0 +1,21 @@
+use crate::{ast::nodes::LogicalExpression, runtime::{nodes::RuntimeResult, operations::to_boolean, Runtime, Value}};
+
+use super::expression::parse_expression;
+
+pub fn parse_logical_expression(runtime: &mut Runtime, expression: &LogicalExpression) -> RuntimeResult<Value> {
+    let left = parse_expression(runtime, &expression.left)?;
+
+    // The right side is only evaluated when the left side does not decide the result
+    let short_circuit = match expression.operator.as_str() {
+        "&&" => !to_boolean(&left),
+        "||" => to_boolean(&left),
+        "??" => !matches!(left, Value::Undefined | Value::Null),
+        operator => panic!("Unsupported logical operator: {}", operator)
+    };
+
+    if short_circuit {
+        return Ok(left);
+    }
+
+    parse_expression(runtime, &expression.right)
+}
@@ -0,0 +1,7 @@
+use crate::{ast::nodes::MemberExpression, runtime::{nodes::RuntimeResult, reference::{evaluate_member, get_value}, Runtime, Value}};
+
+pub fn parse_member_expression(runtime: &mut Runtime, expression: &MemberExpression) -> RuntimeResult<Value> {
+    let reference = evaluate_member(runtime, expression)?;
+
+    get_value(runtime, &reference)
+}
@@ -1,3 +1,29 @@
+pub mod array_expression;
+pub mod assignment_expression;
+pub mod await_expression;
+pub mod binary_expression;
+pub mod block_statement;
+pub mod call_expression;
+pub mod chain_expression;
+pub mod class_expression;
+pub mod expression;
+pub mod export_declaration;
 pub mod expression_statement;
+pub mod for_of_statement;
+pub mod function_expression;
+pub mod if_statement;
+pub mod import_expression;
+pub mod logical_expression;
+pub mod member_expression;
+pub mod new_expression;
+pub mod object_expression;
+pub mod pattern;
+pub mod return_statement;
+pub mod throw_statement;
+pub mod try_statement;
+pub mod unary_expression;
+pub mod update_expression;
 pub mod variable_declaration;
-pub mod parse_function_declaration;
\ No newline at end of file
+pub mod parse_function_declaration;
+pub mod while_statement;
+pub mod yield_expression;
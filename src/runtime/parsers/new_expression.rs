@@ -0,0 +1,152 @@
+use crate::{ast::nodes::NewExpression, runtime::{call_stack::check_call_depth, environment::{this_environment, this_value, Environment, EnvironmentRef, ThisBinding}, nodes::{FunctionCallType, RuntimeResult}, object::{get, Object, ObjectRef, PropertyKey}, operations::same_value, Runtime, Value}};
+
+use super::{call_expression::{call_function, callee_name, evaluate_arguments, evaluate_body}, class_expression::initialize_instance_elements, expression::parse_expression};
+
+pub fn parse_new_expression(runtime: &mut Runtime, expression: &NewExpression) -> RuntimeResult<Value> {
+    let constructor = parse_expression(runtime, &expression.callee)?;
+
+    let args_values = evaluate_arguments(runtime, &expression.arguments)?;
+
+    if !is_constructor(&constructor) {
+        return runtime.type_error(&format!("{} is not a constructor", callee_name(&expression.callee)));
+    }
+
+    runtime.set_position(expression.range.0);
+
+    construct(runtime, &constructor, args_values, &constructor)
+}
+
+pub fn is_constructor(value: &Value) -> bool {
+    value.as_function().is_some_and(|function| function.constructor)
+}
+
+// [[Construct]], the new object inherits from `new_target.prototype` unless the body returns an object of its own.
+// `new_target` is the constructor `new` was applied to, which differs from `constructor` inside `super()`.
+pub fn construct(runtime: &mut Runtime, constructor: &Value, args_values: Vec<Value>, new_target: &Value) -> RuntimeResult<Value> {
+    check_call_depth(runtime)?;
+
+    let (object, function) = match (constructor, constructor.as_function()) {
+        (Value::Object(object), Some(function)) => (object, function),
+        _ => return runtime.type_error(&format!("{} is not a constructor", constructor.to_display_string())),
+    };
+
+    let runtime_call = match &function.function_type {
+        // A bound function constructs its target, the bound `this` is ignored but the bound arguments are not
+        FunctionCallType::BoundFunction(bound) => {
+            let arguments = bound.arguments.iter().cloned().chain(args_values).collect();
+            let new_target = if same_value(constructor, new_target) { &bound.target } else { new_target };
+
+            return construct(runtime, &bound.target, arguments, new_target);
+        },
+        // Built-in constructors create their own objects, a subclass only changes what they inherit from
+        FunctionCallType::SystemCall(_) => {
+            let this_value = Value::Object(Object::new_ordinary(Some(prototype_from_constructor(runtime, new_target)?)));
+            let result = call_function(runtime, &function, this_value.clone(), args_values)?;
+
+            return Ok(match result {
+                Value::Object(result) => {
+                    if !same_value(constructor, new_target) {
+                        result.borrow_mut().prototype = Some(prototype_from_constructor(runtime, new_target)?);
+                    }
+
+                    Value::Object(result)
+                },
+                _ => this_value,
+            });
+        },
+        FunctionCallType::RuntimeCall(runtime_call) => runtime_call,
+    };
+
+    let class = runtime_call.class.clone();
+    let derived = class.as_ref().is_some_and(|class| class.derived);
+
+    // A derived constructor has no `this` until `super()` returns one
+    let this_binding = match derived {
+        true => ThisBinding::Uninitialized,
+        false => ThisBinding::Initialized(Value::Object(Object::new_ordinary(Some(prototype_from_constructor(runtime, new_target)?)))),
+    };
+
+    let environment = Environment::new_function(&runtime_call.environment, runtime_call, this_binding);
+
+    {
+        let mut function_environment = environment.borrow_mut();
+        function_environment.new_target = new_target.clone();
+        function_environment.function_object = Some(object.clone());
+    }
+
+    // Base classes set up their fields before the constructor body runs
+    if !derived {
+        if let Some(class) = &class {
+            let this_value = this_value(runtime, &environment)?;
+            initialize_instance_elements(runtime, &this_value, class)?;
+        }
+    }
+
+    let result = match &class {
+        Some(class) if class.derived && class.default_constructor => {
+            super_call(runtime, &environment, args_values)?;
+            Value::Undefined
+        },
+        _ => evaluate_body(runtime, &function, runtime_call, environment.clone(), args_values)?,
+    };
+
+    match result {
+        Value::Object(_) => Ok(result),
+        Value::Undefined => this_value(runtime, &environment),
+        _ if derived => runtime.type_error("Derived constructors may only return object or undefined"),
+        _ => this_value(runtime, &environment),
+    }
+}
+
+// `super(...)`, constructs the parent class for the current `new` and makes the result `this`
+pub fn super_call(runtime: &mut Runtime, environment: &EnvironmentRef, args_values: Vec<Value>) -> RuntimeResult<Value> {
+    let environment = this_environment(environment);
+
+    let (function_object, new_target) = match &environment.borrow().function_object {
+        Some(function_object) => (function_object.clone(), environment.borrow().new_target.clone()),
+        None => return runtime.syntax_error("'super' keyword unexpected here"),
+    };
+
+    // GetSuperConstructor, the parent is whatever the class currently inherits from
+    let super_constructor = match function_object.borrow().prototype.clone() {
+        Some(prototype) => Value::Object(prototype),
+        None => Value::Null,
+    };
+
+    if !is_constructor(&super_constructor) {
+        return runtime.type_error(&format!("Super constructor {} of anonymous class is not a constructor", super_constructor.to_display_string()));
+    }
+
+    let result = construct(runtime, &super_constructor, args_values, &new_target)?;
+
+    if let ThisBinding::Initialized(_) = environment.borrow().this_binding {
+        return runtime.reference_error("Super constructor may only be called once");
+    }
+
+    environment.borrow_mut().this_binding = ThisBinding::Initialized(result.clone());
+
+    let class = function_object.borrow().function()
+        .and_then(|function| match &function.function_type {
+            FunctionCallType::RuntimeCall(runtime_call) => runtime_call.class.clone(),
+            _ => None,
+        });
+
+    if let Some(class) = class {
+        initialize_instance_elements(runtime, &result, &class)?;
+    }
+
+    Ok(result)
+}
+
+// GetPrototypeFromConstructor, falls back to Object.prototype when `prototype` is not an object
+fn prototype_from_constructor(runtime: &mut Runtime, constructor: &Value) -> RuntimeResult<ObjectRef> {
+    let prototype = match constructor {
+        Value::Object(object) => get(runtime, object, &PropertyKey::from("prototype"), constructor)?,
+        _ => Value::Undefined,
+    };
+
+    Ok(match prototype {
+        Value::Object(prototype) => prototype,
+        _ => runtime.intrinsics.object_prototype.clone(),
+    })
+}
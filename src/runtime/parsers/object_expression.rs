@@ -0,0 +1,92 @@
+use crate::{ast::nodes::{Expression, ObjectExpression, ObjectMember, Property, PropertyKind}, runtime::{nodes::RuntimeResult, object::{set_prototype_of, Object, PropertyDescriptor, PropertyKey}, operations::{copy_data_properties, number_to_string, to_property_key}, Runtime, Value}};
+
+use super::{expression::{parse_expression, parse_named_expression}, parse_function_declaration::{create_function, function_name, FunctionKind}};
+
+pub fn parse_object_expression(runtime: &mut Runtime, expression: &ObjectExpression) -> RuntimeResult<Value> {
+    let object = Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()));
+
+    // Properties are evaluated and defined in source order, later keys overwrite earlier ones
+    for member in &expression.properties {
+        let property = match member {
+            ObjectMember::Property(property) => property,
+            ObjectMember::SpreadElement(spread) => {
+                let source = parse_expression(runtime, &spread.argument)?;
+                copy_data_properties(runtime, &object, &source, &[])?;
+                continue;
+            },
+        };
+
+        // `__proto__: value` sets the prototype instead of defining a property (Annex B.3.1),
+        // values other than objects and null are ignored
+        if is_proto_setter(property) {
+            match parse_expression(runtime, &property.value)? {
+                Value::Object(prototype) => set_prototype_of(&object, Some(prototype)),
+                Value::Null => set_prototype_of(&object, None),
+                _ => true,
+            };
+
+            continue;
+        }
+
+        let key = property_key(runtime, &property.key, property.computed)?;
+
+        let value = match &property.value {
+            Expression::FunctionExpression(function) => {
+                let prefix = match property.kind {
+                    PropertyKind::Init => None,
+                    PropertyKind::Get => Some("get"),
+                    PropertyKind::Set => Some("set"),
+                };
+
+                let name = function_name(&key, prefix);
+                create_function(runtime, &name, &function.params, &function.body, FunctionKind::Method(object.clone()), function.generator, function.is_async)
+            },
+            value => parse_named_expression(runtime, value, &function_name(&key, None))?,
+        };
+
+        let descriptor = match property.kind {
+            PropertyKind::Init => {
+                object.borrow_mut().create_data_property(key, value);
+                continue;
+            },
+            PropertyKind::Get => PropertyDescriptor { get: Some(value), ..Default::default() },
+            PropertyKind::Set => PropertyDescriptor { set: Some(value), ..Default::default() },
+        };
+
+        // A getter and a setter with the same name end up on one accessor property
+        object.borrow_mut().define_own_property(key, PropertyDescriptor {
+            enumerable: Some(true),
+            configurable: Some(true),
+            ..descriptor
+        });
+    }
+
+    Ok(Value::Object(object))
+}
+
+// Only a plain `__proto__: value` counts, not a shorthand, a method or a computed key
+fn is_proto_setter(property: &Property) -> bool {
+    let is_proto_key = match &property.key {
+        Expression::Identifier(identifier) => identifier.name == "__proto__",
+        Expression::StringLiteral(literal) => literal.value == "__proto__",
+        _ => false,
+    };
+
+    is_proto_key && !property.computed && !property.shorthand && !property.method && property.kind == PropertyKind::Init
+}
+
+// The key of an object literal property or a class element, computed keys are evaluated here
+pub fn property_key(runtime: &mut Runtime, key: &Expression, computed: bool) -> RuntimeResult<PropertyKey> {
+    if computed {
+        let key = parse_expression(runtime, key)?;
+        return to_property_key(runtime, &key);
+    }
+
+    Ok(match key {
+        Expression::Identifier(identifier) => PropertyKey::from(identifier.name.as_str()),
+        Expression::StringLiteral(literal) => PropertyKey::from(literal.value.as_str()),
+        Expression::NumericLiteral(literal) => PropertyKey::from(number_to_string(literal.value).as_str()),
+        Expression::BigIntLiteral(literal) => PropertyKey::from(literal.value.to_string().as_str()),
+        key => panic!("Invalid property key at {:?}", key.range()),
+    })
+}
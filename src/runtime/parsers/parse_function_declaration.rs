@@ -1,16 +1,147 @@
-use crate::{ast::nodes::FunctionDeclaration, runtime::{Runtime, nodes::{FunctionCall, FunctionCallType}}};
+use std::{cell::RefCell, rc::{Rc, Weak}};
 
-pub fn parse_function_declaration(runtime: &mut Runtime, statement: &FunctionDeclaration) {
-    let current_scope = runtime.current_scope();
-    
-    let name = statement.id.name.to_string();
-    let params = statement.to_owned().params;
-    let body = statement.to_owned().body;    
+use crate::{ast::nodes::{BlockStatement, FunctionDeclaration, Pattern}, runtime::{Runtime, Value, declarations::has_use_strict_directive, environment::variable_environment, nodes::{ClassDefinition, FunctionCall, FunctionCallType, RuntimeCall, RuntimeResult}, object::{Object, ObjectRef, PropertyDescriptor, PropertyKey}}};
 
+pub enum FunctionKind {
+    // Function declarations and expressions, which can also be used with `new`
+    Normal,
+    // Object and class methods, accessors, field initializers and static blocks, the object is their home object
+    Method(ObjectRef),
+    Arrow,
+    // The class sets up the `prototype` itself
+    ClassConstructor { home_object: ObjectRef, class: Rc<ClassDefinition> },
+}
+
+// Function declarations are created ahead of time by declaration instantiation, so evaluating one
+// only has work to do inside a block, where its value is copied to the `var` binding (Annex B.3.3)
+pub fn parse_function_declaration(runtime: &mut Runtime, statement: &FunctionDeclaration) -> RuntimeResult<()> {
+    if runtime.environment.borrow().function_scope {
+        return Ok(());
+    }
+
+    let name = &statement.id.name;
+    let environment = variable_environment(&runtime.environment);
+
+    if !environment.borrow().block_functions.contains(&statement.range.0) {
+        return Ok(());
+    }
+
+    if let Some(function) = runtime.variable(name)? {
+        environment.borrow_mut().declare(name, function);
+    }
+
+    Ok(())
+}
+
+// Only plain functions and generators get a `prototype` object here, for the instances `new` creates
+// and for the generator objects a call returns
+pub fn create_function(runtime: &Runtime, name: &str, params: &[Pattern], body: &BlockStatement, kind: FunctionKind, generator: bool, is_async: bool) -> Value {
+    let name = name.to_string();
+    let body = body.clone();
+
+    // The `length` counts the parameters before the first one with a default or the rest parameter
+    let length = params.iter()
+        .take_while(|param| !matches!(param, Pattern::AssignmentPattern(_) | Pattern::RestElement(_)))
+        .count();
+
+    // Functions inside strict code are strict, others can opt in with their own directive
+    let strict = runtime.is_strict() || has_use_strict_directive(&body.body);
+
+    let arrow = matches!(kind, FunctionKind::Arrow);
+
+    let (constructor, home_object, class) = match kind {
+        FunctionKind::Normal => (!generator && !is_async, None, None),
+        FunctionKind::Method(home_object) => (false, Some(home_object), None),
+        FunctionKind::Arrow => (false, None, None),
+        FunctionKind::ClassConstructor { home_object, class } => (true, Some(home_object), Some(class)),
+    };
+    let plain_function = constructor && class.is_none();
+
+    // Capture the current environment, so the body sees the variables in scope where it was written
     let function_call = FunctionCall {
-        function_type: FunctionCallType::RuntimeCall(body),
-        arguments: vec![],
+        name,
+        function_type: FunctionCallType::RuntimeCall(RuntimeCall {
+            params: params.to_vec(),
+            body,
+            environment: runtime.environment.clone(),
+            strict,
+            home_object,
+            class,
+            arrow,
+            generator,
+            is_async,
+            script: runtime.current_script(),
+            function_object: RefCell::new(Weak::new()),
+        }),
+        length,
+        constructor,
     };
 
-    current_scope.functions.insert(name, function_call);    
-}
\ No newline at end of file
+    let function_prototype = match (generator, is_async) {
+        (true, true) => &runtime.intrinsics.async_generator_function_prototype,
+        (true, false) => &runtime.intrinsics.generator_function_prototype,
+        (false, true) => &runtime.intrinsics.async_function_prototype,
+        (false, false) => &runtime.intrinsics.function_prototype,
+    };
+
+    let function = Object::new_function(function_call, function_prototype);
+
+    if let Some(FunctionCallType::RuntimeCall(runtime_call)) = function.borrow().function().as_ref().map(|function| &function.function_type) {
+        *runtime_call.function_object.borrow_mut() = Rc::downgrade(&function);
+    }
+
+    // The generator objects inherit from it, it has no `constructor` pointing back
+    if generator {
+        let generator_prototype = match is_async {
+            true => &runtime.intrinsics.async_generator_prototype,
+            false => &runtime.intrinsics.generator_prototype,
+        };
+        let prototype = Object::new_ordinary(Some(generator_prototype.clone()));
+
+        function.borrow_mut().define_own_property(PropertyKey::from("prototype"), PropertyDescriptor {
+            value: Some(Value::Object(prototype)),
+            writable: Some(true),
+            enumerable: Some(false),
+            configurable: Some(false),
+            ..Default::default()
+        });
+    }
+
+    if plain_function {
+        let prototype = Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()));
+
+        prototype.borrow_mut().define_builtin("constructor", Value::Object(function.clone()));
+        function.borrow_mut().define_own_property(PropertyKey::from("prototype"), PropertyDescriptor {
+            value: Some(Value::Object(prototype)),
+            writable: Some(true),
+            enumerable: Some(false),
+            configurable: Some(false),
+            ..Default::default()
+        });
+    }
+
+    Value::Object(function)
+}
+
+// SetFunctionName, a symbol key names the function after its description and accessors get a prefix
+pub fn function_name(key: &PropertyKey, prefix: Option<&str>) -> String {
+    let name = match key {
+        PropertyKey::Symbol(symbol) => format!("[{}]", symbol.description.clone().unwrap_or_default()),
+        PropertyKey::String(name) => name.to_string(),
+    };
+
+    match prefix {
+        Some(prefix) => format!("{} {}", prefix, name),
+        None => name,
+    }
+}
+
+// Names a function after it was created, for anonymous functions in class fields
+pub fn set_function_name(function: &Value, name: &str) {
+    if let Value::Object(function) = function {
+        function.borrow_mut().define_own_property(PropertyKey::from("name"), PropertyDescriptor {
+            value: Some(Value::string(name)),
+            ..Default::default()
+        });
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{ast::nodes::{ArrayPattern, ObjectPattern, Pattern}, runtime::{environment::EnvironmentRef, nodes::RuntimeResult, object::Object, operations::{copy_data_properties, create_array_from_list, get_iterator, get_property, iterator_close, iterator_step_value, IteratorRecord}, reference::{evaluate_member, put_value, Reference}, Runtime, Value}};
+
+use super::{expression::{parse_expression, parse_named_expression}, object_expression::property_key};
+
+// Binds `value` to the names in `pattern`. Declarations and parameters pass the environment their names
+// are initialized in, destructuring assignments pass None and assign to whatever the names resolve to.
+pub fn bind_pattern(runtime: &mut Runtime, pattern: &Pattern, value: Value, environment: Option<&EnvironmentRef>) -> RuntimeResult<()> {
+    match pattern {
+        Pattern::Identifier(identifier) => match environment {
+            Some(environment) => {
+                environment.borrow_mut().initialize(&identifier.name, value);
+                Ok(())
+            },
+            None => runtime.assign_variable(&identifier.name, value),
+        },
+        Pattern::MemberExpression(member) => {
+            let reference = evaluate_member(runtime, member)?;
+            put_value(runtime, reference, value)
+        },
+        // Only undefined is replaced by the default, null is a value like any other
+        Pattern::AssignmentPattern(pattern) => {
+            let value = match (value, &pattern.left) {
+                (Value::Undefined, Pattern::Identifier(identifier)) => parse_named_expression(runtime, &pattern.right, &identifier.name)?,
+                (Value::Undefined, _) => parse_expression(runtime, &pattern.right)?,
+                (value, _) => value,
+            };
+
+            bind_pattern(runtime, &pattern.left, value, environment)
+        },
+        Pattern::ObjectPattern(pattern) => bind_object_pattern(runtime, pattern, value, environment),
+        Pattern::ArrayPattern(pattern) => bind_array_pattern(runtime, pattern, value, environment),
+        // The caller has already collected the rest of the values into an array
+        Pattern::RestElement(rest) => bind_pattern(runtime, &rest.argument, value, environment),
+    }
+}
+
+// The rest object gets the own enumerable properties the pattern did not name
+fn bind_object_pattern(runtime: &mut Runtime, pattern: &ObjectPattern, value: Value, environment: Option<&EnvironmentRef>) -> RuntimeResult<()> {
+    if let Value::Undefined | Value::Null = value {
+        let text = value.to_display_string();
+        return runtime.type_error(&format!("Cannot destructure '{}' as it is {}.", text, text));
+    }
+
+    let mut keys = Vec::new();
+
+    for property in &pattern.properties {
+        let key = property_key(runtime, &property.key, property.computed)?;
+        let target = evaluate_target(runtime, &property.value)?;
+        let property_value = get_property(runtime, &value, &key)?;
+
+        bind_target(runtime, &property.value, target, property_value, environment)?;
+        keys.push(key);
+    }
+
+    if let Some(rest) = &pattern.rest {
+        let target = evaluate_target(runtime, &rest.argument)?;
+        let object = Object::new_ordinary(Some(runtime.intrinsics.object_prototype.clone()));
+        copy_data_properties(runtime, &object, &value, &keys)?;
+
+        bind_target(runtime, &rest.argument, target, Value::Object(object), environment)?;
+    }
+
+    Ok(())
+}
+
+// Values are taken from the iterator as the elements need them, the iterator is closed when the pattern ends before it does
+fn bind_array_pattern(runtime: &mut Runtime, pattern: &ArrayPattern, value: Value, environment: Option<&EnvironmentRef>) -> RuntimeResult<()> {
+    let record = get_iterator(runtime, &value)?;
+    let mut done = false;
+
+    let result = bind_array_elements(runtime, pattern, &record, &mut done, environment);
+
+    match done {
+        true => result,
+        false => iterator_close(runtime, &record, result),
+    }
+}
+
+// Missing values are undefined, a rest element takes whatever values are left as an array
+fn bind_array_elements(runtime: &mut Runtime, pattern: &ArrayPattern, record: &IteratorRecord, done: &mut bool, environment: Option<&EnvironmentRef>) -> RuntimeResult<()> {
+    for element in &pattern.elements {
+        match element {
+            None => {
+                step_value(runtime, record, done)?;
+            },
+            Some(Pattern::RestElement(rest)) => {
+                let target = evaluate_target(runtime, &rest.argument)?;
+                let mut values = Vec::new();
+
+                while let Some(value) = step_value(runtime, record, done)? {
+                    values.push(value);
+                }
+
+                let rest_values = create_array_from_list(runtime, values);
+                bind_target(runtime, &rest.argument, target, rest_values, environment)?;
+            },
+            Some(element) => {
+                let target = evaluate_target(runtime, element)?;
+                let element_value = step_value(runtime, record, done)?.unwrap_or(Value::Undefined);
+                bind_target(runtime, element, target, element_value, environment)?;
+            },
+        }
+    }
+
+    Ok(())
+}
+
+// A property target like `t[k()]` is evaluated before its value is read, other targets are bound afterwards
+fn evaluate_target(runtime: &mut Runtime, pattern: &Pattern) -> RuntimeResult<Option<Reference>> {
+    match pattern {
+        Pattern::MemberExpression(member) => Ok(Some(evaluate_member(runtime, member)?)),
+        Pattern::AssignmentPattern(pattern) => evaluate_target(runtime, &pattern.left),
+        _ => Ok(None),
+    }
+}
+
+// Binds `value` to the target `evaluate_target` found, the default still comes before the assignment
+fn bind_target(runtime: &mut Runtime, pattern: &Pattern, target: Option<Reference>, value: Value, environment: Option<&EnvironmentRef>) -> RuntimeResult<()> {
+    let reference = match target {
+        Some(reference) => reference,
+        None => return bind_pattern(runtime, pattern, value, environment),
+    };
+
+    let value = match (value, pattern) {
+        (Value::Undefined, Pattern::AssignmentPattern(pattern)) => parse_expression(runtime, &pattern.right)?,
+        (value, _) => value,
+    };
+
+    put_value(runtime, reference, value)
+}
+
+// An iterator that finished or threw is done, it is neither stepped nor closed after that
+fn step_value(runtime: &mut Runtime, record: &IteratorRecord, done: &mut bool) -> RuntimeResult<Option<Value>> {
+    if *done {
+        return Ok(None);
+    }
+
+    let result = iterator_step_value(runtime, record);
+
+    if !matches!(result, Ok(Some(_))) {
+        *done = true;
+    }
+
+    result
+}
+
+// The names a pattern declares, for declaration instantiation
+pub fn bound_names(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Identifier(identifier) => names.push(identifier.name.clone()),
+        Pattern::MemberExpression(_) => {},
+        Pattern::ObjectPattern(pattern) => {
+            for property in &pattern.properties {
+                bound_names(&property.value, names);
+            }
+
+            if let Some(rest) = &pattern.rest {
+                bound_names(&rest.argument, names);
+            }
+        },
+        Pattern::ArrayPattern(pattern) => {
+            for element in pattern.elements.iter().flatten() {
+                bound_names(element, names);
+            }
+        },
+        Pattern::AssignmentPattern(pattern) => bound_names(&pattern.left, names),
+        Pattern::RestElement(rest) => bound_names(&rest.argument, names),
+    }
+}
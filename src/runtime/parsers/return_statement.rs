@@ -0,0 +1,12 @@
+use crate::{ast::nodes::ReturnStatement, runtime::{nodes::{Completion, RuntimeResult}, Runtime, Value}};
+
+use super::expression::parse_expression;
+
+pub fn parse_return_statement(runtime: &mut Runtime, statement: &ReturnStatement) -> RuntimeResult<Completion> {
+    let value = match &statement.argument {
+        Some(argument) => parse_expression(runtime, argument)?,
+        None => Value::undefined(),
+    };
+
+    Ok(Completion::Return(value))
+}
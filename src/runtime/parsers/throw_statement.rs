@@ -0,0 +1,10 @@
+use crate::{ast::nodes::ThrowStatement, runtime::{nodes::{Completion, RuntimeResult}, Runtime}};
+
+use super::expression::parse_expression;
+
+// Any value can be thrown, it unwinds to the closest `catch` as the error of the result
+pub fn parse_throw_statement(runtime: &mut Runtime, statement: &ThrowStatement) -> RuntimeResult<Completion> {
+    let value = parse_expression(runtime, &statement.argument)?;
+
+    Err(value)
+}
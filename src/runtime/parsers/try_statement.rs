@@ -0,0 +1,45 @@
+use crate::{ast::nodes::{CatchClause, TryStatement}, runtime::{builtins::generator_return_value, environment::Environment, nodes::{Completion, RuntimeResult}, Runtime, Value}};
+
+use super::{block_statement::parse_block_statement, pattern::bind_pattern};
+
+// The catch block handles an exception from the try block.
+// The finally block always runs and its own abrupt completion replaces the one before it.
+pub fn parse_try_statement(runtime: &mut Runtime, statement: &TryStatement) -> RuntimeResult<Completion> {
+    let mut completion = parse_block_statement(runtime, &statement.block);
+
+    // The `return` of a suspended generator passes catch blocks by, it only stops for finally blocks
+    if let (Err(error), Some(handler)) = (&completion, &statement.handler) {
+        if generator_return_value(error).is_none() {
+            completion = parse_catch_clause(runtime, handler, error.clone());
+        }
+    }
+
+    let finalizer = match &statement.finalizer {
+        Some(finalizer) => finalizer,
+        None => return completion,
+    };
+
+    match parse_block_statement(runtime, finalizer)? {
+        Completion::Normal => completion,
+        finalizer_completion => Ok(finalizer_completion),
+    }
+}
+
+// The binding lives in its own scope around the catch block
+fn parse_catch_clause(runtime: &mut Runtime, handler: &CatchClause, error: Value) -> RuntimeResult<Completion> {
+    let param = match &handler.param {
+        Some(param) => param,
+        None => return parse_block_statement(runtime, &handler.body),
+    };
+
+    let environment = Environment::new(&runtime.environment);
+    let outer_environment = runtime.enter_environment(environment.clone());
+
+    // Defaults in a destructured binding run inside the catch scope
+    let completion = bind_pattern(runtime, param, error, Some(&environment))
+        .and_then(|_| parse_block_statement(runtime, &handler.body));
+
+    runtime.enter_environment(outer_environment);
+
+    completion
+}
@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use crate::{ast::nodes::{Expression, UnaryExpression}, runtime::{environment::delete_variable, nodes::RuntimeResult, operations::{to_boolean, to_int32, to_number, to_numeric, to_object, type_of}, reference::{evaluate_member, Reference}, Runtime, Value}};
+
+use super::expression::parse_expression;
+
+pub fn parse_unary_expression(runtime: &mut Runtime, expression: &UnaryExpression) -> RuntimeResult<Value> {
+    match expression.operator.as_str() {
+        "typeof" => return parse_typeof(runtime, &expression.argument),
+        "delete" => return parse_delete(runtime, &expression.argument),
+        _ => {},
+    }
+
+    let argument = parse_expression(runtime, &expression.argument)?;
+
+    Ok(match expression.operator.as_str() {
+        "!" => Value::Boolean(!to_boolean(&argument)),
+        // Unary plus is ToNumber and throws on BigInts, the others keep them
+        "+" => Value::Number(to_number(runtime, &argument)?),
+        "-" => match to_numeric(runtime, &argument)? {
+            Value::BigInt(value) => Value::BigInt(Rc::new(-value.as_ref())),
+            value => Value::Number(-to_number(runtime, &value)?),
+        },
+        "~" => match to_numeric(runtime, &argument)? {
+            Value::BigInt(value) => Value::BigInt(Rc::new(!value.as_ref())),
+            value => Value::Number(!to_int32(to_number(runtime, &value)?) as f64),
+        },
+        "void" => Value::Undefined,
+        operator => panic!("Unsupported unary operator: {}", operator)
+    })
+}
+
+// `typeof` an undeclared variable is "undefined" instead of an error, one that is not initialized yet still throws
+fn parse_typeof(runtime: &mut Runtime, argument: &Expression) -> RuntimeResult<Value> {
+    let value = match argument {
+        Expression::Identifier(identifier) => runtime.variable(&identifier.name)?.unwrap_or(Value::Undefined),
+        argument => parse_expression(runtime, argument)?,
+    };
+
+    Ok(Value::string(type_of(&value)))
+}
+
+// Only properties and global object bindings can be deleted, anything else is evaluated and gives true
+fn parse_delete(runtime: &mut Runtime, argument: &Expression) -> RuntimeResult<Value> {
+    let member = match argument {
+        Expression::MemberExpression(member) => member,
+        Expression::Identifier(identifier) => {
+            if runtime.is_strict() {
+                return runtime.syntax_error("Delete of an unqualified identifier in strict mode.");
+            }
+
+            return Ok(Value::Boolean(delete_variable(&runtime.environment, &identifier.name)));
+        },
+        argument => {
+            parse_expression(runtime, argument)?;
+            return Ok(Value::Boolean(true));
+        },
+    };
+
+    match evaluate_member(runtime, member)? {
+        Reference::Property { base, key } => {
+            let object = to_object(runtime, &base)?;
+            let deleted = object.borrow_mut().delete(&key);
+
+            // Strict code gets an error for a property that cannot be deleted, sloppy code only gets false
+            if !deleted && runtime.is_strict() {
+                return runtime.type_error(&format!("Cannot delete property '{}' of {}", key, base.to_display_string()));
+            }
+
+            Ok(Value::Boolean(deleted))
+        },
+        Reference::SuperProperty { .. } => runtime.reference_error("Unsupported reference to 'super'"),
+        _ => runtime.syntax_error("Private fields can not be deleted"),
+    }
+}
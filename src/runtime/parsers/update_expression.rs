@@ -0,0 +1,27 @@
+use std::rc::Rc;
+
+use num_bigint::BigInt;
+
+use crate::{ast::nodes::UpdateExpression, runtime::{nodes::RuntimeResult, operations::to_numeric, reference::{evaluate_reference, get_value, put_value}, Runtime, Value}};
+
+pub fn parse_update_expression(runtime: &mut Runtime, expression: &UpdateExpression) -> RuntimeResult<Value> {
+    let reference = evaluate_reference(runtime, &expression.argument)?;
+    let old_value = get_value(runtime, &reference)?;
+    let old_value = to_numeric(runtime, &old_value)?;
+
+    let step = if expression.operator == "++" { 1 } else { -1 };
+
+    let new_value = match &old_value {
+        Value::BigInt(value) => Value::BigInt(Rc::new(value.as_ref() + BigInt::from(step))),
+        Value::Number(value) => Value::Number(value + step as f64),
+        _ => unreachable!(),
+    };
+
+    put_value(runtime, reference, new_value.clone())?;
+
+    Ok(if expression.prefix {
+        new_value
+    } else {
+        old_value
+    })
+}
@@ -1,10 +1,26 @@
-use crate::{runtime::Runtime, ast::nodes::VariableDeclaration};
+use crate::{ast::nodes::{Pattern, VariableDeclaration, VariableKind}, runtime::{nodes::RuntimeResult, Runtime, Value}};
 
-pub fn parse_variable_declaration(runtime: &mut Runtime, declaration: &VariableDeclaration) {
+use super::{expression::{parse_expression, parse_named_expression}, pattern::bind_pattern};
+
+pub fn parse_variable_declaration(runtime: &mut Runtime, declaration: &VariableDeclaration) -> RuntimeResult<()> {
     for variable in declaration.declarations.iter() {
-        let name = variable.id.name.clone();
-        let literal = variable.init.clone();
+        let value = match (&variable.init, &variable.id) {
+            (Some(init), Pattern::Identifier(identifier)) => parse_named_expression(runtime, init, &identifier.name)?,
+            (Some(init), _) => parse_expression(runtime, init)?,
+            // `var x;` leaves the binding as it is, `let x;` initializes it to undefined
+            (None, _) if declaration.kind == VariableKind::Var => continue,
+            (None, _) => Value::Undefined,
+        };
+
+        match declaration.kind {
+            // The `var` bindings themselves were created by declaration instantiation
+            VariableKind::Var => bind_pattern(runtime, &variable.id, value, None)?,
+            VariableKind::Let | VariableKind::Const => {
+                let environment = runtime.environment.clone();
+                bind_pattern(runtime, &variable.id, value, Some(&environment))?;
+            },
+        }
+    }
 
-        runtime.current_scope().variables.insert(name, literal);
-    } 
-}
\ No newline at end of file
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::{ast::nodes::WhileStatement, runtime::{operations::to_boolean, nodes::{Completion, RuntimeResult}, Runtime}};
+
+use super::expression::parse_expression;
+
+pub fn parse_while_statement(runtime: &mut Runtime, statement: &WhileStatement) -> RuntimeResult<Completion> {
+    loop {
+        let test = parse_expression(runtime, &statement.test)?;
+
+        if !to_boolean(&test) {
+            return Ok(Completion::Normal);
+        }
+
+        match runtime.run_statement(&statement.body)? {
+            Completion::Break => return Ok(Completion::Normal),
+            Completion::Return(value) => return Ok(Completion::Return(value)),
+            Completion::Normal | Completion::Continue => {}
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{ast::nodes::{Expression, YieldExpression}, runtime::{builtins::generator_return, coroutine::{suspend, ResumeAction, Suspension}, nodes::RuntimeResult, object::PropertyKey, operations::{call, create_iter_result_object, get_iterator, get_method, get_property, iterator_close, to_boolean}, Runtime, Value}};
+
+use super::{await_expression::{async_iterator_close, await_value, get_async_iterator}, expression::parse_expression};
+
+pub fn parse_yield_expression(runtime: &mut Runtime, expression: &YieldExpression) -> RuntimeResult<Value> {
+    if let (true, Some(argument)) = (expression.delegate, &expression.argument) {
+        return match expression.is_async {
+            true => async_yield_delegate(runtime, argument),
+            false => yield_delegate(runtime, argument),
+        };
+    }
+
+    let value = match &expression.argument {
+        Some(argument) => parse_expression(runtime, argument)?,
+        None => Value::undefined(),
+    };
+
+    // AsyncGeneratorYield, the generator makes the result object once the value is handed out
+    if expression.is_async {
+        let value = await_value(runtime, value)?;
+
+        return match async_generator_suspend(runtime, value) {
+            ResumeAction::Next(value) => Ok(value),
+            ResumeAction::Throw(error) => Err(error),
+            ResumeAction::Return(value) => Err(generator_return(value)),
+        };
+    }
+
+    let result = create_iter_result_object(runtime, value, false);
+
+    match suspend(runtime, Suspension::Yield(result)) {
+        ResumeAction::Next(value) => Ok(value),
+        ResumeAction::Throw(error) => Err(error),
+        ResumeAction::Return(value) => Err(generator_return(value)),
+    }
+}
+
+// `yield*` hands out the results of the inner iterator as they are, and passes `next`, `throw` and `return` on to it.
+// The value of the expression is the value the inner iterator finishes with.
+fn yield_delegate(runtime: &mut Runtime, argument: &Expression) -> RuntimeResult<Value> {
+    let iterable = parse_expression(runtime, argument)?;
+    let record = get_iterator(runtime, &iterable)?;
+    let mut received = ResumeAction::Next(Value::Undefined);
+
+    loop {
+        // A `return` that the inner iterator finishes makes the generator return as well
+        let (result, returning) = match received {
+            ResumeAction::Next(value) => (call(runtime, &record.next_method, record.iterator.clone(), vec![value])?, false),
+            ResumeAction::Throw(error) => match get_method(runtime, &record.iterator, &PropertyKey::from("throw"))? {
+                Some(throw) => (call(runtime, &throw, record.iterator.clone(), vec![error])?, false),
+                // The inner iterator cannot take the exception, so it is closed and the delegation fails
+                None => {
+                    iterator_close(runtime, &record, Ok(()))?;
+                    return runtime.type_error("The iterator does not provide a 'throw' method");
+                },
+            },
+            ResumeAction::Return(value) => match get_method(runtime, &record.iterator, &PropertyKey::from("return"))? {
+                Some(method) => (call(runtime, &method, record.iterator.clone(), vec![value])?, true),
+                None => return Err(generator_return(value)),
+            },
+        };
+
+        if !matches!(result, Value::Object(_)) {
+            return runtime.type_error(&format!("Iterator result {} is not an object", result.to_display_string()));
+        }
+
+        let done = get_property(runtime, &result, &PropertyKey::from("done"))?;
+
+        if to_boolean(&done) {
+            let value = get_property(runtime, &result, &PropertyKey::from("value"))?;
+
+            return match returning {
+                true => Err(generator_return(value)),
+                false => Ok(value),
+            };
+        }
+
+        received = suspend(runtime, Suspension::Yield(result));
+    }
+}
+
+// The async version of `yield*`, which takes an async iterator and awaits what it gives back.
+// A sync iterator works as well, its values are awaited instead.
+fn async_yield_delegate(runtime: &mut Runtime, argument: &Expression) -> RuntimeResult<Value> {
+    let iterable = parse_expression(runtime, argument)?;
+    let iterator = get_async_iterator(runtime, &iterable)?;
+    let record = &iterator.record;
+    let mut received = ResumeAction::Next(Value::Undefined);
+
+    loop {
+        let (result, returning) = match received {
+            ResumeAction::Next(value) => (call(runtime, &record.next_method, record.iterator.clone(), vec![value])?, false),
+            ResumeAction::Throw(error) => match get_method(runtime, &record.iterator, &PropertyKey::from("throw"))? {
+                Some(throw) => (call(runtime, &throw, record.iterator.clone(), vec![error])?, false),
+                None => {
+                    async_iterator_close(runtime, &iterator, Ok(()))?;
+                    return runtime.type_error("The iterator does not provide a 'throw' method");
+                },
+            },
+            ResumeAction::Return(value) => match get_method(runtime, &record.iterator, &PropertyKey::from("return"))? {
+                Some(method) => (call(runtime, &method, record.iterator.clone(), vec![value])?, true),
+                None => return Err(generator_return(await_value(runtime, value)?)),
+            },
+        };
+
+        let result = match iterator.from_sync {
+            true => result,
+            false => await_value(runtime, result)?,
+        };
+
+        if !matches!(result, Value::Object(_)) {
+            return runtime.type_error(&format!("Iterator result {} is not an object", result.to_display_string()));
+        }
+
+        let done = get_property(runtime, &result, &PropertyKey::from("done"))?;
+        let value = get_property(runtime, &result, &PropertyKey::from("value"))?;
+
+        let value = match iterator.from_sync {
+            true => await_value(runtime, value)?,
+            false => value,
+        };
+
+        if to_boolean(&done) {
+            return match returning {
+                true => Err(generator_return(value)),
+                false => Ok(value),
+            };
+        }
+
+        received = async_generator_suspend(runtime, value);
+    }
+}
+
+// Hands out a value of an async generator. The value of a `return` call is awaited before the generator returns,
+// and a rejection is thrown in its place.
+fn async_generator_suspend(runtime: &mut Runtime, value: Value) -> ResumeAction {
+    match suspend(runtime, Suspension::Yield(value)) {
+        ResumeAction::Return(value) => match await_value(runtime, value) {
+            Ok(value) => ResumeAction::Return(value),
+            Err(error) => ResumeAction::Throw(error),
+        },
+        action => action,
+    }
+}
@@ -0,0 +1,116 @@
+use crate::ast::nodes::{Expression, MemberExpression};
+
+use super::{nodes::RuntimeResult, environment::{this_environment, this_value}, object::{get, private_get, private_set, set, PropertyKey}, operations::{get_property, set_property, to_property_key}, parsers::expression::parse_expression, Runtime, Value};
+
+// What the left side of an assignment resolves to, evaluated once so `a[i++] += 1` only increments once
+pub enum Reference {
+    Binding(String),
+    Property { base: Value, key: PropertyKey },
+    // `a.#name`, the key is the symbol the enclosing class created for `#name`
+    PrivateProperty { base: Value, key: PropertyKey },
+    // `super.name`, the lookup starts at `base` but accessors run with the current `this`
+    SuperProperty { base: Value, key: PropertyKey, this_value: Value },
+}
+
+impl Reference {
+    // GetThisValue, the `this` a method called through the reference receives
+    pub fn this_value(&self) -> Value {
+        match self {
+            Reference::Binding(_) => Value::Undefined,
+            Reference::Property { base, .. } | Reference::PrivateProperty { base, .. } => base.clone(),
+            Reference::SuperProperty { this_value, .. } => this_value.clone(),
+        }
+    }
+}
+
+pub fn evaluate_reference(runtime: &mut Runtime, target: &Expression) -> RuntimeResult<Reference> {
+    match target {
+        Expression::Identifier(identifier) => Ok(Reference::Binding(identifier.name.clone())),
+        Expression::MemberExpression(member) => evaluate_member(runtime, member),
+        _ => runtime.syntax_error(&format!("Invalid assignment target at {:?}", target.range())),
+    }
+}
+
+// The object and the property key of `a.b`, `a[b]`, `a.#b` or `super.b`
+pub fn evaluate_member(runtime: &mut Runtime, member: &MemberExpression) -> RuntimeResult<Reference> {
+    if let Expression::Super(_) = &member.object {
+        let this_value = this_value(runtime, &runtime.environment)?;
+        let key = member_key(runtime, member)?;
+
+        return Ok(Reference::SuperProperty { base: super_base(runtime)?, key, this_value });
+    }
+
+    let base = parse_expression(runtime, &member.object)?;
+
+    member_reference(runtime, member, base)
+}
+
+// The reference for a member of an object that was already evaluated, for the links of an optional chain
+pub fn member_reference(runtime: &mut Runtime, member: &MemberExpression, base: Value) -> RuntimeResult<Reference> {
+    if let Expression::PrivateIdentifier(identifier) = &member.property {
+        let key = match runtime.variable(&identifier.name)? {
+            Some(Value::Symbol(symbol)) => PropertyKey::Symbol(symbol),
+            _ => return runtime.syntax_error(&format!("Private field '{}' must be declared in an enclosing class", identifier.name)),
+        };
+
+        return Ok(Reference::PrivateProperty { base, key });
+    }
+
+    let key = member_key(runtime, member)?;
+
+    Ok(Reference::Property { base, key })
+}
+
+fn member_key(runtime: &mut Runtime, member: &MemberExpression) -> RuntimeResult<PropertyKey> {
+    match (&member.property, member.computed) {
+        (Expression::Identifier(identifier), false) => Ok(PropertyKey::from(identifier.name.as_str())),
+        (property, _) => {
+            let property = parse_expression(runtime, property)?;
+            to_property_key(runtime, &property)
+        },
+    }
+}
+
+// The prototype of the home object of the running method, where `super.name` starts looking
+fn super_base(runtime: &Runtime) -> RuntimeResult<Value> {
+    let environment = this_environment(&runtime.environment);
+    let home_object = environment.borrow().home_object.clone();
+
+    match home_object {
+        Some(home_object) => Ok(match &home_object.borrow().prototype {
+            Some(prototype) => Value::Object(prototype.clone()),
+            None => Value::Null,
+        }),
+        None => runtime.syntax_error("'super' keyword unexpected here"),
+    }
+}
+
+pub fn get_value(runtime: &mut Runtime, reference: &Reference) -> RuntimeResult<Value> {
+    match reference {
+        Reference::Binding(name) => match runtime.variable(name)? {
+            Some(value) => Ok(value),
+            None => runtime.reference_error(&format!("{} is not defined", name)),
+        },
+        Reference::Property { base, key } => get_property(runtime, base, key),
+        Reference::PrivateProperty { base, key } => private_get(runtime, base, key),
+        Reference::SuperProperty { base: Value::Object(base), key, this_value } => get(runtime, base, key, this_value),
+        Reference::SuperProperty { key, .. } => runtime.type_error(&format!("Cannot read properties of null (reading '{}')", key)),
+    }
+}
+
+pub fn put_value(runtime: &mut Runtime, reference: Reference, value: Value) -> RuntimeResult<()> {
+    match reference {
+        Reference::Binding(name) => runtime.assign_variable(&name, value),
+        Reference::Property { base, key } => set_property(runtime, &base, key, value),
+        Reference::PrivateProperty { base, key } => private_set(runtime, &base, &key, value),
+        Reference::SuperProperty { base: Value::Object(base), key, this_value } => {
+            // Class code is strict, so a failed assignment is an error
+            if !set(runtime, &base, key.clone(), value, &this_value)? {
+                return runtime.type_error(&format!("Cannot assign to read only property '{}' of {}", key, this_value.to_display_string()));
+            }
+
+            Ok(())
+        },
+        Reference::SuperProperty { key, .. } => runtime.type_error(&format!("Cannot set properties of null (setting '{}')", key)),
+    }
+}
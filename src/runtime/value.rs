@@ -0,0 +1,323 @@
+use std::{fmt, rc::Rc};
+
+use num_bigint::BigInt;
+
+use crate::util::{is_identifier, is_identifier_part};
+
+use super::{builtins::{error_text, PromiseState}, nodes::{FunctionCall, FunctionCallType}, object::{find_property, Object, ObjectKind, ObjectRef, Property, PropertyKey}, operations::number_to_string};
+
+#[derive(Clone)]
+pub enum Value {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    BigInt(Rc<BigInt>),
+    String(Rc<str>),
+    Symbol(Rc<Symbol>),
+    Object(ObjectRef),
+}
+
+// Symbols are compared by identity, the description is only informative
+pub struct Symbol {
+    pub description: Option<String>,
+}
+
+impl Value {
+    pub fn undefined() -> Self {
+        Value::Undefined
+    }
+
+    pub fn string(value: &str) -> Self {
+        Value::String(Rc::from(value))
+    }
+
+    pub fn function(function: FunctionCall, prototype: &ObjectRef) -> Self {
+        Value::Object(Object::new_function(function, prototype))
+    }
+
+    pub fn as_function(&self) -> Option<Rc<FunctionCall>> {
+        match self {
+            Value::Object(object) => object.borrow().function(),
+            _ => None,
+        }
+    }
+
+    // The text `console.log` prints for a value
+    pub fn to_display_string(&self) -> String {
+        self.display(&mut Vec::new(), false)
+    }
+
+    // Strings are quoted inside objects, an object that contains itself shows as [Circular]
+    fn display(&self, seen: &mut Vec<ObjectRef>, nested: bool) -> String {
+        match self {
+            Value::Undefined => "undefined".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Boolean(value) => value.to_string(),
+            Value::Number(value) => number_to_string(*value),
+            Value::BigInt(value) => format!("{}n", value),
+            Value::String(value) if nested => format!("'{}'", value.replace('\'', "\\'")),
+            Value::String(value) => value.to_string(),
+            Value::Symbol(symbol) => format!("Symbol({})", symbol.description.clone().unwrap_or_default()),
+            Value::Object(object) => {
+                if seen.iter().any(|seen| Rc::ptr_eq(seen, object)) {
+                    return "[Circular]".to_string();
+                }
+
+                seen.push(object.clone());
+                let output = display_object(object, seen);
+                seen.pop();
+
+                output
+            },
+        }
+    }
+}
+
+fn display_object(object: &ObjectRef, seen: &mut Vec<ObjectRef>) -> String {
+    if object.borrow().is_array() {
+        return display_array(object, seen);
+    }
+
+    // Shown like the array it resembles
+    if let ObjectKind::Arguments = object.borrow().kind {
+        return format!("[Arguments] {}", display_array(object, seen));
+    }
+
+    if let ObjectKind::Map(_) | ObjectKind::Set(_) = object.borrow().kind {
+        return display_collection(object, seen);
+    }
+
+    if let ObjectKind::Promise(_) = object.borrow().kind {
+        return display_promise(object, seen);
+    }
+
+    // Objects with a tag are followed by braces even when they have no properties to show
+    let ordinary = !matches!(object.borrow().kind, ObjectKind::Function(_) | ObjectKind::RegExp { .. } | ObjectKind::Error);
+
+    let prefix = match &object.borrow().kind {
+        ObjectKind::Ordinary => constructor_prefix(object),
+        ObjectKind::Function(function) => Some(match &function.function_type {
+            FunctionCallType::SystemCall(_) => format!("[Function: {}] (native)", function.name),
+            FunctionCallType::RuntimeCall(runtime_call) if runtime_call.class.is_some() => class_prefix(object),
+            FunctionCallType::RuntimeCall(_) | FunctionCallType::BoundFunction(_) => match function_name(object).as_str() {
+                "" => "[Function (anonymous)]".to_string(),
+                name => format!("[Function: {}]", name),
+            },
+        }),
+        ObjectKind::Array | ObjectKind::Arguments => unreachable!(),
+        ObjectKind::RegExp { pattern, flags } => Some(format!("/{}/{}", pattern, flags)),
+        ObjectKind::Error => Some(error_prefix(object)),
+        ObjectKind::Generator(_) => Some("Object [Generator]".to_string()),
+        ObjectKind::AsyncGenerator(_) => Some("Object [AsyncGenerator]".to_string()),
+        ObjectKind::ArrayIterator { .. } => Some("Object [Array Iterator]".to_string()),
+        ObjectKind::StringIterator { .. } => Some("Object [String Iterator]".to_string()),
+        ObjectKind::MapIterator { .. } => Some("Object [Map Iterator]".to_string()),
+        ObjectKind::SetIterator { .. } => Some("Object [Set Iterator]".to_string()),
+        ObjectKind::ModuleNamespace(_) => Some("[Module: null prototype]".to_string()),
+        ObjectKind::GeneratorReturn(_) | ObjectKind::Map(_) | ObjectKind::Set(_) | ObjectKind::Promise(_) => unreachable!(),
+    };
+
+    let entries = display_properties(object, seen, |_| true);
+
+    match (prefix, entries.is_empty()) {
+        (Some(prefix), true) if ordinary => format!("{} {{}}", prefix),
+        (Some(prefix), true) => prefix,
+        (Some(prefix), false) => format!("{} {{ {} }}", prefix, entries.join(", ")),
+        (None, true) => "{}".to_string(),
+        (None, false) => format!("{{ {} }}", entries.join(", ")),
+    }
+}
+
+// Elements in index order with runs of holes collapsed, followed by any other enumerable properties
+fn display_array(array: &ObjectRef, seen: &mut Vec<ObjectRef>) -> String {
+    let length = array.borrow().array_length();
+    let mut entries = Vec::new();
+
+    let elements: Vec<(u32, Property)> = {
+        let array = array.borrow();
+
+        array.own_property_keys().into_iter()
+            .filter_map(|key| Some((key.array_index()?, array.get_own_property(&key)?)))
+            .collect()
+    };
+
+    let mut next = 0;
+
+    for (index, property) in elements {
+        push_holes(&mut entries, index - next);
+        entries.push(display_property_value(property, seen));
+        next = index + 1;
+    }
+
+    push_holes(&mut entries, length - next);
+
+    entries.extend(display_properties(array, seen, |key| key.array_index().is_none()));
+
+    match entries.is_empty() {
+        true => "[]".to_string(),
+        false => format!("[ {} ]", entries.join(", ")),
+    }
+}
+
+// `Map(1) { 'a' => 1 }` and `Set(1) { 'a' }`, followed by any enumerable properties
+fn display_collection(collection: &ObjectRef, seen: &mut Vec<ObjectRef>) -> String {
+    let (name, entries) = match &collection.borrow().kind {
+        ObjectKind::Map(data) => ("Map", data.iter().map(|(key, value)| (key.clone(), Some(value.clone()))).collect::<Vec<_>>()),
+        ObjectKind::Set(data) => ("Set", data.iter().map(|(value, _)| (value.clone(), None)).collect()),
+        _ => unreachable!(),
+    };
+
+    let size = entries.len();
+
+    let mut entries: Vec<String> = entries.into_iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{} => {}", key.display(seen, true), value.display(seen, true)),
+            None => key.display(seen, true),
+        })
+        .collect();
+
+    entries.extend(display_properties(collection, seen, |_| true));
+
+    match entries.is_empty() {
+        true => format!("{}({}) {{}}", name, size),
+        false => format!("{}({}) {{ {} }}", name, size, entries.join(", ")),
+    }
+}
+
+// `Promise { 1 }`, `Promise { <pending> }` or `Promise { <rejected> reason }`, followed by any enumerable properties
+fn display_promise(promise: &ObjectRef, seen: &mut Vec<ObjectRef>) -> String {
+    let state = match &promise.borrow().kind {
+        ObjectKind::Promise(data) => data.state.clone(),
+        _ => unreachable!(),
+    };
+
+    let mut entries = vec![match state {
+        PromiseState::Pending => "<pending>".to_string(),
+        PromiseState::Fulfilled(value) => value.display(seen, true),
+        PromiseState::Rejected(reason) => format!("<rejected> {}", reason.display(seen, true)),
+    }];
+
+    entries.extend(display_properties(promise, seen, |_| true));
+
+    format!("Promise {{ {} }}", entries.join(", "))
+}
+
+fn push_holes(entries: &mut Vec<String>, holes: u32) {
+    match holes {
+        0 => {},
+        1 => entries.push("<1 empty item>".to_string()),
+        holes => entries.push(format!("<{} empty items>", holes)),
+    }
+}
+
+// Only enumerable own properties are shown, getters are not run
+fn display_properties(object: &ObjectRef, seen: &mut Vec<ObjectRef>, filter: impl Fn(&PropertyKey) -> bool) -> Vec<String> {
+    let properties: Vec<(PropertyKey, Property)> = {
+        let object = object.borrow();
+
+        object.own_property_keys().into_iter()
+            .filter(|key| filter(key))
+            .filter_map(|key| object.get_own_property(&key).map(|property| (key, property)))
+            .filter(|(_, property)| property.enumerable())
+            .collect()
+    };
+
+    properties.into_iter()
+        .map(|(key, property)| format!("{}: {}", display_key(&key), display_property_value(property, seen)))
+        .collect()
+}
+
+fn display_property_value(property: Property, seen: &mut Vec<ObjectRef>) -> String {
+    match property {
+        Property::Data { value, .. } => value.display(seen, true),
+        Property::Accessor { get: Some(_), set: Some(_), .. } => "[Getter/Setter]".to_string(),
+        Property::Accessor { get: Some(_), .. } => "[Getter]".to_string(),
+        Property::Accessor { .. } => "[Setter]".to_string(),
+    }
+}
+
+// Instances are labelled with the constructor their prototype points back to, plain objects are not
+fn constructor_prefix(object: &ObjectRef) -> Option<String> {
+    let prototype = match &object.borrow().prototype {
+        Some(prototype) => prototype.clone(),
+        None => return Some("[Object: null prototype]".to_string()),
+    };
+
+    let constructor = match prototype.borrow().get_own_property(&PropertyKey::from("constructor")) {
+        Some(Property::Data { value: Value::Object(constructor), .. }) if constructor.borrow().function().is_some() => constructor,
+        _ => return None,
+    };
+
+    Some(function_name(&constructor)).filter(|name| name != "Object")
+}
+
+// `TypeError: x is not a function`, read without running getters
+fn error_prefix(error: &ObjectRef) -> String {
+    let text = |name: &str| match find_property(error, &PropertyKey::from(name)) {
+        Some(Property::Data { value: Value::String(text), .. }) => text.to_string(),
+        _ => String::new(),
+    };
+
+    error_text(&text("name"), &text("message"))
+}
+
+// `[class Dog extends Animal]`, the parent is read from the constructor's prototype
+fn class_prefix(class: &ObjectRef) -> String {
+    let name = match function_name(class) {
+        name if name.is_empty() => "(anonymous)".to_string(),
+        name => name,
+    };
+
+    let derived = matches!(class.borrow().function(), Some(function) if matches!(
+        &function.function_type,
+        FunctionCallType::RuntimeCall(runtime_call) if runtime_call.class.as_ref().is_some_and(|class| class.derived)
+    ));
+
+    if !derived {
+        return format!("[class {}]", name);
+    }
+
+    let parent = class.borrow().prototype.clone().filter(|parent| parent.borrow().function().is_some());
+
+    match parent {
+        Some(parent) => format!("[class {} extends {}]", name, function_name(&parent)),
+        None => format!("[class {} extends null]", name),
+    }
+}
+
+// The `name` property when it holds a string, functions named after they were created only have that
+fn function_name(function: &ObjectRef) -> String {
+    let function = function.borrow();
+
+    match function.get_own_property(&PropertyKey::from("name")) {
+        Some(Property::Data { value: Value::String(name), .. }) => name.to_string(),
+        _ => function.function().map(|function| function.name.clone()).unwrap_or_default(),
+    }
+}
+
+// Keys that are valid identifiers are printed bare, others are quoted
+fn display_key(key: &PropertyKey) -> String {
+    match key {
+        PropertyKey::Symbol(_) => format!("[{}]", key),
+        PropertyKey::String(name) => {
+            let mut chars = name.chars();
+            let is_identifier = chars.next().is_some_and(|char| is_identifier(&char)) && chars.all(|char| is_identifier_part(&char));
+
+            if is_identifier {
+                name.to_string()
+            } else {
+                format!("'{}'", name.replace('\'', "\\'"))
+            }
+        },
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(value) => write!(f, "{:?}", value),
+            _ => write!(f, "{}", self.to_display_string()),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 mod tokenizer;
 mod parsers;
 
@@ -12,15 +13,22 @@ pub struct Token {
 #[derive(Debug, PartialEq)]
 pub enum TokenType {
     Identifier,
+    // `#name` inside a class body
+    PrivateName,
     Operator,
     Number,
+    BigInt,
     String,
+    Boolean,
+    Null,
+    RegExp,
     Parenthesis,
     CurlyBraces,
+    SquareBrackets,
     Separator,
     Terminator,
 }
 
-pub fn tokenize(file: &String) -> Vec<Token> {
+pub fn tokenize(file: &str) -> Vec<Token> {
     tokenizer::tokenize(file)
 }
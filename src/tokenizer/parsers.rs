@@ -5,13 +5,16 @@ use crate::util::{
     is_curly_brace,
     is_escape_char,
     is_identifier,
+    is_identifier_part,
     is_number,
     is_operator,
     is_parenthesis,
     is_separator,
+    is_square_bracket,
     is_string_delimiter,
     is_terminator,
-    is_whitespace
+    is_whitespace,
+    OPERATORS
 };
 
 use super::{Token, TokenType, tokenizer::Tokenizer};
@@ -39,8 +42,8 @@ pub fn parse_identifier(tokenizer: &mut Tokenizer) -> Option<Token> {
 
     let mut consumed = tokenizer.consume();
 
-    while consumed.is_some() && is_identifier(&consumed.unwrap()) {
-        value.push(consumed.unwrap().clone());
+    while consumed.is_some() && is_identifier_part(consumed.unwrap()) {
+        value.push(*consumed.unwrap());
 
         consumed = tokenizer.consume();
     }
@@ -49,44 +52,137 @@ pub fn parse_identifier(tokenizer: &mut Tokenizer) -> Option<Token> {
 
     let end = tokenizer.index;
 
+    // `true`, `false` and `null` are literals, not identifiers
+    let token_type = match value.as_str() {
+        "true" | "false" => TokenType::Boolean,
+        "null" => TokenType::Null,
+        _ => TokenType::Identifier,
+    };
+
     Some(Token {
-        token_type: TokenType::Identifier,
+        token_type,
         raw_value: value.clone(),
         value,
         range: (start, end)
     })
 }
 
+// The name keeps its `#`, so it can never clash with an identifier
+pub fn parse_private_name(tokenizer: &mut Tokenizer) -> Option<Token> {
+    if *tokenizer.token().unwrap() != '#' || !tokenizer.peek().is_some_and(is_identifier) {
+        return None;
+    }
+
+    let start = tokenizer.index;
+    tokenizer.consume(); // Skip #
+
+    let name = parse_identifier(tokenizer).unwrap();
+    let value = format!("#{}", name.value);
+
+    Some(Token {
+        token_type: TokenType::PrivateName,
+        raw_value: value.clone(),
+        value,
+        range: (start, name.range.1)
+    })
+}
+
 pub fn parse_number(tokenizer: &mut Tokenizer) -> Option<Token> {
     let token = tokenizer.token().unwrap();
 
-    if !is_number(token) {
+    // A leading dot only starts a number when a digit follows, `.5`
+    let is_leading_dot = *token == '.' && tokenizer.peek().is_some_and(is_number);
+
+    if !is_number(token) && !is_leading_dot {
         return None;
     }
 
     let mut value = String::new();
     let start = tokenizer.index;
 
-    let mut consumed = tokenizer.consume();
+    let radix = match (token, tokenizer.peek()) {
+        ('0', Some('x' | 'X')) => Some(16),
+        ('0', Some('o' | 'O')) => Some(8),
+        ('0', Some('b' | 'B')) => Some(2),
+        _ => None,
+    };
 
-    while consumed.is_some() && is_number(&consumed.unwrap()) {
-        value.push(consumed.unwrap().clone());
+    if let Some(radix) = radix {
+        value.push(*tokenizer.consume().unwrap());
+        value.push(*tokenizer.consume().unwrap());
 
-        consumed = tokenizer.consume();
+        if consume_digits(tokenizer, &mut value, radix) == 0 {
+            panic!("Invalid or unexpected token at index: {}", start);
+        }
+    } else {
+        consume_digits(tokenizer, &mut value, 10);
+
+        // Fraction
+        if tokenizer.token() == Some(&'.') {
+            value.push(*tokenizer.consume().unwrap());
+            consume_digits(tokenizer, &mut value, 10);
+        }
+
+        // Exponent
+        if matches!(tokenizer.token(), Some('e') | Some('E')) {
+            let sign = matches!(tokenizer.peek(), Some('+') | Some('-'));
+            let digit = tokenizer.file_content.get(tokenizer.index + if sign { 2 } else { 1 });
+
+            if digit.is_some_and(is_number) {
+                value.push(*tokenizer.consume().unwrap());
+
+                if sign {
+                    value.push(*tokenizer.consume().unwrap());
+                }
+
+                consume_digits(tokenizer, &mut value, 10);
+            }
+        }
     }
 
-    tokenizer.walk_back();
+    // BigInts are integers with an `n` suffix, `10n` or `0xFFn`
+    let is_bigint = tokenizer.token() == Some(&'n') && (radix.is_some() || value.chars().all(|char| char.is_ascii_digit()));
+
+    if is_bigint {
+        tokenizer.consume();
+    }
+
+    if tokenizer.token().is_some_and(is_identifier_part) {
+        panic!("Invalid or unexpected token at index: {}", tokenizer.index);
+    }
 
     let end = tokenizer.index;
 
     Some(Token {
-        token_type: TokenType::Number,
-        raw_value: value.clone(),
+        token_type: if is_bigint { TokenType::BigInt } else { TokenType::Number },
+        raw_value: tokenizer.file_content[start..end].iter().collect(),
         value,
         range: (start, end)
     })
 }
 
+// Numeric separators may only sit between two digits, they are left out of the value
+fn consume_digits(tokenizer: &mut Tokenizer, value: &mut String, radix: u32) -> usize {
+    let mut count = 0;
+
+    while let Some(token) = tokenizer.token() {
+        if *token == '_' && count > 0 && tokenizer.peek().is_some_and(|next| next.is_digit(radix)) {
+            tokenizer.consume();
+            continue;
+        }
+
+        if !token.is_digit(radix) {
+            break;
+        }
+
+        value.push(*token);
+        tokenizer.consume();
+        count += 1;
+    }
+
+    count
+}
+
 pub fn parse_separator(tokenizer: &mut Tokenizer) -> Option<Token> {
     let token = tokenizer.token().unwrap();
 
@@ -95,10 +191,18 @@ pub fn parse_separator(tokenizer: &mut Tokenizer) -> Option<Token> {
     }
 
     let start = tokenizer.index;
-    let value = tokenizer.consume()
+    let mut value = tokenizer.consume()
         .unwrap()
         .clone()
         .to_string();
+
+    // `...` is a single token, for spread elements
+    if value == "." && tokenizer.token() == Some(&'.') && tokenizer.peek() == Some(&'.') {
+        tokenizer.consume();
+        tokenizer.consume();
+        value.push_str("..");
+    }
+
     let end = tokenizer.index;
 
     Some(Token {
@@ -175,6 +279,28 @@ pub fn parse_curly_brace(tokenizer: &mut Tokenizer) -> Option<Token> {
     })
 }
 
+pub fn parse_square_bracket(tokenizer: &mut Tokenizer) -> Option<Token> {
+    let token = tokenizer.token().unwrap();
+
+    if !is_square_bracket(token) {
+        return None;
+    }
+
+    let start = tokenizer.index;
+    let value = tokenizer.consume()
+        .unwrap()
+        .clone()
+        .to_string();
+    let end = tokenizer.index;
+
+    Some(Token {
+        token_type: TokenType::SquareBrackets,
+        raw_value: value.clone(),
+        value,
+        range: (start, end)
+    })
+}
+
 pub fn parse_operator(tokenizer: &mut Tokenizer) -> Option<Token> {
     let token = tokenizer.token().unwrap();
 
@@ -182,18 +308,23 @@ pub fn parse_operator(tokenizer: &mut Tokenizer) -> Option<Token> {
         return None;
     }
 
-    let mut value = String::new();
     let start = tokenizer.index;
+    let remaining = &tokenizer.file_content[start..];
 
-    let mut consumed = tokenizer.consume();
-
-    while consumed.is_some() && is_operator(&consumed.unwrap()) {
-        value.push(consumed.unwrap().clone());
+    let mut value = OPERATORS.iter()
+        .find(|operator| {
+            operator.len() <= remaining.len() &&
+            operator.chars().zip(remaining.iter()).all(|(a, b)| a == *b)
+        })
+        .unwrap()
+        .to_string();
 
-        consumed = tokenizer.consume();
+    // `a?.5:b` is a `?` followed by the number .5, not optional chaining
+    if value == "?." && remaining.get(2).is_some_and(is_number) {
+        value = "?".to_string();
     }
 
-    tokenizer.walk_back();
+    tokenizer.index += value.len();
 
     let end = tokenizer.index;
 
@@ -267,65 +398,197 @@ fn is_end_block_comment(tokenizer: &Tokenizer) -> bool {
 
 pub fn parse_string(tokenizer: &mut Tokenizer) -> Option<Token> {
     let token = tokenizer.token();
-    let delimiter = is_start_string(token.unwrap());
-
-    if delimiter.is_none() {
-        return None;
-    }
+    let delimiter = is_start_string(token.unwrap())?;
 
-    let mut raw_value = String::new();
     let mut value = String::new();
     let start = tokenizer.index;
-    
-    let delimiter = delimiter.unwrap();
-    raw_value.push(delimiter.clone());
 
-    let mut consumed = tokenizer.consume();
+    tokenizer.consume();
 
-    while consumed.is_some() && !is_end_string(tokenizer, delimiter) {
-        raw_value.push(tokenizer.token().unwrap().clone());
-        value.push(tokenizer.token().unwrap().clone());
+    loop {
+        let char = match tokenizer.consume() {
+            Some(char) => *char,
+            None => panic!("Invalid or unexpected token, unterminated string at index: {}", start),
+        };
 
-        // Do not let the escape char go into the normal value string
-        if is_escape_char(tokenizer.token().unwrap()) &&
-            is_string_delimiter(tokenizer.peek().unwrap_or(&' ')) {
-            value.pop();
+        if char == delimiter {
+            break;
         }
 
-        consumed = tokenizer.consume();
-    }
+        if char == '\n' || char == '\r' {
+            panic!("Invalid or unexpected token, unterminated string at index: {}", start);
+        }
 
-    tokenizer.consume();
-    raw_value.push(delimiter.clone());
+        if is_escape_char(&char) {
+            parse_escape_sequence(tokenizer, &mut value);
+            continue;
+        }
+
+        value.push(char);
+    }
 
     let end = tokenizer.index;
 
     Some(Token {
         token_type: TokenType::String,
-        raw_value,
+        raw_value: tokenizer.file_content[start..end].iter().collect(),
         value,
         range: (start, end)
     })
 }
 
+// Reads what follows a backslash and pushes the character it stands for
+fn parse_escape_sequence(tokenizer: &mut Tokenizer, value: &mut String) {
+    let index = tokenizer.index;
+    let char = match tokenizer.consume() {
+        Some(char) => *char,
+        None => panic!("Invalid or unexpected token at index: {}", index),
+    };
+
+    let escaped = match char {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        'b' => '\u{8}',
+        'f' => '\u{C}',
+        'v' => '\u{B}',
+        '0' if !tokenizer.token().is_some_and(is_number) => '\0',
+        'x' => parse_code_point(tokenizer, 2, index),
+        'u' if tokenizer.token() == Some(&'{') => {
+            tokenizer.consume();
+            let mut digits = String::new();
+
+            while let Some(char) = tokenizer.consume() {
+                if *char == '}' {
+                    break;
+                }
+
+                digits.push(*char);
+            }
+
+            code_point_to_char(u32::from_str_radix(&digits, 16).ok(), index)
+        },
+        'u' => {
+            let high = parse_code_point(tokenizer, 4, index) as u32;
+
+            // Surrogate pairs written as two escapes form a single character
+            let is_pair = (0xD800..0xDC00).contains(&high) &&
+                tokenizer.token() == Some(&'\\') && tokenizer.peek() == Some(&'u');
+
+            if is_pair {
+                let position = tokenizer.index;
+                tokenizer.index += 2;
+
+                let low = parse_code_point(tokenizer, 4, index) as u32;
+
+                if (0xDC00..0xE000).contains(&low) {
+                    value.push(code_point_to_char(Some(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)), index));
+                    return;
+                }
+
+                tokenizer.index = position;
+            }
+
+            char::from_u32(high).unwrap_or('\u{FFFD}')
+        },
+        // A backslash before a line break continues the string on the next line
+        '\r' => {
+            if tokenizer.token() == Some(&'\n') {
+                tokenizer.consume();
+            }
+
+            return;
+        },
+        '\n' | '\u{2028}' | '\u{2029}' => return,
+        _ => char,
+    };
+
+    value.push(escaped);
+}
+
+fn parse_code_point(tokenizer: &mut Tokenizer, length: usize, index: usize) -> char {
+    let digits: String = tokenizer.file_content.iter().skip(tokenizer.index).take(length).collect();
+
+    if digits.len() != length || !digits.chars().all(|char| char.is_ascii_hexdigit()) {
+        panic!("Invalid hexadecimal escape sequence at index: {}", index);
+    }
+
+    tokenizer.index += length;
+
+    // Lone surrogates cannot be stored in a Rust string
+    char::from_u32(u32::from_str_radix(&digits, 16).unwrap()).unwrap_or('\u{FFFD}')
+}
+
+fn code_point_to_char(code_point: Option<u32>, index: usize) -> char {
+    match code_point.filter(|code_point| *code_point <= 0x10FFFF) {
+        Some(code_point) => char::from_u32(code_point).unwrap_or('\u{FFFD}'),
+        None => panic!("Undefined Unicode code-point at index: {}", index),
+    }
+}
+
 fn is_start_string(token: &char) -> Option<char> {
     if is_string_delimiter(token) {
-        return Some(token.clone());
+        return Some(*token);
     }
 
     None
 }
 
-fn is_end_string(tokenizer: &Tokenizer, delimiter: char) -> bool {
-    if is_escaped(tokenizer) { return false; }
+// A slash starts a regular expression wherever an expression may start, otherwise it divides
+pub fn parse_regexp(tokenizer: &mut Tokenizer, previous: Option<&Token>) -> Option<Token> {
+    if tokenizer.token() != Some(&'/') || !is_regexp_allowed(previous) {
+        return None;
+    }
 
-    let token = tokenizer.token().unwrap();
+    let start = tokenizer.index;
+    let mut in_class = false;
+
+    tokenizer.consume();
 
-    token.clone() == delimiter
+    loop {
+        let char = match tokenizer.consume() {
+            Some(char) if *char != '\n' && *char != '\r' => *char,
+            _ => panic!("Invalid regular expression: missing / at index: {}", start),
+        };
+
+        match char {
+            '\\' => { tokenizer.consume(); },
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => break,
+            _ => {}
+        }
+    }
+
+    while tokenizer.token().is_some_and(is_identifier_part) {
+        tokenizer.consume();
+    }
+
+    let end = tokenizer.index;
+    let value: String = tokenizer.file_content[start..end].iter().collect();
+
+    Some(Token {
+        token_type: TokenType::RegExp,
+        raw_value: value.clone(),
+        value,
+        range: (start, end)
+    })
 }
 
-fn is_escaped(tokenizer: &Tokenizer) -> bool {
-    tokenizer.peek_back()
-        .unwrap_or(&' ')
-        .clone() == '\\'
-}
\ No newline at end of file
+fn is_regexp_allowed(previous: Option<&Token>) -> bool {
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return true,
+    };
+
+    match previous.token_type {
+        TokenType::Identifier => matches!(
+            previous.value.as_str(),
+            "return" | "typeof" | "instanceof" | "in" | "of" | "new" | "delete" | "void" | "throw" | "case" | "do" | "else" | "yield" | "await"
+        ),
+        TokenType::Operator => !matches!(previous.value.as_str(), "++" | "--"),
+        TokenType::Parenthesis | TokenType::SquareBrackets => previous.value == "(" || previous.value == "[",
+        TokenType::CurlyBraces | TokenType::Separator | TokenType::Terminator => true,
+        _ => false,
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Token, parsers::{parse_block_comments, parse_curly_brace, parse_identifier, parse_line_comment, parse_number, parse_operator, parse_parenthesis, parse_separator, parse_string, parse_terminator, parse_whitespace}};
+use super::{Token, parsers::{parse_block_comments, parse_curly_brace, parse_identifier, parse_line_comment, parse_number, parse_operator, parse_parenthesis, parse_private_name, parse_regexp, parse_separator, parse_square_bracket, parse_string, parse_terminator, parse_whitespace}};
 
 pub struct Tokenizer {
     pub index: usize,
@@ -6,7 +6,7 @@ pub struct Tokenizer {
 }
 
 impl Tokenizer {
-    pub fn new(file_content: &String) -> Tokenizer {
+    pub fn new(file_content: &str) -> Tokenizer {
         Tokenizer {
             index: 0,
             file_content: file_content.chars().collect()
@@ -25,10 +25,6 @@ impl Tokenizer {
         self.file_content.get(self.index + 1)
     }
 
-    pub fn peek_back(&self) -> Option<&char> {
-        self.file_content.get(self.index - 1)
-    }
-
     pub fn walk_back(&mut self) {
         self.index -= 1;
     }
@@ -38,11 +34,11 @@ impl Tokenizer {
 
         self.index += 1;
 
-        return value;
+        value
     }
 }
 
-pub fn tokenize(file_content: &String) -> Vec<Token> {
+pub fn tokenize(file_content: &str) -> Vec<Token> {
     let mut tokens = Vec::new();
     let mut tokenizer = Tokenizer::new(file_content);
 
@@ -59,55 +55,62 @@ pub fn tokenize(file_content: &String) -> Vec<Token> {
             continue;
         }
 
-        let identifier = parse_identifier(&mut tokenizer);
-        if identifier.is_some() {
-            tokens.push(identifier.unwrap());
+        if let Some(identifier) = parse_identifier(&mut tokenizer) {
+            tokens.push(identifier);
+            continue;
+        }
+
+        if let Some(private_name) = parse_private_name(&mut tokenizer) {
+            tokens.push(private_name);
+            continue;
+        }
+
+        if let Some(number) = parse_number(&mut tokenizer) {
+            tokens.push(number);
+            continue;
+        }
+
+        if let Some(string) = parse_string(&mut tokenizer) {
+            tokens.push(string);
             continue;
         }
 
-        let number = parse_number(&mut tokenizer);
-        if number.is_some() {
-            tokens.push(number.unwrap());
+        if let Some(regexp) = parse_regexp(&mut tokenizer, tokens.last()) {
+            tokens.push(regexp);
             continue;
         }
 
-        let string = parse_string(&mut tokenizer);
-        if string.is_some() {
-            tokens.push(string.unwrap());
+        if let Some(operator) = parse_operator(&mut tokenizer) {
+            tokens.push(operator);
             continue;
         }
 
-        let operator = parse_operator(&mut tokenizer);
-        if operator.is_some() {
-            tokens.push(operator.unwrap());
+        if let Some(curly_brace) = parse_curly_brace(&mut tokenizer) {
+            tokens.push(curly_brace);
             continue;
         }
 
-        let curly_brace = parse_curly_brace(&mut tokenizer);
-        if curly_brace.is_some() {
-            tokens.push(curly_brace.unwrap());
+        if let Some(square_bracket) = parse_square_bracket(&mut tokenizer) {
+            tokens.push(square_bracket);
             continue;
         }
 
-        let seperator = parse_separator(&mut tokenizer);
-        if seperator.is_some() {
-            tokens.push(seperator.unwrap());
+        if let Some(seperator) = parse_separator(&mut tokenizer) {
+            tokens.push(seperator);
             continue;
         }
 
-        let parenthesis = parse_parenthesis(&mut tokenizer);
-        if parenthesis.is_some() {
-            tokens.push(parenthesis.unwrap());
+        if let Some(parenthesis) = parse_parenthesis(&mut tokenizer) {
+            tokens.push(parenthesis);
             continue;
         }
 
-        let terminator = parse_terminator(&mut tokenizer);
-        if terminator.is_some() {
-            tokens.push(terminator.unwrap());
+        if let Some(terminator) = parse_terminator(&mut tokenizer) {
+            tokens.push(terminator);
             continue;
         }
 
-        panic!("Unknown token: {} at index: {}", tokenizer.token().unwrap().clone().to_string(), tokenizer.index);
+        panic!("Unknown token: {} at index: {}", tokenizer.token().unwrap(), tokenizer.index);
     }
 
     tokens
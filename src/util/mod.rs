@@ -11,12 +11,16 @@ pub fn is_whitespace(token: &char) -> bool {
 
 pub fn is_identifier(token: &char) -> bool {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"[a-zA-Z]").unwrap();
+        static ref RE: Regex = Regex::new(r"[a-zA-Z_$]").unwrap();
     }
         
     RE.is_match(&(*token).to_string())
 }
 
+pub fn is_identifier_part(token: &char) -> bool {
+    is_identifier(token) || is_number(token)
+}
+
 pub fn is_number(token: &char) -> bool {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"[0-9]").unwrap();
@@ -26,7 +30,7 @@ pub fn is_number(token: &char) -> bool {
 }
 
 pub fn is_separator(token: &char) -> bool {
-    *token == '.' || *token == ','
+    *token == '.' || *token == ',' || *token == ':'
 }
 
 pub fn is_parenthesis(token: &char) -> bool {
@@ -41,11 +45,16 @@ pub fn is_curly_brace(token: &char) -> bool {
     *token == '{' || *token == '}'
 }
 
+pub fn is_square_bracket(token: &char) -> bool {
+    *token == '[' || *token == ']'
+}
+
 pub fn is_operator(token: &char) -> bool {
-    match *token {
+    matches!(
+        *token,
         '=' |
         '>' |
-        '<' | 
+        '<' |
         '!' |
         '+' |
         '-' |
@@ -55,11 +64,19 @@ pub fn is_operator(token: &char) -> bool {
         '&' |
         '|' |
         '^' |
-        '~' => true,
-        _ => false
-    }
+        '~' |
+        '?'
+    )
 }
 
+// All operators, longest first so the tokenizer can match greedily
+pub const OPERATORS: &[&str] = &[
+    ">>>=",
+    "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=",
+    "==", "!=", "<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "**", "<<", ">>", "=>",
+    "=", ">", "<", "!", "+", "-", "/", "*", "%", "&", "|", "^", "~", "?",
+];
+
 pub fn is_escape_char(token: &char) -> bool {
     *token == '\\'
 }